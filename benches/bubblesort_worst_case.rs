@@ -1,5 +1,4 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use oxcart::list::List;
+use criterion::{criterion_group, criterion_main, Criterion};
 use oxcart::arraylist::ArrayList;
 use oxcart::bubblesort::bubblesort;
 
@@ -8,12 +7,8 @@ fn cmp_leq<T: Eq + Ord>(a: &T, b: &T) -> bool {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let mut list: ArrayList<u64> = ArrayList::new();
-
     /* need to reverse to ensure not already in ascending order */
-    for i in (0..1000).rev() {
-        list.append(i);
-    }
+    let mut list: ArrayList<u64> = (0..1000).rev().collect();
 
     c.bench_function("bubblesort_worst_case 1000",
                      |b| b.iter(|| bubblesort(&mut list, cmp_leq)));
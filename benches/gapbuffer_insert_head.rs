@@ -0,0 +1,12 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use oxcart::list::List;
+use oxcart::gapbuffer::GapBuffer;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut list: GapBuffer<u64> = GapBuffer::new();
+    c.bench_function("gapbuffer_insert_head 1000000",
+                     |b| b.iter(|| list.insert(0, black_box(1000000))));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
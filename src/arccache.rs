@@ -0,0 +1,318 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use crate::cache::{Cache, CacheError, CacheStats};
+use crate::lrucache::LruCache;
+
+/* an adaptive replacement cache (Megiddo and Modha): resident entries are
+ * split between those used once (`recent`) and those used again
+ * (`frequent`), each an LRU list, and the keys most recently evicted from
+ * each are remembered without their values as ghosts; a `put` that hits a
+ * ghost shifts `target`, the share of the capacity given to `recent`,
+ * towards the list that would have kept it, so a one-off scan only ever
+ * churns `recent` while the frequently used entries stay put. an evicted
+ * key is both handed back and kept as a ghost, hence `K: Clone` */
+#[derive(Debug)]
+pub struct ArcCache<K, V> {
+    recent: LruCache<K, V>,
+    frequent: LruCache<K, V>,
+    recent_ghosts: LruCache<K, ()>,
+    frequent_ghosts: LruCache<K, ()>,
+    target: usize,
+    capacity: usize,
+    stats: CacheStats
+}
+
+impl<K: Hash + Eq + Clone, V> ArcCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        ArcCache {
+            recent: LruCache::default(),
+            frequent: LruCache::default(),
+            recent_ghosts: LruCache::default(),
+            frequent_ghosts: LruCache::default(),
+            target: 0,
+            capacity,
+            stats: CacheStats::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.recent.len() + self.frequent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /* how many of the entries the cache currently aims to spend on those
+     * used only once */
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /* looks `key` up, moving it to the front of `frequent` */
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        if let Some((key, value)) = self.recent.pop(key) {
+            self.frequent.put(key, value);
+        }
+
+        let res: Option<&V> = self.frequent.get(key);
+
+        self.stats.record(res.is_some());
+        res
+    }
+
+    /* looks `key` up without counting it as a use */
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.recent.peek(key).or_else(|| self.frequent.peek(key))
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.recent.contains(key) || self.frequent.contains(key)
+    }
+
+    /* inserts or replaces `key`, returning the entry evicted to make room
+     * for it, if any; replacing counts as a use */
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+
+        if self.recent.pop(&key).is_some() || self.frequent.contains(&key) {
+            self.frequent.put(key, value);
+            return None;
+        }
+
+        if self.recent_ghosts.pop(&key).is_some() {
+            let step: usize = (self.frequent_ghosts.len() /
+                               (self.recent_ghosts.len() + 1)).max(1);
+
+            self.target = (self.target + step).min(self.capacity);
+
+            let evicted: Option<(K, V)> = self.replace(false);
+
+            self.frequent.put(key, value);
+            return evicted;
+        }
+
+        if self.frequent_ghosts.pop(&key).is_some() {
+            let step: usize = (self.recent_ghosts.len() /
+                               (self.frequent_ghosts.len() + 1)).max(1);
+
+            self.target = self.target.saturating_sub(step);
+
+            let evicted: Option<(K, V)> = self.replace(true);
+
+            self.frequent.put(key, value);
+            return evicted;
+        }
+
+        let evicted: Option<(K, V)> = if self.recent.len() +
+            self.recent_ghosts.len() >= self.capacity {
+            if self.recent.len() < self.capacity {
+                self.recent_ghosts.pop_lru();
+                self.replace(false)
+            } else {
+                self.recent.pop_lru()
+            }
+        } else {
+            let total: usize = self.len() + self.recent_ghosts.len() +
+                self.frequent_ghosts.len();
+
+            if total >= 2 * self.capacity {
+                self.frequent_ghosts.pop_lru();
+            }
+
+            self.replace(false)
+        };
+
+        self.recent.put(key, value);
+        evicted
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.recent.pop(key).or_else(|| self.frequent.pop(key))
+    }
+
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        self.frequent.clear();
+        self.recent_ghosts.clear();
+        self.frequent_ghosts.clear();
+        self.target = 0;
+    }
+
+    /* if the cache is full, evicts the least recent entry of `recent` when
+     * it is over its target (or at it, for a key that was a ghost of
+     * `frequent`), and of `frequent` otherwise, remembering the key as a
+     * ghost */
+    fn replace(&mut self, frequent_ghost: bool) -> Option<(K, V)> {
+        if self.len() < self.capacity {
+            return None;
+        }
+
+        let from_recent: bool = !self.recent.is_empty() &&
+            (self.recent.len() > self.target ||
+             (frequent_ghost && self.recent.len() == self.target) ||
+             self.frequent.is_empty());
+
+        let (key, value) = if from_recent {
+            let (key, value) = self.recent.pop_lru()?;
+
+            self.recent_ghosts.put(key.clone(), ());
+            (key, value)
+        } else {
+            let (key, value) = self.frequent.pop_lru()?;
+
+            self.frequent_ghosts.put(key.clone(), ());
+            (key, value)
+        };
+
+        Some((key, value))
+    }
+}
+
+impl<K, V> Cache<K, V> for ArcCache<K, V> where K: Hash + Eq + Clone {
+    fn get<Q>(&mut self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        ArcCache::get(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn peek<Q>(&self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        ArcCache::peek(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn put(&mut self, key: K, value: V)
+        -> Result<Option<(K, V)>, CacheError> {
+        Ok(ArcCache::put(self, key, value))
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        match self.pop(key) {
+            Some((_, value)) => Ok(value),
+            None => Err(CacheError::KeyNotFound)
+        }
+    }
+
+    fn contains<Q>(&self, key: &Q) -> Result<bool, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        Ok(ArcCache::contains(self, key))
+    }
+
+    fn size(&self) -> Result<usize, CacheError> {
+        Ok(self.len())
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn clear(&mut self) -> Result<(), CacheError> {
+        ArcCache::clear(self);
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_normal_scan_resistant() -> Result<(), CacheError> {
+        let mut actual_arc: ArcCache<u64, u64> = ArcCache::new(4);
+        let mut actual_lru: LruCache<u64, u64> = LruCache::new(4);
+
+        for key in [1, 2].iter() {
+            Cache::put(&mut actual_arc, *key, *key)?;
+            Cache::put(&mut actual_lru, *key, *key)?;
+            Cache::get(&mut actual_arc, key)?;
+            Cache::get(&mut actual_lru, key)?;
+        }
+
+        for key in 100..200 {
+            Cache::put(&mut actual_arc, key, key)?;
+            Cache::put(&mut actual_lru, key, key)?;
+        }
+
+        assert!(actual_arc.contains(&1) && actual_arc.contains(&2));
+        assert!(!actual_lru.contains(&1) && !actual_lru.contains(&2));
+        assert_eq!(actual_arc.len(), 4);
+        assert!(actual_arc.recent_ghosts.len() <= 4);
+        assert!(actual_arc.frequent_ghosts.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_normal_ghost_adapts() -> Result<(), CacheError> {
+        let mut actual_cache: ArcCache<u64, u64> = ArcCache::new(2);
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        actual_cache.get(&1);
+
+        assert_eq!(actual_cache.put(3, 30), Some((2, 20)));
+        assert_eq!(actual_cache.target(), 0);
+
+        /* 2 comes straight back, so `recent` was too small */
+        assert_eq!(actual_cache.put(2, 21), Some((1, 10)));
+        assert_eq!(actual_cache.target(), 1);
+        assert!(actual_cache.frequent.contains(&2));
+
+        /* and then 1, a ghost of `frequent`, pulls the target back */
+        assert_eq!(actual_cache.put(1, 11), Some((3, 30)));
+        assert_eq!(actual_cache.target(), 0);
+        assert_eq!(actual_cache.peek(&1), Some(&11));
+        assert_eq!(actual_cache.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal_promotes() -> Result<(), CacheError> {
+        let mut actual_cache: ArcCache<&str, u64> = ArcCache::new(3);
+
+        actual_cache.put("a", 1);
+        actual_cache.put("b", 2);
+
+        assert_eq!(actual_cache.get("a"), Some(&1));
+        assert_eq!(actual_cache.get("c"), None);
+        assert!(actual_cache.frequent.contains("a"));
+        assert!(actual_cache.recent.contains("b"));
+        assert_eq!(Cache::stats(&actual_cache),
+                   CacheStats { hits: 1, misses: 1 });
+        assert_eq!(actual_cache.pop("a"), Some(("a", 1)));
+        assert_eq!(actual_cache.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), CacheError> {
+        let mut actual_cache: Box<dyn crate::cache::DynCache<u64, u64>> =
+            Box::new(ArcCache::new(0));
+
+        assert_eq!(actual_cache.put(1, 10)?, Some((1, 10)));
+        assert_eq!(actual_cache.size()?, 0);
+
+        let mut actual_cache: Box<dyn crate::cache::DynCache<u64, u64>> =
+            Box::new(ArcCache::new(2));
+
+        actual_cache.put(1, 10)?;
+
+        assert_eq!(actual_cache.get(&1)?, &10);
+        assert_eq!(actual_cache.remove(&1)?, 10);
+        assert!(!actual_cache.contains(&1)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,757 @@
+use std::ops::{Index, IndexMut};
+use std::iter::FromIterator;
+use std::fmt;
+use std::fmt::Display;
+use std::slice;
+use crate::deque::{Deque, DequeError};
+use crate::queue::{Queue, QueueError};
+use crate::stack::{Stack, StackError};
+
+/* ring buffer; the logical element `i` lives at `(head + i) % capacity` and
+ * every slot outside the logical range holds `None` */
+#[derive(Clone, Debug)]
+pub struct ArrayDeque<T> {
+    elems: Vec<Option<T>>,
+    head: usize,
+    len: usize
+}
+
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, Option<T>>,
+    back: slice::Iter<'a, Option<T>>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elem) => elem.as_ref(),
+            None => self.back.next().and_then(|elem| elem.as_ref())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elem) => elem.as_ref(),
+            None => self.front.next_back().and_then(|elem| elem.as_ref())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, Option<T>>,
+    back: slice::IterMut<'a, Option<T>>
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elem) => elem.as_mut(),
+            None => self.back.next().and_then(|elem| elem.as_mut())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elem) => elem.as_mut(),
+            None => self.front.next_back().and_then(|elem| elem.as_mut())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    deque: ArrayDeque<T>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back().ok()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> ArrayDeque<T> {
+    pub fn new() -> Self {
+        ArrayDeque {
+            elems: Vec::new(),
+            head: 0,
+            len: 0
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut elems: Vec<Option<T>> = Vec::with_capacity(capacity);
+        elems.resize_with(capacity, || None);
+
+        ArrayDeque {
+            elems,
+            head: 0,
+            len: 0
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.slices();
+
+        Iter {
+            front: front.iter(),
+            back: back.iter()
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.slices_mut();
+
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut()
+        }
+    }
+
+    fn physical(&self, pos: usize) -> usize {
+        (self.head + pos) % self.elems.len()
+    }
+
+    /* the occupied slots, split into the run starting at `head` and the
+     * run that wrapped around to the start of the buffer */
+    fn slices(&self) -> (&[Option<T>], &[Option<T>]) {
+        let end: usize = self.head + self.len;
+
+        if end <= self.elems.len() {
+            (&self.elems[self.head..end], &[])
+        } else {
+            let wrapped: usize = end - self.elems.len();
+            let (back, front) = self.elems.split_at(self.head);
+            (front, &back[..wrapped])
+        }
+    }
+
+    fn slices_mut(&mut self) -> (&mut [Option<T>], &mut [Option<T>]) {
+        let end: usize = self.head + self.len;
+
+        if end <= self.elems.len() {
+            (&mut self.elems[self.head..end], &mut [])
+        } else {
+            let wrapped: usize = end - self.elems.len();
+            let (back, front) = self.elems.split_at_mut(self.head);
+            (front, &mut back[..wrapped])
+        }
+    }
+
+    fn grow(&mut self) {
+        let new_capacity: usize = if self.elems.is_empty() {
+            4
+        } else {
+            self.elems.len() * 2
+        };
+
+        let mut new_elems: Vec<Option<T>> = Vec::with_capacity(new_capacity);
+
+        for i in 0..self.len {
+            let pos: usize = self.physical(i);
+            new_elems.push(self.elems[pos].take());
+        }
+
+        new_elems.resize_with(new_capacity, || None);
+
+        self.elems = new_elems;
+        self.head = 0;
+    }
+}
+
+impl<T> Default for ArrayDeque<T> {
+    fn default() -> Self {
+        ArrayDeque::new()
+    }
+}
+
+impl<T> PartialEq for ArrayDeque<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for ArrayDeque<T> where T: Eq {}
+
+impl<T> IntoIterator for ArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            deque: self
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque: ArrayDeque<T> = ArrayDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for ArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            if self.len == self.elems.len() {
+                self.grow();
+            }
+
+            let pos: usize = self.physical(self.len);
+            self.elems[pos] = Some(elem);
+            self.len += 1;
+        }
+    }
+}
+
+impl<T> Index<usize> for ArrayDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayDeque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl<T> Display for ArrayDeque<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in self.iter() {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+impl<T> Deque<T> for ArrayDeque<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        ArrayDeque::new()
+    }
+
+    fn push_front(&mut self, elem: T) -> Result<(), DequeError> {
+        if self.len == self.elems.len() {
+            self.grow();
+        }
+
+        self.head = (self.head + self.elems.len() - 1) % self.elems.len();
+        self.elems[self.head] = Some(elem);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn push_back(&mut self, elem: T) -> Result<(), DequeError> {
+        if self.len == self.elems.len() {
+            self.grow();
+        }
+
+        let pos: usize = self.physical(self.len);
+        self.elems[pos] = Some(elem);
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn pop_front(&mut self) -> Result<T, DequeError> {
+        if self.len == 0 { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        let elem: Option<T> = self.elems[self.head].take();
+        self.head = (self.head + 1) % self.elems.len();
+        self.len -= 1;
+
+        match elem {
+            Some(elem) => Ok(elem),
+            None => Err(DequeError::Impossible)
+        }
+    }
+
+    fn pop_back(&mut self) -> Result<T, DequeError> {
+        if self.len == 0 { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        let pos: usize = self.physical(self.len - 1);
+        self.len -= 1;
+
+        match self.elems[pos].take() {
+            Some(elem) => Ok(elem),
+            None => Err(DequeError::Impossible)
+        }
+    }
+
+    fn peek_front(&self) -> Result<&T, DequeError> {
+        self.get(0)
+    }
+
+    fn peek_back(&self) -> Result<&T, DequeError> {
+        if self.len == 0 { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        self.get(self.len - 1)
+    }
+
+    fn get(&self, pos: usize) -> Result<&T, DequeError> {
+        if pos >= self.len { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        match self.elems[self.physical(pos)].as_ref() {
+            Some(elem) => Ok(elem),
+            None => Err(DequeError::Impossible)
+        }
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, DequeError> {
+        if pos >= self.len { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        let pos: usize = self.physical(pos);
+
+        match self.elems[pos].as_mut() {
+            Some(elem) => Ok(elem),
+            None => Err(DequeError::Impossible)
+        }
+    }
+
+    fn length(&self) -> Result<usize, DequeError> {
+        Ok(self.len)
+    }
+
+    fn clear(&mut self) -> Result<(), DequeError> {
+        for elem in self.elems.iter_mut() {
+            *elem = None;
+        }
+
+        self.head = 0;
+        self.len = 0;
+
+        Ok(())
+    }
+
+    fn rotate_left(&mut self, n: usize) -> Result<(), DequeError> {
+        if n > self.len { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        if self.len == self.elems.len() { /* full, so just move the head */
+            if self.len > 0 {
+                self.head = (self.head + n) % self.elems.len();
+            }
+
+            return Ok(());
+        }
+
+        if n > self.len - n { /* cheaper to go the other way */
+            return self.rotate_right(self.len - n);
+        }
+
+        for _i in 0..n {
+            let elem: T = self.pop_front()?;
+            self.push_back(elem)?;
+        }
+
+        Ok(())
+    }
+
+    fn rotate_right(&mut self, n: usize) -> Result<(), DequeError> {
+        if n > self.len { /* bounds check */
+            return Err(DequeError::OutOfBounds);
+        }
+
+        if self.len == self.elems.len() { /* full, so just move the head */
+            if self.len > 0 {
+                self.head = (self.head + self.elems.len() - n) %
+                    self.elems.len();
+            }
+
+            return Ok(());
+        }
+
+        if n > self.len - n { /* cheaper to go the other way */
+            return self.rotate_left(self.len - n);
+        }
+
+        for _i in 0..n {
+            let elem: T = self.pop_back()?;
+            self.push_front(elem)?;
+        }
+
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayDeque::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        ArrayDeque::iter_mut(self)
+    }
+}
+
+impl<T> Stack<T> for ArrayDeque<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        ArrayDeque::new()
+    }
+
+    fn push(&mut self, elem: T) -> Result<(), StackError> {
+        match self.push_back(elem) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(StackError::Impossible)
+        }
+    }
+
+    fn pop(&mut self) -> Result<T, StackError> {
+        match self.pop_back() {
+            Ok(elem) => Ok(elem),
+            Err(DequeError::OutOfBounds) => Err(StackError::OutOfBounds),
+            Err(DequeError::Impossible) => Err(StackError::Impossible)
+        }
+    }
+
+    fn peek(&self) -> Result<&T, StackError> {
+        match self.peek_back() {
+            Ok(elem) => Ok(elem),
+            Err(DequeError::OutOfBounds) => Err(StackError::OutOfBounds),
+            Err(DequeError::Impossible) => Err(StackError::Impossible)
+        }
+    }
+
+    fn depth(&self) -> Result<usize, StackError> {
+        Ok(self.len)
+    }
+
+    fn clear(&mut self) -> Result<(), StackError> {
+        match Deque::clear(self) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(StackError::Impossible)
+        }
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayDeque::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        ArrayDeque::iter_mut(self)
+    }
+}
+
+impl<T> Queue<T> for ArrayDeque<T> {
+    fn new() -> Self {
+        ArrayDeque::new()
+    }
+
+    fn push(&mut self, elem: T) -> Result<(), QueueError> {
+        match self.push_back(elem) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(QueueError::OutOfBounds)
+        }
+    }
+
+    fn pop(&mut self) -> Result<T, QueueError> {
+        match self.pop_front() {
+            Ok(elem) => Ok(elem),
+            Err(_) => Err(QueueError::OutOfBounds)
+        }
+    }
+
+    fn peek(&self) -> Result<&T, QueueError> {
+        match self.peek_front() {
+            Ok(elem) => Ok(elem),
+            Err(_) => Err(QueueError::OutOfBounds)
+        }
+    }
+
+    fn length(&self) -> Result<usize, QueueError> {
+        Ok(self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(deque: &ArrayDeque<u64>) -> Vec<u64> {
+        deque.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_new_normal() -> Result<(), DequeError> {
+        let actual_deque: ArrayDeque<u64> = ArrayDeque::new();
+        let expected_deque: ArrayDeque<u64> = ArrayDeque {
+            elems: Vec::new(),
+            head: 0,
+            len: 0
+        };
+
+        assert_eq!(actual_deque, expected_deque);
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_normal_both_ends() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::new();
+
+        actual_deque.push_back(2)?;
+        actual_deque.push_back(3)?;
+        actual_deque.push_front(1)?;
+        actual_deque.push_front(0)?;
+        actual_deque.push_back(4)?;
+
+        assert_eq!(contents(&actual_deque), vec![0, 1, 2, 3, 4]);
+        assert_eq!(actual_deque.peek_front()?, &0);
+        assert_eq!(actual_deque.peek_back()?, &4);
+        assert_eq!(actual_deque.get(2)?, &2);
+        assert_eq!(Deque::length(&actual_deque)?, 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_normal_wrapped() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::with_capacity(4);
+
+        for i in 0..4 {
+            actual_deque.push_back(i)?;
+        }
+
+        actual_deque.pop_front()?;
+        actual_deque.pop_front()?;
+        actual_deque.push_back(4)?;
+        actual_deque.push_back(5)?;
+
+        assert_eq!(actual_deque.capacity(), 4);
+        assert_eq!(contents(&actual_deque), vec![2, 3, 4, 5]);
+        assert_eq!(actual_deque.iter().rev().cloned().collect::<Vec<u64>>(),
+                   vec![5, 4, 3, 2]);
+        assert_eq!(actual_deque.pop_back()?, 5);
+        assert_eq!(actual_deque.pop_front()?, 2);
+        assert_eq!(actual_deque.iter().len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_error_out_of_bounds() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::new();
+
+        assert_eq!(actual_deque.pop_front(), Err(DequeError::OutOfBounds));
+        assert_eq!(actual_deque.pop_back(), Err(DequeError::OutOfBounds));
+        assert_eq!(actual_deque.peek_back(), Err(DequeError::OutOfBounds));
+        assert_eq!(actual_deque.get(0), Err(DequeError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal_wrapped() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::with_capacity(4);
+
+        actual_deque.push_back(1)?;
+        actual_deque.push_back(2)?;
+        actual_deque.push_front(0)?;
+
+        for elem in &mut actual_deque {
+            *elem *= 10;
+        }
+
+        assert_eq!(contents(&actual_deque), vec![0, 10, 20]);
+        assert_eq!(actual_deque[1], 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_normal() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = (0..5).collect();
+
+        actual_deque.rotate_left(2)?;
+        assert_eq!(contents(&actual_deque), vec![2, 3, 4, 0, 1]);
+
+        actual_deque.rotate_right(1)?;
+        assert_eq!(contents(&actual_deque), vec![1, 2, 3, 4, 0]);
+
+        actual_deque.rotate_left(4)?;
+        assert_eq!(contents(&actual_deque), vec![0, 1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_normal_full() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::with_capacity(4);
+
+        actual_deque.extend(0..4);
+        actual_deque.rotate_right(3)?;
+
+        assert_eq!(contents(&actual_deque), vec![1, 2, 3, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_error_out_of_bounds() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = (0..3).collect();
+
+        assert_eq!(actual_deque.rotate_left(4), Err(DequeError::OutOfBounds));
+        assert_eq!(contents(&actual_deque), vec![0, 1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sliding_window_normal() -> Result<(), DequeError> {
+        let input: Vec<u64> = vec![1, 3, 2, 5, 4, 1, 0];
+        let mut window: ArrayDeque<usize> = ArrayDeque::new();
+        let mut actual_maxima: Vec<u64> = Vec::new();
+
+        for (i, elem) in input.iter().enumerate() {
+            while let Ok(back) = window.peek_back() {
+                if input[*back] > *elem {
+                    break;
+                }
+
+                window.pop_back()?;
+            }
+
+            window.push_back(i)?;
+
+            if *window.peek_front()? + 3 <= i {
+                window.pop_front()?;
+            }
+
+            if i >= 2 {
+                actual_maxima.push(input[*window.peek_front()?]);
+            }
+        }
+
+        assert_eq!(actual_maxima, vec![3, 5, 5, 5, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stack_normal() -> Result<(), StackError> {
+        let mut actual_stack: ArrayDeque<u64> = ArrayDeque::new();
+
+        Stack::push(&mut actual_stack, 1)?;
+        Stack::push(&mut actual_stack, 2)?;
+
+        assert_eq!(Stack::peek(&actual_stack)?, &2);
+        assert_eq!(Stack::pop(&mut actual_stack)?, 2);
+        assert_eq!(actual_stack.depth()?, 1);
+        assert_eq!(Stack::pop(&mut actual_stack)?, 1);
+        assert_eq!(Stack::pop(&mut actual_stack), Err(StackError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_queue_normal() -> Result<(), QueueError> {
+        let mut actual_queue: ArrayDeque<u64> = ArrayDeque::new();
+
+        Queue::push(&mut actual_queue, 1)?;
+        Queue::push(&mut actual_queue, 2)?;
+
+        assert_eq!(Queue::peek(&actual_queue)?, &1);
+        assert_eq!(Queue::pop(&mut actual_queue)?, 1);
+        assert_eq!(Queue::length(&actual_queue)?, 1);
+        assert_eq!(Queue::pop(&mut actual_queue)?, 2);
+        assert_eq!(Queue::pop(&mut actual_queue), Err(QueueError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_normal() -> Result<(), DequeError> {
+        let mut actual_deque: ArrayDeque<u64> = ArrayDeque::with_capacity(2);
+
+        actual_deque.push_back(1)?;
+        actual_deque.push_front(0)?;
+        actual_deque.push_back(2)?;
+
+        let actual_elems: Vec<u64> = actual_deque.into_iter().rev().collect();
+
+        assert_eq!(actual_elems, vec![2, 1, 0]);
+        Ok(())
+    }
+}
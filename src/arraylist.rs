@@ -1,13 +1,46 @@
+use std::borrow::Borrow;
 use std::ops::{Index, IndexMut};
+use std::iter::FromIterator;
 use std::fmt;
-use std::fmt::{Debug, Display};
-use crate::list::{List, ListError};
+use std::fmt::Display;
+use crate::list::{List, ListError, Reserve};
+
+#[macro_export]
+macro_rules! arraylist {
+    () => {
+        $crate::arraylist::ArrayList::from(::std::vec::Vec::new())
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::arraylist::ArrayList::from(::std::vec![$elem; $n])
+    };
+    ($($elem:expr),+ $(,)?) => {
+        $crate::arraylist::ArrayList::from(::std::vec![$($elem),+])
+    };
+}
 
 #[derive(Clone, Debug)]
 pub struct ArrayList<T> {
     elems: Vec<T>
 }
 
+impl<T> ArrayList<T> {
+    pub fn as_slice(&self) -> &[T] {
+        self.elems.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.elems.as_mut_slice()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elems.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.elems.iter_mut()
+    }
+}
+
 impl<T> PartialEq for ArrayList<T> where T: Eq {
     fn eq(&self, other: &Self) -> bool {
         if self.elems.len() != other.elems.len() {
@@ -35,6 +68,74 @@ impl<T> IntoIterator for ArrayList<T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a ArrayList<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayList<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for ArrayList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ArrayList {
+            elems: Vec::from_iter(iter)
+        }
+    }
+}
+
+impl<T> Extend<T> for ArrayList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.elems.extend(iter);
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for ArrayList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.elems.extend(iter);
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayList<T> {
+    fn from(elems: Vec<T>) -> Self {
+        ArrayList {
+            elems
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArrayList<T> {
+    fn from(elems: [T; N]) -> Self {
+        ArrayList {
+            elems: Vec::from(elems)
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for ArrayList<T> {
+    fn from(elems: &[T]) -> Self {
+        ArrayList {
+            elems: elems.to_vec()
+        }
+    }
+}
+
+impl<T> From<ArrayList<T>> for Vec<T> {
+    fn from(list: ArrayList<T>) -> Self {
+        list.elems
+    }
+}
+
 impl<T> Index<usize> for ArrayList<T> {
     type Output = T;
 
@@ -63,13 +164,22 @@ impl<T> Display for ArrayList<T> where T: Display {
     }
 }
 
-impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
+impl<T> List<T> for ArrayList<T> {
+    type Iter<'a> = std::slice::Iter<'a, T> where T: 'a;
+    type IterMut<'a> = std::slice::IterMut<'a, T> where T: 'a;
+
     fn new() -> Self {
         ArrayList {
             elems: Vec::new()
         }
     }
 
+    fn with_capacity(capacity: usize) -> Self {
+        ArrayList {
+            elems: Vec::with_capacity(capacity)
+        }
+    }
+
     fn get(&self, pos: usize) -> Result<&T, ListError> {
         if pos >= self.elems.len() { /* bounds check */
             return Err(ListError::OutOfBounds);
@@ -137,13 +247,11 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
         }
 
         let tmp_a: T = self.elems.remove(a);
-        let tmp_b: T;
-
-        if b < a {
-            tmp_b = self.elems.remove(b);
+        let tmp_b: T = if b < a {
+            self.elems.remove(b)
         } else {
-            tmp_b = self.elems.remove(b - 1);
-        }
+            self.elems.remove(b - 1)
+        };
 
         self.elems.insert(a, tmp_b);
         self.elems.insert(b, tmp_a);
@@ -151,9 +259,10 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
         Ok(())
     }
 
-    fn contains(&self, elem: T) -> Result<bool, ListError> {
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
         for element in self.elems.iter() {
-            if *element == elem {
+            if element.borrow() == elem {
                 return Ok(true);
             }
         }
@@ -161,16 +270,12 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
         Ok(false)
     }
 
-    fn find_all(&self, elem: T) -> Result<Option<Vec<usize>>, ListError> {
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
         let mut res: Vec<usize> = Vec::new();
 
-        for i in 0..self.elems.len() {
-            let curr_elem: T = match self.elems.get(i) {
-                Some(curr) => curr.clone(),
-                None => return Ok(None)
-            };
-
-            if curr_elem == elem {
+        for (i, curr_elem) in self.elems.iter().enumerate() {
+            if curr_elem.borrow() == elem {
                 res.push(i);
             }
         }
@@ -178,14 +283,10 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
         Ok(Some(res))
     }
 
-    fn find(&self, elem: T) -> Result<Option<usize>, ListError> {
-        for i in 0..self.elems.len() {
-            let curr_elem: T = match self.elems.get(i) {
-                Some(curr) => curr.clone(),
-                None => return Ok(None)
-            };
-
-            if curr_elem == elem {
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        for (i, curr_elem) in self.elems.iter().enumerate() {
+            if curr_elem.borrow() == elem {
                 return Ok(Some(i));
             }
         }
@@ -193,16 +294,12 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
         Ok(None)
     }
 
-    fn count(&self, elem: T) -> Result<usize, ListError> {
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
         let mut count: usize = 0;
 
-        for i in 0..self.elems.len() {
-            let curr_elem: T = match self.elems.get(i) {
-                Some(curr) => curr.clone(),
-                None => return Ok(count)
-            };
-
-            if curr_elem == elem {
+        for curr_elem in self.elems.iter() {
+            if curr_elem.borrow() == elem {
                 count += 1;
             }
         }
@@ -221,6 +318,42 @@ impl<T> List<T> for ArrayList<T> where T: Sized + Clone + Eq + Display + Debug {
 
         Ok(())
     }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.elems.iter()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.elems.iter_mut()
+    }
+}
+
+impl<T> Reserve for ArrayList<T> {
+    fn capacity(&self) -> Result<usize, ListError> {
+        Ok(self.elems.capacity())
+    }
+
+    fn reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        self.elems.reserve(additional);
+        Ok(())
+    }
+
+    fn reserve_exact(&mut self, additional: usize) -> Result<(), ListError> {
+        self.elems.reserve_exact(additional);
+        Ok(())
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        match self.elems.try_reserve(additional) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(ListError::AllocationFailed)
+        }
+    }
+
+    fn shrink_to_fit(&mut self) -> Result<(), ListError> {
+        self.elems.shrink_to_fit();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -536,5 +669,288 @@ mod test {
 
         Ok(())
     }
-}
 
+    #[test]
+    fn test_iter_normal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        actual_list.append(1)?;
+        actual_list.append(2)?;
+        actual_list.append(3)?;
+
+        let actual_elems: Vec<&u64> = actual_list.iter().collect();
+        let expected_elems: Vec<&u64> = vec![&1, &2, &3];
+
+        assert_eq!(actual_elems, expected_elems);
+        assert_eq!(actual_list.iter().len(), 3);
+        assert_eq!(actual_list.iter().next_back(), Some(&3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal_empty() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = ArrayList::new();
+
+        assert_eq!(actual_list.iter().next(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+        let expected_list: ArrayList<u64> = ArrayList {
+            elems: vec![2, 4, 6]
+        };
+
+        actual_list.append(1)?;
+        actual_list.append(2)?;
+        actual_list.append(3)?;
+
+        for elem in actual_list.iter_mut() {
+            *elem *= 2;
+        }
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_normal_borrowed() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+        let expected_list: ArrayList<u64> = ArrayList {
+            elems: vec![11, 12]
+        };
+
+        actual_list.append(1)?;
+        actual_list.append(2)?;
+
+        for elem in &mut actual_list {
+            *elem += 10;
+        }
+
+        let mut sum: u64 = 0;
+
+        for elem in &actual_list {
+            sum += elem;
+        }
+
+        assert_eq!(sum, 23);
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal_through_trait() -> Result<(), ListError> {
+        fn total<L: List<u64>>(list: &L) -> u64 {
+            List::iter(list).rev().sum()
+        }
+
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        actual_list.append(1)?;
+        actual_list.append(2)?;
+        actual_list.append(3)?;
+
+        assert_eq!(total(&actual_list), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_iter_normal() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = (1..5).collect();
+        let expected_list: ArrayList<u64> = ArrayList {
+            elems: vec![1, 2, 3, 4]
+        };
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extend_normal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+        let expected_list: ArrayList<u64> = ArrayList {
+            elems: vec![1, 2, 3, 4]
+        };
+
+        actual_list.append(1)?;
+        actual_list.extend(vec![2, 3]);
+        actual_list.extend(&[4]);
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_normal() -> Result<(), ListError> {
+        let expected_list: ArrayList<u64> = ArrayList {
+            elems: vec![1, 2, 3]
+        };
+        let slice: &[u64] = &[1, 2, 3];
+
+        assert_eq!(ArrayList::from(vec![1, 2, 3]), expected_list);
+        assert_eq!(ArrayList::from([1, 2, 3]), expected_list);
+        assert_eq!(ArrayList::from(slice), expected_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_vec_normal() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = ArrayList {
+            elems: vec![1, 2, 3]
+        };
+
+        let actual_vec: Vec<u64> = actual_list.into();
+        let expected_vec: Vec<u64> = vec![1, 2, 3];
+
+        assert_eq!(actual_vec, expected_vec);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arraylist_macro_normal() -> Result<(), ListError> {
+        let empty_list: ArrayList<u64> = arraylist![];
+        let repeat_list: ArrayList<u64> = arraylist![7; 3];
+        let actual_list: ArrayList<u64> = arraylist![1, 2, 3,];
+
+        assert_eq!(empty_list, ArrayList { elems: Vec::new() });
+        assert_eq!(repeat_list, ArrayList { elems: vec![7, 7, 7] });
+        assert_eq!(actual_list, ArrayList { elems: vec![1, 2, 3] });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_capacity_normal() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = ArrayList::with_capacity(16);
+        let expected_list: ArrayList<u64> = ArrayList::new();
+
+        assert!(actual_list.capacity()? >= 16);
+        assert_eq!(actual_list, expected_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserve_normal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        actual_list.append(1)?;
+        actual_list.reserve(10)?;
+        assert!(actual_list.capacity()? >= 11);
+
+        actual_list.reserve_exact(20)?;
+        assert!(actual_list.capacity()? >= 21);
+
+        actual_list.shrink_to_fit()?;
+        assert!(actual_list.capacity()? >= 1);
+        assert_eq!(actual_list.length()?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_reserve_normal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        let actual_res: Result<(), ListError> = actual_list.try_reserve(8);
+        let expected_res: Result<(), ListError> = Ok(());
+
+        assert_eq!(actual_res, expected_res);
+        assert!(actual_list.capacity()? >= 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_reserve_error_allocation_failed() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        let actual_res: Result<(), ListError> =
+            actual_list.try_reserve(usize::MAX);
+        let expected_res: Result<(), ListError> =
+            Err(ListError::AllocationFailed);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_contains_normal() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = arraylist![33, 12, 1, 10];
+
+        assert_eq!(actual_list.contains(&12), Ok(true));
+        assert_eq!(actual_list.contains(&13), Ok(false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal_borrowed() -> Result<(), ListError> {
+        let actual_list: ArrayList<String> = arraylist![
+            String::from("foo"),
+            String::from("bar"),
+            String::from("foo")
+        ];
+
+        assert_eq!(actual_list.contains("bar"), Ok(true));
+        assert_eq!(actual_list.find("foo"), Ok(Some(0)));
+        assert_eq!(actual_list.find("baz"), Ok(None));
+        assert_eq!(actual_list.find_all("foo"), Ok(Some(vec![0, 2])));
+        assert_eq!(actual_list.count("foo"), Ok(2));
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_find_normal_by_value() -> Result<(), ListError> {
+        let actual_list: ArrayList<u64> = arraylist![33, 12, 1, 12];
+
+        assert_eq!(actual_list.contains_by_value(1), Ok(true));
+        assert_eq!(actual_list.find_by_value(12), Ok(Some(1)));
+        assert_eq!(actual_list.find_all_by_value(12), Ok(Some(vec![1, 3])));
+        assert_eq!(actual_list.count_by_value(12), Ok(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_normal_closures() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<Box<dyn Fn(u64) -> u64>> =
+            ArrayList::new();
+
+        actual_list.append(Box::new(|x| x + 1))?;
+        actual_list.append(Box::new(|x| x * 2))?;
+        actual_list.swap(0, 1)?;
+
+        let actual_res: u64 = actual_list.iter().fold(3, |acc, f| f(acc));
+        let expected_res: u64 = 7;
+
+        assert_eq!(actual_list.length()?, 2);
+        assert_eq!(actual_res, expected_res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal_no_display() -> Result<(), ListError> {
+        #[derive(PartialEq, Eq)]
+        struct Point {
+            x: i64,
+            y: i64
+        }
+
+        let actual_list: ArrayList<Point> = arraylist![
+            Point { x: 0, y: 0 },
+            Point { x: 1, y: 2 }
+        ];
+
+        assert_eq!(actual_list.find(&Point { x: 1, y: 2 })?, Some(1));
+        assert_eq!(actual_list.count(&Point { x: 3, y: 3 })?, 0);
+
+        Ok(())
+    }
+}
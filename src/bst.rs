@@ -0,0 +1,254 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use crate::map::MapError;
+
+/* the linked binary search tree underneath `TreapMap` and `OrderStatMap`;
+ * each keeps whatever it balances by in `meta` and does its own
+ * restructuring, while lookups and in-order walks are shared here */
+pub type Link<K, V, M> = Option<Box<Node<K, V, M>>>;
+
+#[derive(Clone, Debug)]
+pub struct Node<K, V, M> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    pub(crate) meta: M,
+    pub(crate) left: Link<K, V, M>,
+    pub(crate) right: Link<K, V, M>
+}
+
+/* an AVL node's height and the number of entries in its subtree */
+#[derive(Clone, Copy, Debug)]
+pub struct Counts {
+    pub(crate) height: usize,
+    pub(crate) size: usize
+}
+
+pub struct Iter<'a, K, V, M> {
+    pub(crate) stack: Vec<&'a Node<K, V, M>>,
+    pub(crate) remaining: usize
+}
+
+impl<'a, K, V, M> Iter<'a, K, V, M> {
+    pub fn new(root: &'a Link<K, V, M>, len: usize) -> Self {
+        let mut res: Iter<'a, K, V, M> = Iter {
+            stack: Vec::new(),
+            remaining: len
+        };
+
+        res.push_left(root);
+        res
+    }
+
+    fn push_left(&mut self, mut link: &'a Link<K, V, M>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V, M> Iterator for Iter<'a, K, V, M> {
+    type Item = (&'a K, &'a V);
+
+    /* `remaining` may stop a range walk short of the stack running dry */
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: &'a Node<K, V, M> = self.stack.pop()?;
+
+        self.push_left(&node.right);
+        self.remaining -= 1;
+
+        Some((&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, M> ExactSizeIterator for Iter<'a, K, V, M> {}
+
+pub struct IterMut<'a, K, V, M> {
+    stack: Vec<(&'a K, &'a mut V, &'a mut Link<K, V, M>)>,
+    remaining: usize
+}
+
+impl<'a, K, V, M> IterMut<'a, K, V, M> {
+    pub fn new(root: &'a mut Link<K, V, M>, len: usize) -> Self {
+        let mut res: IterMut<'a, K, V, M> = IterMut {
+            stack: Vec::new(),
+            remaining: len
+        };
+
+        res.push_left(root);
+        res
+    }
+
+    fn push_left(&mut self, mut link: &'a mut Link<K, V, M>) {
+        while let Some(node) = link {
+            let Node { key, value, left, right, .. } = &mut **node;
+
+            self.stack.push((key, value, right));
+            link = left;
+        }
+    }
+}
+
+impl<'a, K, V, M> Iterator for IterMut<'a, K, V, M> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value, right) = self.stack.pop()?;
+
+        self.push_left(right);
+        self.remaining -= 1;
+
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V, M> ExactSizeIterator for IterMut<'a, K, V, M> {}
+
+pub struct IntoIter<K, V, M> {
+    stack: Vec<Box<Node<K, V, M>>>,
+    remaining: usize
+}
+
+impl<K, V, M> IntoIter<K, V, M> {
+    pub fn new(root: Link<K, V, M>, len: usize) -> Self {
+        let mut res: IntoIter<K, V, M> = IntoIter {
+            stack: Vec::new(),
+            remaining: len
+        };
+
+        res.push_left(root);
+        res
+    }
+
+    fn push_left(&mut self, mut link: Link<K, V, M>) {
+        while let Some(mut node) = link {
+            link = node.left.take();
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<K, V, M> Iterator for IntoIter<K, V, M> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node: Box<Node<K, V, M>> = self.stack.pop()?;
+
+        self.push_left(node.right.take());
+        self.remaining -= 1;
+
+        Some((node.key, node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V, M> ExactSizeIterator for IntoIter<K, V, M> {}
+
+pub fn find<'a, K, V, M, Q>(mut link: &'a Link<K, V, M>, key: &Q)
+    -> Option<&'a Node<K, V, M>> where K: Borrow<Q>, Q: Ord + ?Sized {
+    while let Some(node) = link {
+        match key.cmp(node.key.borrow()) {
+            Ordering::Less => link = &node.left,
+            Ordering::Greater => link = &node.right,
+            Ordering::Equal => return Some(node)
+        }
+    }
+
+    None
+}
+
+pub fn find_mut<'a, K, V, M, Q>(mut link: &'a mut Link<K, V, M>, key: &Q)
+    -> Option<&'a mut Node<K, V, M>> where K: Borrow<Q>, Q: Ord + ?Sized {
+    while let Some(node) = link {
+        match key.cmp(node.key.borrow()) {
+            Ordering::Less => link = &mut node.left,
+            Ordering::Greater => link = &mut node.right,
+            Ordering::Equal => return Some(node)
+        }
+    }
+
+    None
+}
+
+/* the greatest node below `key` (or, if `inclusive`, at most `key`) */
+pub fn below<'a, K, V, M, Q>(mut link: &'a Link<K, V, M>, key: &Q,
+                             inclusive: bool)
+    -> Option<&'a Node<K, V, M>> where K: Borrow<Q>, Q: Ord + ?Sized {
+    let mut res: Option<&'a Node<K, V, M>> = None;
+
+    while let Some(node) = link {
+        let k: &Q = node.key.borrow();
+
+        if k < key || (inclusive && k == key) {
+            res = Some(node);
+            link = &node.right;
+        } else {
+            link = &node.left;
+        }
+    }
+
+    res
+}
+
+/* the least node above `key` (or, if `inclusive`, at least `key`) */
+pub fn above<'a, K, V, M, Q>(mut link: &'a Link<K, V, M>, key: &Q,
+                             inclusive: bool)
+    -> Option<&'a Node<K, V, M>> where K: Borrow<Q>, Q: Ord + ?Sized {
+    let mut res: Option<&'a Node<K, V, M>> = None;
+
+    while let Some(node) = link {
+        let k: &Q = node.key.borrow();
+
+        if k > key || (inclusive && k == key) {
+            res = Some(node);
+            link = &node.left;
+        } else {
+            link = &node.right;
+        }
+    }
+
+    res
+}
+
+pub fn leftmost<K, V, M>(link: &Link<K, V, M>) -> Option<&Node<K, V, M>> {
+    let mut node: &Node<K, V, M> = link.as_ref()?;
+
+    while let Some(left) = node.left.as_ref() {
+        node = left;
+    }
+
+    Some(node)
+}
+
+pub fn rightmost<K, V, M>(link: &Link<K, V, M>) -> Option<&Node<K, V, M>> {
+    let mut node: &Node<K, V, M> = link.as_ref()?;
+
+    while let Some(right) = node.right.as_ref() {
+        node = right;
+    }
+
+    Some(node)
+}
+
+pub fn entry<K, V, M>(node: Option<&Node<K, V, M>>)
+    -> Result<(&K, &V), MapError> {
+    match node {
+        Some(node) => Ok((&node.key, &node.value)),
+        None => Err(MapError::KeyNotFound)
+    }
+}
@@ -1,8 +1,7 @@
-use std::fmt::{Display, Debug};
 use crate::list::{List, ListError};
 
 pub fn bubblesort<L, T>(list: &mut L, cmp: fn(a: &T, b: &T) -> bool) ->
-    Result<(), ListError> where L: List<T>, T: Eq + Clone + Display + Debug {
+    Result<(), ListError> where L: List<T> {
     if list.length()? <= 1 {
         return Ok(());
     }
@@ -0,0 +1,136 @@
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum CacheError {
+    KeyNotFound,
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CacheError::KeyNotFound => write!(f, "key not cached")
+        }
+    }
+}
+
+impl Error for CacheError {}
+
+/* lookups made through `get` since the cache was built */
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64
+}
+
+impl CacheStats {
+    /* the fraction of lookups that hit, or zero before any */
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64
+        }
+    }
+
+    pub(crate) fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
+
+/* a bounded map that chooses what to drop by itself; `get` is a use, which
+ * counts towards both the statistics and the replacement policy, while
+ * `peek` and `contains` are neither, and `put` hands back whatever entry it
+ * evicted to make room */
+pub trait Cache<K: Eq + Hash, V> {
+    fn get<Q>(&mut self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized;
+    fn peek<Q>(&self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized;
+    fn put(&mut self, key: K, value: V) -> Result<Option<(K, V)>, CacheError>;
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized;
+    fn contains<Q>(&self, key: &Q) -> Result<bool, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized;
+    fn size(&self) -> Result<usize, CacheError>;
+    fn capacity(&self) -> usize;
+    fn clear(&mut self) -> Result<(), CacheError>;
+    fn stats(&self) -> CacheStats;
+}
+
+/* object-safe subset of `Cache` for use as `Box<dyn DynCache<K, V>>` */
+pub trait DynCache<K, V> {
+    fn get(&mut self, key: &K) -> Result<&V, CacheError>;
+    fn peek(&self, key: &K) -> Result<&V, CacheError>;
+    fn put(&mut self, key: K, value: V) -> Result<Option<(K, V)>, CacheError>;
+    fn remove(&mut self, key: &K) -> Result<V, CacheError>;
+    fn contains(&self, key: &K) -> Result<bool, CacheError>;
+    fn size(&self) -> Result<usize, CacheError>;
+    fn capacity(&self) -> usize;
+    fn clear(&mut self) -> Result<(), CacheError>;
+    fn stats(&self) -> CacheStats;
+}
+
+impl<K, V, C> DynCache<K, V> for C where K: Eq + Hash, C: Cache<K, V> {
+    fn get(&mut self, key: &K) -> Result<&V, CacheError> {
+        Cache::get(self, key)
+    }
+
+    fn peek(&self, key: &K) -> Result<&V, CacheError> {
+        Cache::peek(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Result<Option<(K, V)>, CacheError> {
+        Cache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<V, CacheError> {
+        Cache::remove(self, key)
+    }
+
+    fn contains(&self, key: &K) -> Result<bool, CacheError> {
+        Cache::contains(self, key)
+    }
+
+    fn size(&self) -> Result<usize, CacheError> {
+        Cache::size(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Cache::capacity(self)
+    }
+
+    fn clear(&mut self) -> Result<(), CacheError> {
+        Cache::clear(self)
+    }
+
+    fn stats(&self) -> CacheStats {
+        Cache::stats(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_ratio_normal() {
+        let mut actual_stats: CacheStats = CacheStats::default();
+
+        assert_eq!(actual_stats.hit_ratio(), 0.0);
+
+        actual_stats.record(true);
+        actual_stats.record(true);
+        actual_stats.record(false);
+        actual_stats.record(true);
+
+        assert_eq!(actual_stats, CacheStats { hits: 3, misses: 1 });
+        assert_eq!(actual_stats.hit_ratio(), 0.75);
+    }
+}
@@ -0,0 +1,369 @@
+use std::borrow::Borrow;
+use std::iter::FromIterator;
+use std::fmt;
+use std::fmt::Display;
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct Cell<T> {
+    elem: T,
+    next: Option<Arc<Cell<T>>>
+}
+
+/* an immutable singly-linked list whose cells are shared between every
+ * version built from them, so `cons` and `tail` are O(1) and never copy */
+pub struct ConsList<T> {
+    head: Option<Arc<Cell<T>>>,
+    len: usize
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Cell<T>>,
+    remaining: usize
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell: &'a Cell<T> = self.next?;
+
+        self.next = cell.next.as_deref();
+        self.remaining -= 1;
+
+        Some(&cell.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Arc<Cell<T>>>,
+    remaining: usize
+}
+
+impl<'a, T: Clone> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Cell { elem, next } = Arc::make_mut(self.next.take()?);
+
+        self.next = next.as_mut();
+        self.remaining -= 1;
+
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: Clone> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    list: ConsList<T>
+}
+
+impl<T: Clone> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_head()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> ConsList<T> {
+    pub fn new() -> Self {
+        ConsList {
+            head: None,
+            len: 0
+        }
+    }
+
+    /* a new version with `elem` in front of the cells of `self` */
+    pub fn cons(&self, elem: T) -> Self {
+        ConsList {
+            head: Some(Arc::new(Cell {
+                elem,
+                next: self.head.clone()
+            })),
+            len: self.len + 1
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_deref().map(|cell| &cell.elem)
+    }
+
+    /* the version without the head, sharing all of its cells with `self` */
+    pub fn tail(&self) -> Option<Self> {
+        self.head.as_deref().map(|cell| ConsList {
+            head: cell.next.clone(),
+            len: self.len - 1
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains<Q>(&self, elem: &Q) -> bool where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter().any(|curr| curr.borrow() == elem)
+    }
+
+    pub fn find<Q>(&self, elem: &Q) -> Option<usize> where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter().position(|curr| curr.borrow() == elem)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            remaining: self.len
+        }
+    }
+
+    /* whether both versions start at the same cell */
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false
+        }
+    }
+}
+
+impl<T: Clone> ConsList<T> {
+    /* advances this version past its head, moving the head out when no
+     * other version shares its cell and cloning it otherwise */
+    pub fn pop_head(&mut self) -> Option<T> {
+        let cell: Arc<Cell<T>> = self.head.take()?;
+
+        self.len -= 1;
+
+        match Arc::try_unwrap(cell) {
+            Ok(cell) => {
+                self.head = cell.next;
+                Some(cell.elem)
+            },
+            Err(shared) => {
+                self.head = shared.next.clone();
+                Some(shared.elem.clone())
+            }
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        let mut res: ConsList<T> = ConsList::new();
+
+        for elem in self.iter() {
+            res = res.cons(elem.clone());
+        }
+
+        res
+    }
+
+    /* copies only the cells still shared with other versions before
+     * handing them out mutably */
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_mut(),
+            remaining: self.len
+        }
+    }
+}
+
+impl<T> Drop for ConsList<T> {
+    /* unlinks the cells this version solely owns iteratively so that long
+     * lists don't overflow the stack */
+    fn drop(&mut self) {
+        let mut curr: Option<Arc<Cell<T>>> = self.head.take();
+
+        while let Some(cell) = curr {
+            match Arc::try_unwrap(cell) {
+                Ok(mut cell) => curr = cell.next.take(),
+                Err(_) => break
+            }
+        }
+    }
+}
+
+impl<T> Clone for ConsList<T> {
+    fn clone(&self) -> Self {
+        ConsList {
+            head: self.head.clone(),
+            len: self.len
+        }
+    }
+}
+
+impl<T> Default for ConsList<T> {
+    fn default() -> Self {
+        ConsList::new()
+    }
+}
+
+impl<T> fmt::Debug for ConsList<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for ConsList<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        self.ptr_eq(other) || self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for ConsList<T> where T: Eq {}
+
+impl<T: Clone> IntoIterator for ConsList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            list: self
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ConsList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> FromIterator<T> for ConsList<T> {
+    /* keeps the iteration order, so the first element becomes the head */
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elems: Vec<T> = iter.into_iter().collect();
+        let mut res: ConsList<T> = ConsList::new();
+
+        for elem in elems.into_iter().rev() {
+            res = res.cons(elem);
+        }
+
+        res
+    }
+}
+
+impl<T> From<Vec<T>> for ConsList<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T> Display for ConsList<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+
+        for elem in self.iter() {
+            write!(f, "{} ", elem)?;
+        }
+
+        write!(f, ")")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(list: &ConsList<u64>) -> Vec<u64> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_cons_normal_shares_tail() {
+        let base: ConsList<u64> = ConsList::from(vec![2, 3]);
+        let a: ConsList<u64> = base.cons(1);
+        let b: ConsList<u64> = base.cons(10);
+
+        assert_eq!(contents(&a), vec![1, 2, 3]);
+        assert_eq!(contents(&b), vec![10, 2, 3]);
+        assert_eq!(contents(&base), vec![2, 3]);
+        assert!(a.tail().is_some_and(|tail| tail.ptr_eq(&base)));
+        assert!(b.tail().is_some_and(|tail| tail.ptr_eq(&base)));
+    }
+
+    #[test]
+    fn test_head_normal() {
+        let actual_list: ConsList<u64> = ConsList::new().cons(2).cons(1);
+
+        assert_eq!(actual_list.head(), Some(&1));
+        assert_eq!(actual_list.length(), 2);
+        assert_eq!(ConsList::<u64>::new().head(), None);
+        assert!(ConsList::<u64>::new().tail().is_none());
+    }
+
+    #[test]
+    fn test_pop_head_normal_shared() {
+        let base: ConsList<u64> = ConsList::from(vec![1, 2, 3]);
+        let mut actual_list: ConsList<u64> = base.clone();
+
+        assert_eq!(actual_list.pop_head(), Some(1));
+        assert_eq!(actual_list.pop_head(), Some(2));
+        assert_eq!(contents(&actual_list), vec![3]);
+        assert_eq!(contents(&base), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_iter_mut_normal_copy_on_write() {
+        let base: ConsList<u64> = ConsList::from(vec![1, 2, 3]);
+        let mut actual_list: ConsList<u64> = base.tail().unwrap_or_default()
+            .cons(10);
+
+        for elem in actual_list.iter_mut() {
+            *elem *= 2;
+        }
+
+        assert_eq!(contents(&actual_list), vec![20, 4, 6]);
+        assert_eq!(contents(&base), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_reverse_normal() {
+        let actual_list: ConsList<u64> = (0..5).collect();
+
+        assert_eq!(contents(&actual_list.reverse()), vec![4, 3, 2, 1, 0]);
+        assert_eq!(actual_list.into_iter().collect::<Vec<u64>>(),
+                   vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_drop_normal_long() {
+        let actual_list: ConsList<u64> = (0..1_000_000).collect();
+        let shared: ConsList<u64> = actual_list.tail().unwrap_or_default();
+
+        drop(actual_list);
+
+        assert_eq!(shared.length(), 999_999);
+        assert_eq!(shared.head(), Some(&1));
+    }
+}
@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Hash, Debug)]
+#[allow(dead_code)]
+pub enum DequeError {
+    OutOfBounds,
+    Impossible
+}
+
+impl Eq for DequeError {}
+
+impl fmt::Display for DequeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DequeError::OutOfBounds => write!(f, "index out of bounds"),
+            DequeError::Impossible => write!(f, "impossible state reached")
+        }
+    }
+}
+
+impl Error for DequeError {}
+
+pub trait Deque<T>: IntoIterator {
+    type Iter<'a>: Iterator<Item = &'a T> + DoubleEndedIterator +
+        ExactSizeIterator where Self: 'a, T: 'a;
+    type IterMut<'a>: Iterator<Item = &'a mut T> + DoubleEndedIterator +
+        ExactSizeIterator where Self: 'a, T: 'a;
+
+    fn new() -> Self;
+    fn push_front(&mut self, elem: T) -> Result<(), DequeError>;
+    fn push_back(&mut self, elem: T) -> Result<(), DequeError>;
+    fn pop_front(&mut self) -> Result<T, DequeError>;
+    fn pop_back(&mut self) -> Result<T, DequeError>;
+    fn peek_front(&self) -> Result<&T, DequeError>;
+    fn peek_back(&self) -> Result<&T, DequeError>;
+    fn get(&self, pos: usize) -> Result<&T, DequeError>;
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, DequeError>;
+    fn length(&self) -> Result<usize, DequeError>;
+    fn clear(&mut self) -> Result<(), DequeError>;
+    fn rotate_left(&mut self, n: usize) -> Result<(), DequeError>;
+    fn rotate_right(&mut self, n: usize) -> Result<(), DequeError>;
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+}
@@ -0,0 +1,283 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::cache::CacheError;
+use crate::deque::DequeError;
+use crate::list::ListError;
+use crate::map::MapError;
+use crate::priority_queue::PriorityQueueError;
+use crate::queue::QueueError;
+use crate::set::SetError;
+use crate::stack::StackError;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ErrorKind {
+    List(ListError),
+    Stack(StackError),
+    Queue(QueueError),
+    Deque(DequeError),
+    Map(MapError),
+    Set(SetError),
+    PriorityQueue(PriorityQueueError),
+    Cache(CacheError)
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::List(err) => write!(f, "list error: {}", err),
+            ErrorKind::Stack(err) => write!(f, "stack error: {}", err),
+            ErrorKind::Queue(err) => write!(f, "queue error: {}", err),
+            ErrorKind::Deque(err) => write!(f, "deque error: {}", err),
+            ErrorKind::Map(err) => write!(f, "map error: {}", err),
+            ErrorKind::Set(err) => write!(f, "set error: {}", err),
+            ErrorKind::PriorityQueue(err) =>
+                write!(f, "priority queue error: {}", err),
+            ErrorKind::Cache(err) => write!(f, "cache error: {}", err)
+        }
+    }
+}
+
+/* crate-wide error wrapping the per-container error enums along with
+ * whatever context the caller had to hand when it failed; the `From`
+ * conversions leave the context unset, to be filled in with the `with_`
+ * builders where the error is converted */
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OxcartError {
+    kind: ErrorKind,
+    operation: Option<&'static str>,
+    index: Option<usize>,
+    length: Option<usize>
+}
+
+impl OxcartError {
+    pub fn new(kind: ErrorKind) -> Self {
+        OxcartError {
+            kind,
+            operation: None,
+            index: None,
+            length: None
+        }
+    }
+
+    pub fn with_operation(mut self, operation: &'static str) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn operation(&self) -> Option<&'static str> {
+        self.operation
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+}
+
+impl fmt::Display for OxcartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(operation) = self.operation {
+            write!(f, " in `{}`", operation)?;
+        }
+
+        match (self.index, self.length) {
+            (Some(index), Some(length)) =>
+                write!(f, " (index {}, length {})", index, length)?,
+            (Some(index), None) => write!(f, " (index {})", index)?,
+            (None, Some(length)) => write!(f, " (length {})", length)?,
+            (None, None) => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for OxcartError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::List(err) => Some(err),
+            ErrorKind::Stack(err) => Some(err),
+            ErrorKind::Queue(err) => Some(err),
+            ErrorKind::Deque(err) => Some(err),
+            ErrorKind::Map(err) => Some(err),
+            ErrorKind::Set(err) => Some(err),
+            ErrorKind::PriorityQueue(err) => Some(err),
+            ErrorKind::Cache(err) => Some(err)
+        }
+    }
+}
+
+impl From<ListError> for OxcartError {
+    fn from(err: ListError) -> Self {
+        OxcartError::new(ErrorKind::List(err))
+    }
+}
+
+impl From<StackError> for OxcartError {
+    fn from(err: StackError) -> Self {
+        OxcartError::new(ErrorKind::Stack(err))
+    }
+}
+
+impl From<QueueError> for OxcartError {
+    fn from(err: QueueError) -> Self {
+        OxcartError::new(ErrorKind::Queue(err))
+    }
+}
+
+impl From<DequeError> for OxcartError {
+    fn from(err: DequeError) -> Self {
+        OxcartError::new(ErrorKind::Deque(err))
+    }
+}
+
+impl From<MapError> for OxcartError {
+    fn from(err: MapError) -> Self {
+        OxcartError::new(ErrorKind::Map(err))
+    }
+}
+
+impl From<SetError> for OxcartError {
+    fn from(err: SetError) -> Self {
+        OxcartError::new(ErrorKind::Set(err))
+    }
+}
+
+impl From<PriorityQueueError> for OxcartError {
+    fn from(err: PriorityQueueError) -> Self {
+        OxcartError::new(ErrorKind::PriorityQueue(err))
+    }
+}
+
+impl From<CacheError> for OxcartError {
+    fn from(err: CacheError) -> Self {
+        OxcartError::new(ErrorKind::Cache(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arraylist::ArrayList;
+    use crate::heap::Heap;
+    use crate::list::List;
+    use crate::priority_queue::PriorityQueue;
+
+    fn first_and_max() -> Result<(u64, u64), Box<dyn Error>> {
+        let list: ArrayList<u64> = ArrayList::from(vec![3, 1, 2]);
+        let mut heap: Heap<u64> = Heap::new();
+
+        for elem in list.iter() {
+            heap.push(*elem)?;
+        }
+
+        Ok((*list.get(0)?, heap.pop()?))
+    }
+
+    fn nth(list: &ArrayList<u64>, pos: usize) -> Result<u64, OxcartError> {
+        list.get(pos).copied().map_err(|err| {
+            OxcartError::from(err)
+                .with_operation("nth")
+                .with_index(pos)
+                .with_length(list.iter().len())
+        })
+    }
+
+    #[test]
+    fn test_from_normal() -> Result<(), OxcartError> {
+        let actual_err: OxcartError = ListError::OutOfBounds.into();
+        let expected_err: OxcartError =
+            OxcartError::new(ErrorKind::List(ListError::OutOfBounds));
+
+        assert_eq!(actual_err, expected_err);
+        assert_eq!(OxcartError::from(MapError::KeyNotFound).kind(),
+                   ErrorKind::Map(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_normal() -> Result<(), OxcartError> {
+        let actual_err: OxcartError = OxcartError::from(ListError::OutOfBounds)
+            .with_operation("get")
+            .with_index(4)
+            .with_length(4);
+
+        assert_eq!(actual_err.to_string(),
+                   "list error: index out of bounds in `get` \
+                    (index 4, length 4)");
+        assert_eq!(OxcartError::from(QueueError::OutOfBounds).to_string(),
+                   "queue error: queue is empty");
+        assert_eq!(OxcartError::from(MapError::KeyNotFound).with_length(2)
+                   .to_string(), "map error: key not found (length 2)");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_normal() -> Result<(), OxcartError> {
+        let actual_err: OxcartError =
+            OxcartError::from(PriorityQueueError::OutOfBounds);
+
+        assert_eq!(actual_err.source().map(|err| err.to_string()),
+                   Some(String::from("priority queue is empty")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_mark_normal_boxed() -> Result<(), OxcartError> {
+        assert_eq!(first_and_max().ok(), Some((3, 3)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_err_normal_context() -> Result<(), OxcartError> {
+        let list: ArrayList<u64> = ArrayList::from(vec![3, 1, 2]);
+        let actual_err: Option<OxcartError> = nth(&list, 5).err();
+
+        assert_eq!(nth(&list, 1)?, 1);
+        assert_eq!(actual_err.and_then(|err| err.operation()), Some("nth"));
+        assert_eq!(actual_err.and_then(|err| err.index()), Some(5));
+        assert_eq!(actual_err.and_then(|err| err.length()), Some(3));
+        assert_eq!(actual_err.map(|err| err.kind()),
+                   Some(ErrorKind::List(ListError::OutOfBounds)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_question_mark_normal_oxcart() -> Result<(), OxcartError> {
+        let mut list: ArrayList<u64> = ArrayList::new();
+        let mut heap: Heap<u64> = Heap::new();
+
+        list.append(1)?;
+        heap.push(2)?;
+
+        assert_eq!(list.length()?, 1);
+        assert_eq!(heap.length()?, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,654 @@
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut};
+use std::iter::{Flatten, FromIterator};
+use std::fmt;
+use std::fmt::Display;
+use std::slice;
+use crate::list::{List, ListError, Reserve};
+
+/* elements live in `elems[..gap_start]` and `elems[gap_end..]`, with the
+ * slots in between empty; edits happen at the gap, so clustered edits only
+ * pay for moving the gap the distance between them */
+#[derive(Clone, Debug)]
+pub struct GapBuffer<T> {
+    elems: Vec<Option<T>>,
+    gap_start: usize,
+    gap_end: usize
+}
+
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, Option<T>>,
+    back: slice::Iter<'a, Option<T>>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elem) => elem.as_ref(),
+            None => self.back.next().and_then(|elem| elem.as_ref())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elem) => elem.as_ref(),
+            None => self.front.next_back().and_then(|elem| elem.as_ref())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, Option<T>>,
+    back: slice::IterMut<'a, Option<T>>
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(elem) => elem.as_mut(),
+            None => self.back.next().and_then(|elem| elem.as_mut())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(elem) => elem.as_mut(),
+            None => self.front.next_back().and_then(|elem| elem.as_mut())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<T> GapBuffer<T> {
+    pub fn gap_position(&self) -> usize {
+        self.gap_start
+    }
+
+    pub fn move_gap(&mut self, pos: usize) -> Result<(), ListError> {
+        if pos > self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let gap_len: usize = self.gap_end - self.gap_start;
+
+        if pos < self.gap_start {
+            for i in (pos..self.gap_start).rev() {
+                self.elems.swap(i, i + gap_len);
+            }
+        } else {
+            for i in self.gap_start..pos {
+                self.elems.swap(i, i + gap_len);
+            }
+        }
+
+        self.gap_start = pos;
+        self.gap_end = pos + gap_len;
+
+        Ok(())
+    }
+
+    /* places the (empty) gap after the last element, which is where a
+     * freshly built buffer is most likely to be appended to */
+    fn at_end(mut self) -> Self {
+        self.gap_start = self.elems.len();
+        self.gap_end = self.elems.len();
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.elems.len() - (self.gap_end - self.gap_start)
+    }
+
+    fn physical(&self, pos: usize) -> usize {
+        if pos < self.gap_start {
+            pos
+        } else {
+            pos + (self.gap_end - self.gap_start)
+        }
+    }
+
+    /* rebuilds the buffer with a gap of exactly `gap_len` slots */
+    fn resize_gap(&mut self, gap_len: usize) -> Result<(), ListError> {
+        let mut new_elems: Vec<Option<T>> = Vec::new();
+        let total: usize = match self.len().checked_add(gap_len) {
+            Some(total) => total,
+            None => return Err(ListError::AllocationFailed)
+        };
+
+        if new_elems.try_reserve_exact(total).is_err() {
+            return Err(ListError::AllocationFailed);
+        }
+
+        let tail: Vec<Option<T>> = self.elems.drain(self.gap_end..).collect();
+
+        self.elems.truncate(self.gap_start);
+        new_elems.append(&mut self.elems);
+        new_elems.resize_with(self.gap_start + gap_len, || None);
+        new_elems.extend(tail);
+
+        self.elems = new_elems;
+        self.gap_end = self.gap_start + gap_len;
+
+        Ok(())
+    }
+
+    fn ensure_gap(&mut self, additional: usize) -> Result<(), ListError> {
+        let gap_len: usize = self.gap_end - self.gap_start;
+
+        if gap_len >= additional {
+            return Ok(());
+        }
+
+        let new_gap_len: usize = additional.max(self.len()).max(8);
+
+        self.resize_gap(new_gap_len)
+    }
+}
+
+impl<T> Default for GapBuffer<T> {
+    fn default() -> Self {
+        GapBuffer {
+            elems: Vec::new(),
+            gap_start: 0,
+            gap_end: 0
+        }
+    }
+}
+
+impl<T> PartialEq for GapBuffer<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        List::iter(self).zip(List::iter(other)).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for GapBuffer<T> where T: Eq {}
+
+impl<T> IntoIterator for GapBuffer<T> {
+    type Item = T;
+    type IntoIter = Flatten<std::vec::IntoIter<Option<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.into_iter().flatten()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a GapBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut GapBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter_mut(self)
+    }
+}
+
+impl<T> FromIterator<T> for GapBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        GapBuffer {
+            elems: iter.into_iter().map(Some).collect(),
+            gap_start: 0,
+            gap_end: 0
+        }.at_end()
+    }
+}
+
+impl<T> Extend<T> for GapBuffer<T> {
+    /* like `Vec::extend`, panics rather than dropping elements if the
+     * buffer cannot grow; inserting at the end is never out of bounds */
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter: I::IntoIter = iter.into_iter();
+
+        self.ensure_gap(iter.size_hint().0)
+            .expect("gap buffer capacity overflow");
+
+        for elem in iter {
+            let len: usize = self.len();
+
+            self.insert(len, elem).expect("gap buffer capacity overflow");
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for GapBuffer<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T> Index<usize> for GapBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> IndexMut<usize> for GapBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl<T> Display for GapBuffer<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in List::iter(self) {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+impl<T> List<T> for GapBuffer<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        GapBuffer::default()
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut elems: Vec<Option<T>> = Vec::with_capacity(capacity);
+        elems.resize_with(capacity, || None);
+
+        GapBuffer {
+            elems,
+            gap_start: 0,
+            gap_end: capacity
+        }
+    }
+
+    fn get(&self, pos: usize) -> Result<&T, ListError> {
+        if pos >= self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        match self.elems[self.physical(pos)].as_ref() {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError> {
+        if pos >= self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let pos: usize = self.physical(pos);
+
+        match self.elems[pos].as_mut() {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        *self.get_mut(pos)? = elem;
+        Ok(())
+    }
+
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        if pos > self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.ensure_gap(1)?;
+        self.move_gap(pos)?;
+
+        self.elems[self.gap_start] = Some(elem);
+        self.gap_start += 1;
+
+        Ok(())
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<T, ListError> {
+        if pos >= self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        if pos + 1 == self.gap_start { /* deleting backwards from the gap */
+            self.gap_start -= 1;
+
+            return match self.elems[self.gap_start].take() {
+                Some(elem) => Ok(elem),
+                None => Err(ListError::Impossible)
+            };
+        }
+
+        self.move_gap(pos)?;
+
+        let elem: Option<T> = self.elems[self.gap_end].take();
+        self.gap_end += 1;
+
+        match elem {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        Ok(self.len())
+    }
+
+    fn append(&mut self, elem: T) -> Result<(), ListError> {
+        let len: usize = self.len();
+        self.insert(len, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        if a >= self.len() || b >= self.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let a: usize = self.physical(a);
+        let b: usize = self.physical(b);
+
+        self.elems.swap(a, b);
+        Ok(())
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).any(|curr| curr.borrow() == elem))
+    }
+
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+
+        for (i, curr_elem) in List::iter(self).enumerate() {
+            if curr_elem.borrow() == elem {
+                res.push(i);
+            }
+        }
+
+        Ok(Some(res))
+    }
+
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).position(|curr| curr.borrow() == elem))
+    }
+
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).filter(|curr| (*curr).borrow() == elem).count())
+    }
+
+    fn clear(&mut self) -> Result<(), ListError> {
+        for elem in self.elems.iter_mut() {
+            *elem = None;
+        }
+
+        self.gap_start = 0;
+        self.gap_end = self.elems.len();
+
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        let (front, rest) = self.elems.split_at(self.gap_start);
+
+        Iter {
+            front: front.iter(),
+            back: rest[self.gap_end - self.gap_start..].iter()
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let gap_len: usize = self.gap_end - self.gap_start;
+        let (front, rest) = self.elems.split_at_mut(self.gap_start);
+
+        IterMut {
+            front: front.iter_mut(),
+            back: rest[gap_len..].iter_mut()
+        }
+    }
+}
+
+impl<T> Reserve for GapBuffer<T> {
+    fn capacity(&self) -> Result<usize, ListError> {
+        Ok(self.elems.len())
+    }
+
+    fn reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        self.ensure_gap(additional)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) -> Result<(), ListError> {
+        if self.gap_end - self.gap_start >= additional {
+            return Ok(());
+        }
+
+        self.resize_gap(additional)
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        self.ensure_gap(additional)
+    }
+
+    fn shrink_to_fit(&mut self) -> Result<(), ListError> {
+        self.resize_gap(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(buffer: &GapBuffer<u64>) -> Vec<u64> {
+        List::iter(buffer).cloned().collect()
+    }
+
+    #[test]
+    fn test_new_normal() -> Result<(), ListError> {
+        let actual_buffer: GapBuffer<u64> = GapBuffer::new();
+        let expected_buffer: GapBuffer<u64> = GapBuffer {
+            elems: Vec::new(),
+            gap_start: 0,
+            gap_end: 0
+        };
+
+        assert_eq!(actual_buffer, expected_buffer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_middle() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::new();
+
+        actual_buffer.insert(0, 33)?;
+        actual_buffer.insert(0, 12)?;
+        actual_buffer.insert(0, 3)?;
+        actual_buffer.insert(1, 10)?;
+
+        assert_eq!(contents(&actual_buffer), vec![3, 10, 12, 33]);
+        assert_eq!(actual_buffer.gap_position(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::new();
+
+        let actual_res: Result<(), ListError> = actual_buffer.insert(1, 33);
+        let expected_res: Result<(), ListError> = Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        assert_eq!(actual_buffer.length()?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> =
+            GapBuffer::from(vec![33, 12, 1, 10]);
+
+        assert_eq!(actual_buffer.remove(2)?, 1);
+        assert_eq!(contents(&actual_buffer), vec![33, 12, 10]);
+
+        /* backspacing from the gap */
+        assert_eq!(actual_buffer.remove(1)?, 12);
+        assert_eq!(contents(&actual_buffer), vec![33, 10]);
+
+        assert_eq!(actual_buffer.remove(1)?, 10);
+        assert_eq!(actual_buffer.remove(0)?, 33);
+        assert_eq!(actual_buffer.remove(0), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_gap_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![1, 2, 3]);
+
+        actual_buffer.reserve(4)?;
+        actual_buffer.move_gap(1)?;
+        actual_buffer.insert(1, 7)?;
+        actual_buffer.insert(2, 8)?;
+
+        assert_eq!(actual_buffer.gap_position(), 3);
+        assert_eq!(contents(&actual_buffer), vec![1, 7, 8, 2, 3]);
+
+        actual_buffer.move_gap(5)?;
+        actual_buffer.move_gap(0)?;
+
+        assert_eq!(contents(&actual_buffer), vec![1, 7, 8, 2, 3]);
+        assert_eq!(actual_buffer.move_gap(6), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![1, 2, 3]);
+
+        actual_buffer.insert(1, 9)?;
+        *actual_buffer.get_mut(3)? *= 2;
+        actual_buffer.set(0, 4)?;
+
+        assert_eq!(actual_buffer.get(0)?, &4);
+        assert_eq!(actual_buffer.get(1)?, &9);
+        assert_eq!(actual_buffer[2], 2);
+        assert_eq!(actual_buffer.get(3)?, &6);
+        assert_eq!(actual_buffer.get(4), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_normal_across_gap() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![1, 2, 3]);
+
+        actual_buffer.insert(1, 0)?;
+        actual_buffer.swap(0, 3)?;
+
+        assert_eq!(contents(&actual_buffer), vec![3, 0, 2, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![5, 6, 5]);
+
+        actual_buffer.insert(1, 5)?;
+
+        assert!(actual_buffer.contains(&6)?);
+        assert_eq!(actual_buffer.find(&6)?, Some(2));
+        assert_eq!(actual_buffer.find_all(&5)?, Some(vec![0, 1, 3]));
+        assert_eq!(actual_buffer.count(&5)?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![1, 2, 3]);
+
+        actual_buffer.move_gap(1)?;
+
+        for elem in &mut actual_buffer {
+            *elem += 1;
+        }
+
+        assert_eq!(List::iter(&actual_buffer).rev().cloned()
+                   .collect::<Vec<u64>>(), vec![4, 3, 2]);
+        assert_eq!(List::iter(&actual_buffer).len(), 3);
+        assert_eq!(actual_buffer.into_iter().collect::<Vec<u64>>(),
+                   vec![2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::from(vec![1, 2, 3]);
+
+        actual_buffer.clear()?;
+        actual_buffer.append(4)?;
+
+        assert_eq!(contents(&actual_buffer), vec![4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_capacity_normal() -> Result<(), ListError> {
+        let mut actual_buffer: GapBuffer<u64> = GapBuffer::with_capacity(4);
+
+        assert_eq!(actual_buffer.capacity()?, 4);
+
+        actual_buffer.append(1)?;
+        actual_buffer.reserve_exact(10)?;
+        assert!(actual_buffer.capacity()? >= 11);
+
+        actual_buffer.shrink_to_fit()?;
+        assert_eq!(actual_buffer.capacity()?, 1);
+        assert_eq!(actual_buffer.try_reserve(usize::MAX),
+                   Err(ListError::AllocationFailed));
+        assert_eq!(actual_buffer.reserve(usize::MAX),
+                   Err(ListError::AllocationFailed));
+        assert_eq!(actual_buffer.reserve_exact(usize::MAX),
+                   Err(ListError::AllocationFailed));
+
+        Ok(())
+    }
+}
@@ -1,14 +1,34 @@
+use std::borrow::Borrow;
 use std::collections::BinaryHeap;
-use std::collections::binary_heap::IntoIter;
-use std::fmt::{Debug, Display};
+use std::collections::binary_heap::{IntoIter, Iter};
+use std::iter::FromIterator;
 
-use crate::priority_queue::{PriorityQueue, PriorityQueueError};
+use crate::priority_queue::{PriorityQueue, PriorityQueueError, Reserve};
+
+#[macro_export]
+macro_rules! heap {
+    () => {
+        $crate::heap::Heap::from(::std::vec::Vec::new())
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::heap::Heap::from(::std::vec![$elem; $n])
+    };
+    ($($elem:expr),+ $(,)?) => {
+        $crate::heap::Heap::from(::std::vec![$($elem),+])
+    };
+}
 
 #[derive(Clone, Debug)]
 pub struct Heap<T> {
     elems: BinaryHeap<T>
 }
 
+impl<T> Heap<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.elems.iter()
+    }
+}
+
 impl<T: Eq> PartialEq for Heap<T> {
     fn eq(&self, other: &Self) -> bool {
         let mut local_elems: Vec<&T> = Vec::new();
@@ -32,7 +52,7 @@ impl<T: Eq> PartialEq for Heap<T> {
             }
         }
 
-        return true;
+        true
     }
 }
 
@@ -47,21 +67,101 @@ impl<T> IntoIterator for Heap<T> {
     }
 }
 
-impl<T: Sized + Eq + Clone + Ord + Display + Debug> PriorityQueue<T> for
-    Heap<T> {
+impl<T: Ord> FromIterator<T> for Heap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Heap {
+            elems: BinaryHeap::from_iter(iter)
+        }
+    }
+}
+
+impl<T: Ord> Extend<T> for Heap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.elems.extend(iter);
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for Heap<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.elems.extend(iter);
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for Heap<T> {
+    fn from(elems: Vec<T>) -> Self {
+        Heap {
+            elems: BinaryHeap::from(elems)
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for Heap<T> {
+    fn from(elems: [T; N]) -> Self {
+        Heap {
+            elems: BinaryHeap::from(elems)
+        }
+    }
+}
+
+impl<T: Ord + Clone> From<&[T]> for Heap<T> {
+    fn from(elems: &[T]) -> Self {
+        Heap {
+            elems: BinaryHeap::from(elems.to_vec())
+        }
+    }
+}
+
+impl<T> From<BinaryHeap<T>> for Heap<T> {
+    fn from(elems: BinaryHeap<T>) -> Self {
+        Heap {
+            elems
+        }
+    }
+}
+
+impl<T> From<Heap<T>> for BinaryHeap<T> {
+    fn from(heap: Heap<T>) -> Self {
+        heap.elems
+    }
+}
+
+impl<T> From<Heap<T>> for Vec<T> {
+    fn from(heap: Heap<T>) -> Self {
+        heap.elems.into_vec()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Heap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter()
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for Heap<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+
     fn new() -> Self {
         Heap {
             elems: BinaryHeap::new()
         }
     }
 
+    fn with_capacity(capacity: usize) -> Self {
+        Heap {
+            elems: BinaryHeap::with_capacity(capacity)
+        }
+    }
+
     fn push(&mut self, elem: T) -> Result<(), PriorityQueueError> {
         self.elems.push(elem);
         Ok(())
     }
 
     fn pop(&mut self) -> Result<T, PriorityQueueError> {
-        if self.elems.len() == 0 { /* bounds check */
+        if self.elems.is_empty() { /* bounds check */
             return Err(PriorityQueueError::OutOfBounds);
         }
 
@@ -72,25 +172,22 @@ impl<T: Sized + Eq + Clone + Ord + Display + Debug> PriorityQueue<T> for
     }
 
     fn peek(&self) -> Result<&T, PriorityQueueError> {
-        if self.elems.len() == 0 { /* bounds check */
+        if self.elems.is_empty() { /* bounds check */
             return Err(PriorityQueueError::OutOfBounds);
         }
 
         match self.elems.peek() {
-            Some(elem) => Ok(&elem),
+            Some(elem) => Ok(elem),
             None => Err(PriorityQueueError::OutOfBounds)
         }
     }
 
-    fn find(&self, elem: T) -> Result<Option<usize>, PriorityQueueError> {
-        let mut curr_pos: usize = 0;
-
-        for local_elem in self.elems.iter() {
-            if *local_elem == elem {
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, PriorityQueueError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
+        for (curr_pos, local_elem) in self.elems.iter().enumerate() {
+            if local_elem.borrow() == elem {
                 return Ok(Some(curr_pos));
             }
-
-            curr_pos += 1;
         }
 
         Ok(None)
@@ -99,6 +196,41 @@ impl<T: Sized + Eq + Clone + Ord + Display + Debug> PriorityQueue<T> for
     fn length(&self) -> Result<usize, PriorityQueueError> {
         Ok(self.elems.len())
     }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.elems.iter()
+    }
+}
+
+impl<T> Reserve for Heap<T> {
+    fn capacity(&self) -> Result<usize, PriorityQueueError> {
+        Ok(self.elems.capacity())
+    }
+
+    fn reserve(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError> {
+        self.elems.reserve(additional);
+        Ok(())
+    }
+
+    fn reserve_exact(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError> {
+        self.elems.reserve_exact(additional);
+        Ok(())
+    }
+
+    fn try_reserve(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError> {
+        match self.elems.try_reserve(additional) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(PriorityQueueError::AllocationFailed)
+        }
+    }
+
+    fn shrink_to_fit(&mut self) -> Result<(), PriorityQueueError> {
+        self.elems.shrink_to_fit();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -136,5 +268,179 @@ mod tests {
 
         Ok(())
     }
-}
 
+    #[test]
+    fn test_iter_normal() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = Heap::new();
+
+        for i in 1..10 {
+            actual_priority_queue.push(i)?;
+        }
+
+        let mut actual_elems: Vec<u64> =
+            actual_priority_queue.iter().cloned().collect();
+        actual_elems.sort();
+
+        let expected_elems: Vec<u64> = (1..10).collect();
+
+        assert_eq!(actual_elems, expected_elems);
+        assert_eq!(actual_priority_queue.iter().len(), 9);
+        assert_eq!(actual_priority_queue.length()?, 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_normal_borrowed() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = Heap::new();
+
+        actual_priority_queue.push(4)?;
+        actual_priority_queue.push(5)?;
+
+        let mut sum: u64 = 0;
+
+        for elem in &actual_priority_queue {
+            sum += elem;
+        }
+
+        assert_eq!(sum, 9);
+        assert_eq!(PriorityQueue::iter(&actual_priority_queue).count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_iter_normal() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = (1..4).collect();
+
+        assert_eq!(actual_priority_queue.pop()?, 3);
+        assert_eq!(actual_priority_queue.pop()?, 2);
+        assert_eq!(actual_priority_queue.pop()?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extend_normal() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = Heap::new();
+
+        actual_priority_queue.push(2)?;
+        actual_priority_queue.extend(vec![5, 1]);
+        actual_priority_queue.extend(&[3]);
+
+        assert_eq!(actual_priority_queue.length()?, 4);
+        assert_eq!(actual_priority_queue.pop()?, 5);
+        assert_eq!(actual_priority_queue.pop()?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_normal() -> Result<(), PriorityQueueError> {
+        let slice: &[u64] = &[3, 1, 2];
+        let mut binary_heap: BinaryHeap<u64> = BinaryHeap::new();
+        binary_heap.push(2);
+
+        assert_eq!(Heap::from(vec![3, 1, 2]).pop()?, 3);
+        assert_eq!(Heap::from([3, 1, 2]).pop()?, 3);
+        assert_eq!(Heap::from(slice).pop()?, 3);
+        assert_eq!(Heap::from(binary_heap).pop()?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_vec_normal() -> Result<(), PriorityQueueError> {
+        let actual_priority_queue: Heap<u64> = Heap::from(vec![3, 1, 2]);
+
+        let mut actual_vec: Vec<u64> = actual_priority_queue.into();
+        actual_vec.sort();
+
+        assert_eq!(actual_vec, vec![1, 2, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_heap_macro_normal() -> Result<(), PriorityQueueError> {
+        let empty_priority_queue: Heap<u64> = heap![];
+        let mut actual_priority_queue: Heap<u64> = heap![4, 9, 1];
+
+        assert_eq!(empty_priority_queue.length()?, 0);
+        assert_eq!(heap![7u64; 3].length()?, 3);
+        assert_eq!(actual_priority_queue.pop()?, 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_capacity_normal() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = Heap::with_capacity(16);
+
+        assert!(actual_priority_queue.capacity()? >= 16);
+        assert_eq!(actual_priority_queue.length()?, 0);
+
+        actual_priority_queue.push(1)?;
+        actual_priority_queue.shrink_to_fit()?;
+        actual_priority_queue.reserve(4)?;
+        actual_priority_queue.reserve_exact(8)?;
+
+        assert!(actual_priority_queue.capacity()? >= 9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_reserve_error_allocation_failed() ->
+        Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Heap<u64> = Heap::new();
+
+        let actual_res: Result<(), PriorityQueueError> =
+            actual_priority_queue.try_reserve(usize::MAX);
+        let expected_res: Result<(), PriorityQueueError> =
+            Err(PriorityQueueError::AllocationFailed);
+
+        assert_eq!(actual_priority_queue.try_reserve(4), Ok(()));
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal() -> Result<(), PriorityQueueError> {
+        let actual_priority_queue: Heap<String> =
+            heap![String::from("foo"), String::from("bar")];
+
+        assert!(actual_priority_queue.find("bar")?.is_some());
+        assert_eq!(actual_priority_queue.find("baz"), Ok(None));
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_find_normal_by_value() -> Result<(), PriorityQueueError> {
+        let actual_priority_queue: Heap<u64> = heap![3];
+
+        assert_eq!(actual_priority_queue.find_by_value(3), Ok(Some(0)));
+        assert_eq!(actual_priority_queue.find_by_value(4), Ok(None));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_normal_no_display() -> Result<(), PriorityQueueError> {
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        struct Job {
+            priority: u64,
+            name: &'static str
+        }
+
+        let mut actual_priority_queue: Heap<Job> = Heap::new();
+
+        actual_priority_queue.push(Job { priority: 1, name: "low" })?;
+        actual_priority_queue.push(Job { priority: 9, name: "high" })?;
+
+        assert_eq!(actual_priority_queue.pop()?.name, "high");
+        assert_eq!(actual_priority_queue.pop()?.name, "low");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::cache::{Cache, CacheError, CacheStats};
+use crate::lrucache::LruCache;
+
+/* a bounded map that evicts its least frequently used entry, the least
+ * recently used among equals; entries sit in one recency-ordered bucket per
+ * use count, and the smallest non-empty count is tracked, so lookups,
+ * insertions and evictions are O(1) expected; only an explicit `remove`
+ * that empties the smallest bucket looks across the buckets. each key is
+ * held both in `freqs` and in its bucket, hence `K: Clone` */
+#[derive(Debug)]
+pub struct LfuCache<K, V> {
+    freqs: HashMap<K, u64>,
+    buckets: HashMap<u64, LruCache<K, V>>,
+    min_freq: u64,
+    capacity: usize,
+    stats: CacheStats
+}
+
+impl<K: Hash + Eq + Clone, V> LfuCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LfuCache {
+            freqs: HashMap::new(),
+            buckets: HashMap::new(),
+            min_freq: 0,
+            capacity,
+            stats: CacheStats::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.freqs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.freqs.is_empty()
+    }
+
+    /* the number of uses of `key`, counting the `put` that added it */
+    pub fn frequency<Q>(&self, key: &Q) -> Option<u64> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.freqs.get(key).copied()
+    }
+
+    /* looks `key` up, counting it as a use */
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        let freq: Option<u64> = self.freqs.get(key).copied();
+
+        self.stats.record(freq.is_some());
+
+        let (key_owned, value) = self.take(key, freq?)?;
+
+        self.place(key_owned, value, freq? + 1);
+        self.buckets.get(&(freq? + 1))?.peek(key)
+    }
+
+    /* looks `key` up without counting it as a use */
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.buckets.get(self.freqs.get(key)?)?.peek(key)
+    }
+
+    /* inserts `key` with a single use, or replaces its value as one more
+     * use, returning the entry evicted to make room for it, if any */
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&freq) = self.freqs.get(&key) {
+            self.take(&key, freq);
+            self.place(key, value, freq + 1);
+            return None;
+        }
+
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+
+        let evicted: Option<(K, V)> = if self.len() >= self.capacity {
+            self.evict()
+        } else {
+            None
+        };
+
+        self.place(key, value, 1);
+        self.min_freq = 1;
+        evicted
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        let freq: u64 = self.freqs.remove(key)?;
+        let res: Option<(K, V)> = self.take(key, freq);
+
+        if !self.buckets.contains_key(&self.min_freq) {
+            self.min_freq = self.buckets.keys().min().copied().unwrap_or(0);
+        }
+
+        res
+    }
+
+    pub fn clear(&mut self) {
+        self.freqs.clear();
+        self.buckets.clear();
+        self.min_freq = 0;
+    }
+
+    /* unhooks `key` from the bucket for `freq`, dropping the bucket if it
+     * empties; the caller settles `freqs` and `min_freq` */
+    fn take<Q>(&mut self, key: &Q, freq: u64) -> Option<(K, V)> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let bucket: &mut LruCache<K, V> = self.buckets.get_mut(&freq)?;
+        let res: Option<(K, V)> = bucket.pop(key);
+
+        if bucket.is_empty() {
+            self.buckets.remove(&freq);
+
+            if self.min_freq == freq {
+                self.min_freq = freq + 1;
+            }
+        }
+
+        res
+    }
+
+    fn place(&mut self, key: K, value: V, freq: u64) {
+        match self.freqs.get_mut(&key) {
+            Some(old) => *old = freq,
+            None => {
+                self.freqs.insert(key.clone(), freq);
+            }
+        }
+
+        self.buckets.entry(freq).or_default().put(key, value);
+    }
+
+    fn evict(&mut self) -> Option<(K, V)> {
+        let freq: u64 = self.min_freq;
+        let bucket: &mut LruCache<K, V> = self.buckets.get_mut(&freq)?;
+        let (key, value) = bucket.pop_lru()?;
+
+        if bucket.is_empty() {
+            self.buckets.remove(&freq);
+        }
+
+        self.freqs.remove(&key);
+        Some((key, value))
+    }
+}
+
+impl<K, V> Cache<K, V> for LfuCache<K, V> where K: Hash + Eq + Clone {
+    fn get<Q>(&mut self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        LfuCache::get(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn peek<Q>(&self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        LfuCache::peek(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn put(&mut self, key: K, value: V)
+        -> Result<Option<(K, V)>, CacheError> {
+        Ok(LfuCache::put(self, key, value))
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        match self.pop(key) {
+            Some((_, value)) => Ok(value),
+            None => Err(CacheError::KeyNotFound)
+        }
+    }
+
+    fn contains<Q>(&self, key: &Q) -> Result<bool, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        Ok(self.freqs.contains_key(key))
+    }
+
+    fn size(&self) -> Result<usize, CacheError> {
+        Ok(self.len())
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn clear(&mut self) -> Result<(), CacheError> {
+        LfuCache::clear(self);
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_normal_evicts_lfu() -> Result<(), CacheError> {
+        let mut actual_cache: LfuCache<u64, u64> = LfuCache::new(3);
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        actual_cache.put(3, 30);
+        actual_cache.get(&1);
+        actual_cache.get(&1);
+        actual_cache.get(&3);
+
+        assert_eq!(actual_cache.put(4, 40), Some((2, 20)));
+
+        /* 3 and 4 tie on uses once 4 is read, and 3 was read longer ago */
+        actual_cache.get(&4);
+
+        assert_eq!(actual_cache.put(5, 50), Some((3, 30)));
+        assert_eq!(actual_cache.frequency(&1), Some(3));
+        assert_eq!(actual_cache.frequency(&5), Some(1));
+        assert_eq!(actual_cache.put(6, 60), Some((5, 50)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_normal_replace_counts() -> Result<(), CacheError> {
+        let mut actual_cache: LfuCache<u64, u64> = LfuCache::new(2);
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        actual_cache.put(1, 11);
+
+        assert_eq!(actual_cache.peek(&1), Some(&11));
+        assert_eq!(actual_cache.frequency(&1), Some(2));
+        assert_eq!(actual_cache.put(3, 30), Some((2, 20)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal_min_frequency() -> Result<(), CacheError> {
+        let mut actual_cache: LfuCache<u64, u64> = LfuCache::new(3);
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        actual_cache.get(&2);
+        actual_cache.put(3, 30);
+
+        for _ in 0..3 {
+            actual_cache.get(&3);
+        }
+
+        assert_eq!(Cache::remove(&mut actual_cache, &1)?, 10);
+        assert_eq!(Cache::remove(&mut actual_cache, &1),
+                   Err(CacheError::KeyNotFound));
+
+        actual_cache.put(4, 40);
+        actual_cache.get(&4);
+        actual_cache.get(&4);
+
+        /* with 1 gone, 2 has the fewest uses */
+        assert_eq!(actual_cache.put(5, 50), Some((2, 20)));
+        assert_eq!(actual_cache.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_normal() -> Result<(), CacheError> {
+        let mut actual_cache: LfuCache<&str, u64> = LfuCache::new(0);
+
+        assert_eq!(actual_cache.put("a", 1), Some(("a", 1)));
+        assert_eq!(actual_cache.get("a"), None);
+        assert!(actual_cache.is_empty());
+
+        let mut actual_cache: LfuCache<&str, u64> = LfuCache::new(4);
+
+        actual_cache.put("a", 1);
+        actual_cache.get("a");
+        actual_cache.get("b");
+        actual_cache.peek("b");
+
+        assert_eq!(Cache::stats(&actual_cache),
+                   CacheStats { hits: 1, misses: 1 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), CacheError> {
+        let mut actual_cache: Box<dyn crate::cache::DynCache<u64, u64>> =
+            Box::new(LfuCache::new(2));
+
+        actual_cache.put(1, 10)?;
+        actual_cache.put(2, 20)?;
+        actual_cache.get(&2)?;
+
+        assert_eq!(actual_cache.put(3, 30)?, Some((1, 10)));
+        assert_eq!(actual_cache.get(&1), Err(CacheError::KeyNotFound));
+        assert_eq!(actual_cache.stats().hit_ratio(), 0.5);
+
+        actual_cache.clear()?;
+
+        assert_eq!(actual_cache.size()?, 0);
+        Ok(())
+    }
+}
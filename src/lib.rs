@@ -1,12 +1,41 @@
+pub mod error;
+
 pub mod list;
 pub mod stack;
 pub mod queue;
+pub mod deque;
 pub mod map;
 pub mod priority_queue;
 pub mod set;
+pub mod cache;
 
 pub mod bubblesort;
+pub mod parallel;
+pub mod rng;
 
 pub mod arraylist;
+pub mod gapbuffer;
+pub mod tieredvec;
+pub mod unrolledlist;
+pub mod persistentvector;
+pub mod conslist;
+pub mod persistentstack;
+pub mod persistenthashmap;
+pub mod skiplist;
+pub mod skiplistmap;
+pub mod splaymap;
+mod bst;
+pub mod treapmap;
+pub mod orderstatmap;
+pub mod orderstatset;
+pub mod triemap;
+pub mod radixmap;
+pub mod lrucache;
+pub mod lfucache;
+pub mod arccache;
+pub mod ttlcache;
+pub mod linkedhashmap;
+pub mod rope;
 pub mod heap;
+pub mod arraydeque;
 
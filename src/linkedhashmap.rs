@@ -0,0 +1,839 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::fmt;
+use std::slice;
+use crate::map::{Map, MapError, MapLookup};
+
+#[derive(Clone, Debug)]
+struct Node<K, V> {
+    entry: Option<(K, V)>,
+    hash: u64,
+    prev: Option<usize>,
+    next: Option<usize>,
+    /* the next node in the same bucket */
+    chain: Option<usize>
+}
+
+/* a hash map that remembers the order its keys were inserted in: a hash
+ * index over an arena of nodes threaded on a doubly linked list, oldest at
+ * the front, so lookups and updates stay O(1) expected; replacing a value
+ * keeps the key where it was, unless the map is in access order, where
+ * every `get_mut`, `get_refresh` or `insert` of a key also moves it to the
+ * back; `get` only has `&self`, so it never reorders.
+ *
+ * the buckets of the index are chained through the nodes by the hash each
+ * keeps, so a key is stored once, in its node, and need not be `Clone` */
+#[derive(Clone, Debug)]
+pub struct LinkedHashMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    free: Vec<usize>,
+    /* the first node of each bucket; empty, or a power of two long */
+    buckets: Vec<Option<usize>>,
+    hasher: RandomState,
+    head: Option<usize>,
+    tail: Option<usize>,
+    access_order: bool
+}
+
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    front: Option<usize>,
+    back: Option<usize>,
+    remaining: usize
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: &'a Node<K, V> = &self.nodes[self.front?];
+
+        self.front = node.next;
+        self.remaining -= 1;
+        node.entry.as_ref().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: &'a Node<K, V> = &self.nodes[self.back?];
+
+        self.back = node.prev;
+        self.remaining -= 1;
+        node.entry.as_ref().map(|(k, v)| (k, v))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/* the entries' slots, which `iter_mut` first puts in list order */
+pub struct IterMut<'a, K, V> {
+    nodes: slice::IterMut<'a, Node<K, V>>
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next()?.entry.as_mut().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nodes.next_back()?.entry.as_mut().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+pub struct IntoIter<K, V> {
+    nodes: Vec<Node<K, V>>,
+    front: Option<usize>,
+    back: Option<usize>,
+    remaining: usize
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: &mut Node<K, V> = &mut self.nodes[self.front?];
+
+        self.front = node.next;
+        self.remaining -= 1;
+        node.entry.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node: &mut Node<K, V> = &mut self.nodes[self.back?];
+
+        self.back = node.prev;
+        self.remaining -= 1;
+        node.entry.take()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> LinkedHashMap<K, V> {
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_access_order(&self) -> bool {
+        self.access_order
+    }
+
+    /* oldest first */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            front: self.head,
+            back: self.tail,
+            remaining: self.len()
+        }
+    }
+
+    /* oldest first, like `iter`; the arena is rearranged into that order
+     * beforehand, so the walk itself is over a plain slice */
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let len: usize = self.len();
+
+        self.compact();
+
+        IterMut {
+            nodes: self.nodes[..len].iter_mut()
+        }
+    }
+
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.entry(self.head?)
+    }
+
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.entry(self.tail?)
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.buckets.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    fn entry(&self, node: usize) -> Option<(&K, &V)> {
+        self.nodes[node].entry.as_ref().map(|(k, v)| (k, v))
+    }
+
+    fn unlink(&mut self, node: usize) {
+        let prev: Option<usize> = self.nodes[node].prev;
+        let next: Option<usize> = self.nodes[node].next;
+
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next
+        }
+
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev
+        }
+    }
+
+    fn push_back(&mut self, node: usize) {
+        self.nodes[node].next = None;
+        self.nodes[node].prev = self.tail;
+
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(node),
+            None => self.head = Some(node)
+        }
+
+        self.tail = Some(node);
+    }
+
+    fn relink_back(&mut self, node: usize) {
+        if self.tail != Some(node) {
+            self.unlink(node);
+            self.push_back(node);
+        }
+    }
+
+    /* marks `node` as used, which only matters in access order */
+    fn touch(&mut self, node: usize) {
+        if self.access_order {
+            self.relink_back(node);
+        }
+    }
+
+    /* unhooks `node` from the list and from its bucket and frees its slot */
+    fn detach(&mut self, node: usize) -> Option<(K, V)> {
+        self.unlink(node);
+        self.unchain(node);
+        self.free.push(node);
+        self.nodes[node].entry.take()
+    }
+
+    fn bucket(&self, hash: u64) -> usize {
+        hash as usize & (self.buckets.len() - 1)
+    }
+
+    fn chain(&mut self, node: usize) {
+        let bucket: usize = self.bucket(self.nodes[node].hash);
+
+        self.nodes[node].chain = self.buckets[bucket];
+        self.buckets[bucket] = Some(node);
+    }
+
+    fn unchain(&mut self, node: usize) {
+        let bucket: usize = self.bucket(self.nodes[node].hash);
+        let next: Option<usize> = self.nodes[node].chain;
+
+        if self.buckets[bucket] == Some(node) {
+            self.buckets[bucket] = next;
+            return;
+        }
+
+        let mut prev: Option<usize> = self.buckets[bucket];
+
+        while let Some(i) = prev {
+            if self.nodes[i].chain == Some(node) {
+                self.nodes[i].chain = next;
+                return;
+            }
+
+            prev = self.nodes[i].chain;
+        }
+    }
+
+    /* empties the buckets and files every entry again */
+    fn rechain(&mut self) {
+        let mut node: Option<usize> = self.head;
+
+        for bucket in self.buckets.iter_mut() {
+            *bucket = None;
+        }
+
+        while let Some(i) = node {
+            self.chain(i);
+            node = self.nodes[i].next;
+        }
+    }
+
+    /* swaps two slots of the arena and repoints the links to whatever
+     * lives in them; the chains are left for the caller to rebuild */
+    fn swap_slots(&mut self, a: usize, b: usize) {
+        let moved = |i: usize| if i == a {
+            b
+        } else if i == b {
+            a
+        } else {
+            i
+        };
+
+        self.nodes.swap(a, b);
+
+        for slot in [a, b] {
+            if self.nodes[slot].entry.is_some() {
+                let node: &mut Node<K, V> = &mut self.nodes[slot];
+
+                node.prev = node.prev.map(moved);
+                node.next = node.next.map(moved);
+            }
+        }
+
+        /* only now that both nodes' own links are right, or an adjacent
+         * pair would be repointed twice */
+        for slot in [a, b] {
+            if self.nodes[slot].entry.is_none() {
+                continue;
+            }
+
+            match self.nodes[slot].prev {
+                Some(prev) => self.nodes[prev].next = Some(slot),
+                None => self.head = Some(slot)
+            }
+
+            match self.nodes[slot].next {
+                Some(next) => self.nodes[next].prev = Some(slot),
+                None => self.tail = Some(slot)
+            }
+        }
+    }
+
+    /* permutes the arena into list order with the free slots after the
+     * entries, rewriting the links, chains and free list in place */
+    fn compact(&mut self) {
+        let len: usize = self.len();
+        let mut node: Option<usize> = self.head;
+        let mut moved: bool = false;
+
+        for pos in 0..len {
+            let i: usize = match node {
+                Some(i) => i,
+                None => break
+            };
+
+            if i != pos {
+                self.swap_slots(pos, i);
+                moved = true;
+            }
+
+            node = self.nodes[pos].next;
+        }
+
+        if moved {
+            for (slot, i) in self.free.iter_mut().zip(len..) {
+                *slot = i;
+            }
+
+            self.rechain();
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> LinkedHashMap<K, V> {
+    pub fn new() -> Self {
+        LinkedHashMap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            buckets: Vec::new(),
+            hasher: RandomState::new(),
+            head: None,
+            tail: None,
+            access_order: false
+        }
+    }
+
+    /* a map ordered by last use rather than by insertion, so `front` is
+     * always the least recently used entry */
+    pub fn with_access_order() -> Self {
+        let mut res: LinkedHashMap<K, V> = LinkedHashMap::new();
+
+        res.access_order = true;
+        res
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        let node: usize = self.find(key)?;
+        self.nodes[node].entry.as_ref().map(|(_, v)| v)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let node: usize = self.find(key)?;
+
+        self.touch(node);
+        self.nodes[node].entry.as_mut().map(|(_, v)| v)
+    }
+
+    /* looks `key` up and moves it to the back, whatever the order */
+    pub fn get_refresh<Q>(&mut self, key: &Q) -> Option<&mut V> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let node: usize = self.find(key)?;
+
+        self.relink_back(node);
+        self.nodes[node].entry.as_mut().map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.find(key).is_some()
+    }
+
+    /* appends `key`, or replaces its value where it stands, returning the
+     * value it had */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(node) = self.find(&key) {
+            self.touch(node);
+
+            return self.nodes[node].entry.as_mut()
+                .map(|(_, v)| std::mem::replace(v, value));
+        }
+
+        if self.len() >= self.buckets.len() {
+            let buckets: usize = (self.buckets.len() * 2).max(8);
+
+            self.buckets.resize(buckets, None);
+            self.rechain();
+        }
+
+        let hash: u64 = self.hasher.hash_one(&key);
+        let node: Node<K, V> = Node {
+            entry: Some((key, value)),
+            hash,
+            prev: None,
+            next: None,
+            chain: None
+        };
+        let i: usize = match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        self.chain(i);
+        self.push_back(i);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let node: usize = self.find(key)?;
+        self.detach(node)
+    }
+
+    /* moves `key` to the back as if it had just been inserted, returning
+     * whether it was there */
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        match self.find(key) {
+            Some(node) => {
+                self.relink_back(node);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        let node: usize = self.head?;
+        self.detach(node)
+    }
+
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        let node: usize = self.tail?;
+        self.detach(node)
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        let hash: u64 = self.hasher.hash_one(key);
+        let mut node: Option<usize> = self.buckets[self.bucket(hash)];
+
+        while let Some(i) = node {
+            if self.nodes[i].hash == hash {
+                if let Some((k, _)) = self.nodes[i].entry.as_ref() {
+                    if k.borrow() == key {
+                        return Some(i);
+                    }
+                }
+            }
+
+            node = self.nodes[i].chain;
+        }
+
+        None
+    }
+}
+
+impl<K: Hash + Eq, V> Default for LinkedHashMap<K, V> {
+    fn default() -> Self {
+        LinkedHashMap::new()
+    }
+}
+
+/* equal entries in the same order */
+impl<K, V> PartialEq for LinkedHashMap<K, V> where K: PartialEq,
+    V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for LinkedHashMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for LinkedHashMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        IntoIter {
+            front: self.head,
+            back: self.tail,
+            remaining: self.len(),
+            nodes: std::mem::take(&mut self.nodes)
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LinkedHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for LinkedHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: LinkedHashMap<K, V> = LinkedHashMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for LinkedHashMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Map<K, V> for LinkedHashMap<K, V> where K: Hash + Eq {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        LinkedHashMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len())
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        LinkedHashMap::clear(self);
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        LinkedHashMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        LinkedHashMap::iter_mut(self)
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for LinkedHashMap<K, V> where
+    K: Hash + Eq + Borrow<Q>, Q: Hash + Eq + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        LinkedHashMap::get(self, key)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        LinkedHashMap::get_mut(self, key)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        LinkedHashMap::remove(self, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(map: &LinkedHashMap<&'static str, u64>) -> Vec<&'static str> {
+        map.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn test_into_iter_normal_insertion_order() -> Result<(), MapError> {
+        let mut actual_map: LinkedHashMap<&str, u64> = LinkedHashMap::new();
+
+        actual_map.set("zeta", 1)?;
+        actual_map.set("alpha", 2)?;
+        actual_map.set("mid", 3)?;
+        actual_map.set("zeta", 4)?;
+
+        assert_eq!(Map::get(&actual_map, "alpha")?, &2);
+        assert_eq!(actual_map.to_string(), "{zeta: 4, alpha: 2, mid: 3, }");
+        assert_eq!(actual_map.into_iter().collect::<Vec<(&str, u64)>>(),
+                   vec![("zeta", 4), ("alpha", 2), ("mid", 3)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_to_back_normal() -> Result<(), MapError> {
+        let mut actual_map: LinkedHashMap<&str, u64> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+
+        assert!(actual_map.move_to_back("a"));
+        assert!(!actual_map.move_to_back("d"));
+        assert_eq!(keys(&actual_map), vec!["b", "c", "a"]);
+
+        assert!(actual_map.move_to_back("a"));
+        assert_eq!(keys(&actual_map), vec!["b", "c", "a"]);
+
+        *actual_map.get_refresh("b").ok_or(MapError::KeyNotFound)? += 10;
+
+        assert_eq!(keys(&actual_map), vec!["c", "a", "b"]);
+        assert_eq!(actual_map.back(), Some((&"b", &12)));
+        assert_eq!(actual_map.get_refresh("d"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_normal_both_ends() -> Result<(), MapError> {
+        let mut actual_map: LinkedHashMap<&str, u64> =
+            vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)].into_iter()
+                .collect();
+
+        assert_eq!(actual_map.pop_front(), Some(("a", 1)));
+        assert_eq!(actual_map.pop_back(), Some(("d", 4)));
+        assert_eq!(actual_map.front(), Some((&"b", &2)));
+        assert_eq!(actual_map.back(), Some((&"c", &3)));
+        assert!(!actual_map.contains_key("a"));
+
+        actual_map.insert("a", 5);
+
+        assert_eq!(keys(&actual_map), vec!["b", "c", "a"]);
+        assert_eq!(actual_map.iter().rev().map(|(k, _)| *k)
+                   .collect::<Vec<&str>>(), vec!["a", "c", "b"]);
+        assert_eq!(actual_map.nodes.len(), 4);
+
+        actual_map.pop_front();
+        actual_map.pop_front();
+        actual_map.pop_front();
+
+        assert_eq!(actual_map.pop_back(), None);
+        assert!(actual_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal_access_order() -> Result<(), MapError> {
+        let mut actual_map: LinkedHashMap<&str, u64> =
+            LinkedHashMap::with_access_order();
+
+        actual_map.insert("a", 1);
+        actual_map.insert("b", 2);
+        actual_map.insert("c", 3);
+
+        assert_eq!(actual_map.get_refresh("a"), Some(&mut 1));
+        assert_eq!(keys(&actual_map), vec!["b", "c", "a"]);
+
+        *Map::get_mut(&mut actual_map, "b")? += 10;
+        actual_map.insert("c", 30);
+
+        assert_eq!(keys(&actual_map), vec!["a", "b", "c"]);
+
+        /* neither checking for a key nor a shared lookup is a use */
+        assert!(actual_map.contains_key("a"));
+        assert_eq!(Map::get(&actual_map, "a")?, &1);
+        assert_eq!(keys(&actual_map), vec!["a", "b", "c"]);
+        assert_eq!(actual_map.pop_front(), Some(("a", 1)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_eq_normal_order_sensitive() -> Result<(), MapError> {
+        let actual_map: LinkedHashMap<u64, u64> =
+            vec![(1, 1), (2, 2)].into_iter().collect();
+        let reordered_map: LinkedHashMap<u64, u64> =
+            vec![(2, 2), (1, 1)].into_iter().collect();
+
+        assert_ne!(actual_map, reordered_map);
+        assert_eq!(actual_map, actual_map.clone());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal_after_churn() -> Result<(), MapError> {
+        let mut actual_map: LinkedHashMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+        let mut expected_keys: Vec<u64> = Vec::new();
+
+        for i in (0..100).step_by(3) {
+            actual_map.remove(&i);
+        }
+
+        for i in (1..100).step_by(7) {
+            actual_map.move_to_back(&i);
+        }
+
+        actual_map.insert(200, 200);
+        actual_map.insert(0, 0);
+
+        expected_keys.extend(actual_map.iter().map(|(k, _)| *k));
+
+        for (key, value) in actual_map.iter_mut() {
+            *value = key + 1000;
+        }
+
+        let actual_keys: Vec<u64> = actual_map.iter_mut().rev()
+            .map(|(k, _)| *k)
+            .collect();
+
+        assert_eq!(actual_keys.into_iter().rev().collect::<Vec<u64>>(),
+                   expected_keys);
+        assert_eq!(actual_map.iter().map(|(k, _)| *k).collect::<Vec<u64>>(),
+                   expected_keys);
+
+        for key in expected_keys.iter() {
+            assert_eq!(actual_map.get(key), Some(&(key + 1000)));
+        }
+
+        actual_map.insert(300, 300);
+
+        assert_eq!(actual_map.back(), Some((&300, &300)));
+        assert_eq!(actual_map.remove(&200), Some(1200));
+        assert_eq!(actual_map.get(&3), None);
+        assert_eq!(actual_map.len(), expected_keys.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_key_not_clone() -> Result<(), MapError> {
+        #[derive(PartialEq, Eq, Hash, Debug)]
+        struct Name(&'static str);
+
+        let mut actual_map: LinkedHashMap<Name, u64> = LinkedHashMap::new();
+
+        actual_map.insert(Name("b"), 2);
+        actual_map.insert(Name("a"), 1);
+
+        assert_eq!(actual_map.get(&Name("a")), Some(&1));
+        assert_eq!(actual_map.pop_front(), Some((Name("b"), 2)));
+        assert_eq!(Map::get(&actual_map, &Name("b")),
+                   Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), MapError> {
+        let mut actual_map: Box<dyn crate::map::DynMap<u64, u64>> =
+            Box::new(LinkedHashMap::new());
+
+        actual_map.set(3, 30)?;
+        actual_map.set(1, 10)?;
+        actual_map.set(2, 20)?;
+        actual_map.remove(&1)?;
+
+        for (_, value) in actual_map.iter_mut() {
+            *value += 1;
+        }
+
+        assert_eq!(actual_map.iter().map(|(k, v)| (*k, *v))
+                   .collect::<Vec<(u64, u64)>>(), vec![(3, 31), (2, 21)]);
+        assert_eq!(actual_map.remove(&1), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+}
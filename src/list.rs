@@ -1,5 +1,7 @@
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
 use std::ops::{Index, IndexMut};
-use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
@@ -7,13 +9,90 @@ use std::hash::Hash;
 pub enum ListError {
     OutOfBounds,
     Impossible,
+    AllocationFailed,
 }
 
 impl Eq for ListError {}
 
-pub trait List<T: Sized + Clone + Eq + Display + Debug>: Clone + Eq + Debug +
-    Display + IntoIterator + Index<usize> + IndexMut<usize>  {
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListError::OutOfBounds => write!(f, "index out of bounds"),
+            ListError::Impossible => write!(f, "impossible state reached"),
+            ListError::AllocationFailed => write!(f, "memory allocation failed")
+        }
+    }
+}
+
+impl Error for ListError {}
+
+pub trait List<T>: IntoIterator + Index<usize> + IndexMut<usize> {
+    type Iter<'a>: Iterator<Item = &'a T> + DoubleEndedIterator +
+        ExactSizeIterator where Self: 'a, T: 'a;
+    type IterMut<'a>: Iterator<Item = &'a mut T> + DoubleEndedIterator +
+        ExactSizeIterator where Self: 'a, T: 'a;
+
     fn new() -> Self;
+    fn with_capacity(capacity: usize) -> Self;
+    fn get(&self, pos: usize) -> Result<&T, ListError>;
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError>;
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError>;
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError>;
+    fn remove(&mut self, pos: usize) -> Result<T, ListError>;
+    fn length(&self) -> Result<usize, ListError>;
+    fn append(&mut self, elem: T) -> Result<(), ListError>;
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError>;
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized;
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized;
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized;
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized;
+    fn clear(&mut self) -> Result<(), ListError>;
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    #[deprecated(note = "use `contains` with a reference instead")]
+    fn contains_by_value(&self, elem: T) -> Result<bool, ListError> where
+        T: Eq {
+        self.contains(&elem)
+    }
+
+    #[deprecated(note = "use `find_all` with a reference instead")]
+    fn find_all_by_value(&self, elem: T) ->
+        Result<Option<Vec<usize>>, ListError> where T: Eq {
+        self.find_all(&elem)
+    }
+
+    #[deprecated(note = "use `find` with a reference instead")]
+    fn find_by_value(&self, elem: T) -> Result<Option<usize>, ListError> where
+        T: Eq {
+        self.find(&elem)
+    }
+
+    #[deprecated(note = "use `count` with a reference instead")]
+    fn count_by_value(&self, elem: T) -> Result<usize, ListError> where
+        T: Eq {
+        self.count(&elem)
+    }
+}
+
+/* capacity management for lists backed by contiguous storage; node-based
+ * lists have no spare capacity to report or reserve, so they leave this out.
+ * every method is object safe, so `dyn Reserve` needs no separate trait */
+pub trait Reserve {
+    fn capacity(&self) -> Result<usize, ListError>;
+    fn reserve(&mut self, additional: usize) -> Result<(), ListError>;
+    fn reserve_exact(&mut self, additional: usize) -> Result<(), ListError>;
+    fn try_reserve(&mut self, additional: usize) -> Result<(), ListError>;
+    fn shrink_to_fit(&mut self) -> Result<(), ListError>;
+}
+
+/* object-safe subset of `List`, so that the backing implementation can be
+ * chosen at runtime via `Box<dyn DynList<T>>` */
+pub trait DynList<T> {
     fn get(&self, pos: usize) -> Result<&T, ListError>;
     fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError>;
     fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError>;
@@ -22,10 +101,143 @@ pub trait List<T: Sized + Clone + Eq + Display + Debug>: Clone + Eq + Debug +
     fn length(&self) -> Result<usize, ListError>;
     fn append(&mut self, elem: T) -> Result<(), ListError>;
     fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError>;
-    fn contains(&self, elem: T) -> Result<bool, ListError>;
-    fn find_all(&self, elem: T) -> Result<Option<Vec<usize>>, ListError>;
-    fn find(&self, elem: T) -> Result<Option<usize>, ListError>;
-    fn count(&self, elem: T) -> Result<usize, ListError>;
+    fn contains(&self, elem: &T) -> Result<bool, ListError> where T: Eq;
+    fn find_all(&self, elem: &T) -> Result<Option<Vec<usize>>, ListError>
+        where T: Eq;
+    fn find(&self, elem: &T) -> Result<Option<usize>, ListError> where T: Eq;
+    fn count(&self, elem: &T) -> Result<usize, ListError> where T: Eq;
     fn clear(&mut self) -> Result<(), ListError>;
+    fn iter(&self) -> Box<dyn DoubleEndedIterator<Item = &T> + '_>;
+    fn iter_mut(&mut self) -> Box<dyn DoubleEndedIterator<Item = &mut T> + '_>;
+}
+
+impl<T, L> DynList<T> for L where L: List<T> {
+    fn get(&self, pos: usize) -> Result<&T, ListError> {
+        List::get(self, pos)
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError> {
+        List::get_mut(self, pos)
+    }
+
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        List::set(self, pos, elem)
+    }
+
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        List::insert(self, pos, elem)
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<T, ListError> {
+        List::remove(self, pos)
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        List::length(self)
+    }
+
+    fn append(&mut self, elem: T) -> Result<(), ListError> {
+        List::append(self, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        List::swap(self, a, b)
+    }
+
+    fn contains(&self, elem: &T) -> Result<bool, ListError> where T: Eq {
+        List::contains(self, elem)
+    }
+
+    fn find_all(&self, elem: &T) -> Result<Option<Vec<usize>>, ListError>
+        where T: Eq {
+        List::find_all(self, elem)
+    }
+
+    fn find(&self, elem: &T) -> Result<Option<usize>, ListError> where T: Eq {
+        List::find(self, elem)
+    }
+
+    fn count(&self, elem: &T) -> Result<usize, ListError> where T: Eq {
+        List::count(self, elem)
+    }
+
+    fn clear(&mut self) -> Result<(), ListError> {
+        List::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn DoubleEndedIterator<Item = &T> + '_> {
+        Box::new(List::iter(self))
+    }
+
+    fn iter_mut(&mut self) ->
+        Box<dyn DoubleEndedIterator<Item = &mut T> + '_> {
+        Box::new(List::iter_mut(self))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arraylist::ArrayList;
+
+    fn make_list(kind: &str) -> Box<dyn DynList<u64>> {
+        match kind {
+            "arraylist" => Box::new(ArrayList::new()),
+            _ => unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_dyn_list_normal() -> Result<(), ListError> {
+        let mut actual_list: Box<dyn DynList<u64>> = make_list("arraylist");
+
+        actual_list.append(33)?;
+        actual_list.append(12)?;
+        actual_list.insert(0, 1)?;
+        actual_list.swap(0, 2)?;
+        *actual_list.get_mut(1)? += 1;
+
+        let actual_elems: Vec<u64> = actual_list.iter().cloned().collect();
+        let expected_elems: Vec<u64> = vec![12, 34, 1];
+
+        assert_eq!(actual_elems, expected_elems);
+        assert_eq!(actual_list.length()?, 3);
+        assert_eq!(actual_list.find(&1)?, Some(2));
+        assert_eq!(actual_list.remove(0)?, 12);
+        assert_eq!(actual_list.get(0)?, &34);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_list_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_list: Box<dyn DynList<u64>> = make_list("arraylist");
+
+        let actual_res: Result<u64, ListError> = actual_list.remove(0);
+        let expected_res: Result<u64, ListError> =
+            Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_list_normal_iter_mut() -> Result<(), ListError> {
+        let mut lists: Vec<Box<dyn DynList<u64>>> = vec![
+            Box::new(ArrayList::from(vec![1, 2])),
+            Box::new(ArrayList::from(vec![3]))
+        ];
+
+        for list in lists.iter_mut() {
+            for elem in list.iter_mut().rev() {
+                *elem *= 10;
+            }
+        }
+
+        assert_eq!(lists[0].count(&20)?, 1);
+        assert!(lists[1].contains(&30)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,505 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::iter::{FromIterator, Rev};
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+use crate::cache::{Cache, CacheError, CacheStats};
+use crate::linkedhashmap::{self, LinkedHashMap};
+use crate::map::{Map, MapError, MapLookup};
+
+type Callback<K, V> = Box<dyn FnMut(&K, &V) + Send>;
+
+/* a bounded map that evicts its least recently used entry: a
+ * `LinkedHashMap` in access order, whose front is the entry to evict, so
+ * every operation is O(1) expected. a use moves the entry, so `get` takes
+ * `&mut self` and `peek` is the lookup that leaves the order alone; the
+ * eviction callback sees every entry that `put`, `set` or `resize` pushes
+ * out */
+pub struct LruCache<K, V> {
+    entries: LinkedHashMap<K, V>,
+    capacity: usize,
+    /* behind a `Mutex` only so that the cache is still `Sync`; it is
+     * reached through `&mut self`, so it is never actually locked */
+    on_evict: Option<Mutex<Callback<K, V>>>,
+    stats: CacheStats
+}
+
+/* the map keeps the most recent entry at the back, so these walk it
+ * backwards */
+pub type Iter<'a, K, V> = Rev<linkedhashmap::Iter<'a, K, V>>;
+pub type IterMut<'a, K, V> = Rev<linkedhashmap::IterMut<'a, K, V>>;
+pub type IntoIter<K, V> = Rev<linkedhashmap::IntoIter<K, V>>;
+
+impl<K, V> LruCache<K, V> {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /* most recently used first */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.entries.iter().rev()
+    }
+
+    /* the entry that would be evicted next */
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.entries.front()
+    }
+
+    /* drops every entry, without the callback */
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<K: Hash + Eq, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            entries: LinkedHashMap::with_access_order(),
+            capacity,
+            on_evict: None,
+            stats: CacheStats::default()
+        }
+    }
+
+    /* `callback` is handed each entry as it is evicted */
+    pub fn with_eviction_callback<F>(capacity: usize, callback: F) -> Self
+        where F: FnMut(&K, &V) + Send + 'static {
+        let mut res: LruCache<K, V> = LruCache::new(capacity);
+
+        res.on_evict = Some(Mutex::new(Box::new(callback)));
+        res
+    }
+
+    /* looks `key` up and marks it as the most recently used */
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let res: Option<&mut V> = self.entries.get_refresh(key);
+
+        self.stats.record(res.is_some());
+        res
+    }
+
+    /* looks `key` up without touching its recency */
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.entries.get(key)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.entries.contains_key(key)
+    }
+
+    /* inserts or replaces `key` as the most recently used entry, returning
+     * the entry evicted to make room for it, if any; with no capacity at
+     * all, that is the new entry itself */
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.entries.contains_key(&key) {
+            self.entries.insert(key, value);
+            return None;
+        }
+
+        if self.capacity == 0 {
+            self.notify(&key, &value);
+            return Some((key, value));
+        }
+
+        let evicted: Option<(K, V)> = if self.len() >= self.capacity {
+            self.evict()
+        } else {
+            None
+        };
+
+        self.entries.insert(key, value);
+        evicted
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.entries.remove_entry(key)
+    }
+
+    /* removes the least recently used entry, without the callback */
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.pop_front()
+    }
+
+    /* changes the capacity, evicting from the least recent end until the
+     * entries fit */
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+
+        while self.len() > self.capacity {
+            self.evict();
+        }
+    }
+
+    fn evict(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.pop_lru()?;
+
+        self.notify(&key, &value);
+        Some((key, value))
+    }
+
+    fn notify(&mut self, key: &K, value: &V) {
+        /* a callback that panicked before can still be called */
+        if let Some(callback) = self.on_evict.as_mut() {
+            let call: &mut Callback<K, V> =
+                callback.get_mut().unwrap_or_else(PoisonError::into_inner);
+
+            call(key, value);
+        }
+    }
+}
+
+/* unbounded, so it never evicts */
+impl<K: Hash + Eq, V> Default for LruCache<K, V> {
+    fn default() -> Self {
+        LruCache::new(usize::MAX)
+    }
+}
+
+impl<K, V> fmt::Debug for LruCache<K, V> where K: fmt::Debug,
+    V: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("entries", &self.iter().collect::<Vec<(&K, &V)>>())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/* equal entries in the same recency order */
+impl<K, V> PartialEq for LruCache<K, V> where K: PartialEq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for LruCache<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for LruCache<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter().rev()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LruCache<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/* unbounded, with the last entry yielded the most recent */
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for LruCache<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: LruCache<K, V> = LruCache::default();
+
+        for (key, value) in iter {
+            res.put(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for LruCache<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+/* `Map::new` makes an unbounded cache; `get_mut` and `set` count as uses,
+ * while `get`, which only has `&self`, peeks, as do `contains_key` and the
+ * iterators */
+impl<K, V> Map<K, V> for LruCache<K, V> where K: Hash + Eq {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        LruCache::default()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.put(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len())
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        LruCache::clear(self);
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        LruCache::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.entries.iter_mut().rev()
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for LruCache<K, V> where
+    K: Hash + Eq + Borrow<Q>, Q: Hash + Eq + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        LruCache::peek(self, key)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        LruCache::get_mut(self, key)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        self.pop(key).map(|(_, value)| value)
+    }
+}
+
+impl<K, V> Cache<K, V> for LruCache<K, V> where K: Hash + Eq {
+    fn get<Q>(&mut self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        LruCache::get(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn peek<Q>(&self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        LruCache::peek(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn put(&mut self, key: K, value: V)
+        -> Result<Option<(K, V)>, CacheError> {
+        Ok(LruCache::put(self, key, value))
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        match self.pop(key) {
+            Some((_, value)) => Ok(value),
+            None => Err(CacheError::KeyNotFound)
+        }
+    }
+
+    fn contains<Q>(&self, key: &Q) -> Result<bool, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        Ok(LruCache::contains(self, key))
+    }
+
+    fn size(&self) -> Result<usize, CacheError> {
+        Ok(self.len())
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn clear(&mut self) -> Result<(), CacheError> {
+        LruCache::clear(self);
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn keys(cache: &LruCache<u64, u64>) -> Vec<u64> {
+        cache.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn test_put_normal_evicts_lru() -> Result<(), MapError> {
+        let mut actual_cache: LruCache<u64, u64> = LruCache::new(3);
+
+        assert_eq!(actual_cache.put(1, 10), None);
+        assert_eq!(actual_cache.put(2, 20), None);
+        assert_eq!(actual_cache.put(3, 30), None);
+        assert_eq!(LruCache::get(&mut actual_cache, &1), Some(&10));
+        assert_eq!(actual_cache.put(4, 40), Some((2, 20)));
+        assert_eq!(keys(&actual_cache), vec![4, 1, 3]);
+
+        /* replacing is a use, not an eviction */
+        assert_eq!(actual_cache.put(3, 31), None);
+        assert_eq!(keys(&actual_cache), vec![3, 4, 1]);
+        assert_eq!(actual_cache.peek_lru(), Some((&1, &10)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_peek_normal_keeps_order() -> Result<(), MapError> {
+        let mut actual_cache: LruCache<u64, u64> = LruCache::new(2);
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+
+        assert_eq!(actual_cache.peek(&1), Some(&10));
+        assert_eq!(actual_cache.put(3, 30), Some((1, 10)));
+        assert_eq!(actual_cache.peek(&1), None);
+        assert_eq!(Map::get(&actual_cache, &2)?, &20);
+        assert_eq!(actual_cache.put(4, 40), Some((2, 20)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resize_normal_callback() -> Result<(), MapError> {
+        let evicted: Arc<Mutex<Vec<u64>>> = Arc::new(Mutex::new(vec![]));
+        let log: Arc<Mutex<Vec<u64>>> = Arc::clone(&evicted);
+        let mut actual_cache: LruCache<u64, u64> =
+            LruCache::with_eviction_callback(4, move |k, _| {
+                if let Ok(mut log) = log.lock() {
+                    log.push(*k);
+                }
+            });
+        let logged = || -> Vec<u64> {
+            evicted.lock().map(|log| log.clone()).unwrap_or_default()
+        };
+
+        for i in 0..6 {
+            actual_cache.put(i, i * 10);
+        }
+
+        actual_cache.resize(2);
+
+        assert_eq!(logged(), vec![0, 1, 2, 3]);
+        assert_eq!(keys(&actual_cache), vec![5, 4]);
+        assert_eq!(actual_cache.capacity(), 2);
+
+        /* explicit removals are not evictions */
+        actual_cache.pop(&5);
+        actual_cache.pop_lru();
+
+        assert_eq!(logged(), vec![0, 1, 2, 3]);
+        assert!(actual_cache.is_empty());
+
+        actual_cache.resize(0);
+
+        assert_eq!(actual_cache.put(7, 70), Some((7, 70)));
+        assert_eq!(logged(), vec![0, 1, 2, 3, 7]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_normal_send_sync() {
+        fn shareable<T: Send + Sync>(_: &T) {}
+
+        shareable(&LruCache::<u64, u64>::with_eviction_callback(1,
+                                                                |_, _| {}));
+    }
+
+    #[test]
+    fn test_remove_normal_interleaved() -> Result<(), MapError> {
+        let mut actual_cache: LruCache<u64, u64> = LruCache::new(3);
+
+        for i in 0..100 {
+            actual_cache.put(i, i);
+
+            if i % 2 == 0 {
+                Map::remove(&mut actual_cache, &i)?;
+            }
+        }
+
+        assert_eq!(keys(&actual_cache), vec![99, 97, 95]);
+        assert_eq!(actual_cache.len(), 3);
+        assert_eq!(Map::remove(&mut actual_cache, &98),
+                   Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_normal() -> Result<(), MapError> {
+        let actual_cache: LruCache<u64, u64> =
+            vec![(1, 10), (2, 20), (1, 11)].into_iter().collect();
+
+        assert_eq!(actual_cache.to_string(), "{1: 11, 2: 20, }");
+        assert_eq!(actual_cache.into_iter().collect::<Vec<(u64, u64)>>(),
+                   vec![(1, 11), (2, 20)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_normal() -> Result<(), CacheError> {
+        let mut actual_cache: LruCache<u64, u64> = LruCache::new(2);
+
+        Cache::put(&mut actual_cache, 1, 10)?;
+        Cache::put(&mut actual_cache, 2, 20)?;
+
+        assert_eq!(Cache::get(&mut actual_cache, &1)?, &10);
+        assert_eq!(Cache::put(&mut actual_cache, 3, 30)?, Some((2, 20)));
+        assert_eq!(Cache::get(&mut actual_cache, &2),
+                   Err(CacheError::KeyNotFound));
+        assert_eq!(Cache::peek(&actual_cache, &3)?, &30);
+        assert_eq!(Cache::stats(&actual_cache),
+                   CacheStats { hits: 1, misses: 1 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), MapError> {
+        let mut actual_cache: Box<dyn crate::map::DynMap<u64, u64>> =
+            Box::new(LruCache::new(2));
+
+        actual_cache.set(1, 10)?;
+        actual_cache.set(2, 20)?;
+        *actual_cache.get_mut(&1)? += 1;
+        actual_cache.set(3, 30)?;
+
+        assert_eq!(actual_cache.size()?, 2);
+        assert!(!actual_cache.contains_key(&2)?);
+
+        for (_, value) in actual_cache.iter_mut() {
+            *value += 100;
+        }
+
+        assert_eq!(actual_cache.iter().map(|(k, v)| (*k, *v))
+                   .collect::<Vec<(u64, u64)>>(), vec![(3, 130), (1, 111)]);
+
+        Ok(())
+    }
+}
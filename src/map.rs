@@ -1,19 +1,187 @@
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeBounds;
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 #[allow(dead_code)]
 pub enum MapError {
     KeyNotFound,
 }
 
-pub trait Map<K: Sized + Eq + Clone, V: Sized + Eq + Clone>: IntoIterator +
-    Eq + Clone {
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::KeyNotFound => write!(f, "key not found")
+        }
+    }
+}
+
+impl Error for MapError {}
+
+pub trait Map<K: Eq, V>: IntoIterator {
+    type Iter<'a>: Iterator<Item = (&'a K, &'a V)> where Self: 'a, K: 'a,
+        V: 'a;
+    type IterMut<'a>: Iterator<Item = (&'a K, &'a mut V)> where Self: 'a,
+        K: 'a, V: 'a;
+
     fn new() -> Self;
-    fn get(&self, key: K) -> Result<&V, MapError>;
-    fn get_mut(&mut self, key: K) -> Result<&mut V, MapError>;
     fn set(&mut self, key: K, value: V) -> Result<(), MapError>;
-    fn remove(&mut self, key: K) -> Result<(), MapError>;
     fn size(&self) -> Result<usize, MapError>;
-    fn contains_key(&self, key: K) -> Result<bool, MapError>;
-    fn contains_value(&self, value: V) -> Result<bool, MapError>;
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq;
+    fn clear(&mut self) -> Result<(), MapError>;
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    fn get<Q>(&self, key: &Q) -> Result<&V, MapError> where
+        Self: MapLookup<K, V, Q>, Q: ?Sized {
+        self.lookup(key).ok_or(MapError::KeyNotFound)
+    }
+
+    fn get_mut<Q>(&mut self, key: &Q) -> Result<&mut V, MapError> where
+        Self: MapLookup<K, V, Q>, Q: ?Sized {
+        self.lookup_mut(key).ok_or(MapError::KeyNotFound)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<(), MapError> where
+        Self: MapLookup<K, V, Q>, Q: ?Sized {
+        match self.lookup_remove(key) {
+            Some(_) => Ok(()),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    fn contains_key<Q>(&self, key: &Q) -> Result<bool, MapError> where
+        Self: MapLookup<K, V, Q>, Q: ?Sized {
+        Ok(self.lookup(key).is_some())
+    }
+
+    #[deprecated(note = "use `get` with a reference instead")]
+    fn get_by_value(&self, key: K) -> Result<&V, MapError> where
+        Self: MapLookup<K, V, K> {
+        self.get(&key)
+    }
+
+    #[deprecated(note = "use `get_mut` with a reference instead")]
+    fn get_mut_by_value(&mut self, key: K) -> Result<&mut V, MapError> where
+        Self: MapLookup<K, V, K> {
+        self.get_mut(&key)
+    }
+
+    #[deprecated(note = "use `remove` with a reference instead")]
+    fn remove_by_value(&mut self, key: K) -> Result<(), MapError> where
+        Self: MapLookup<K, V, K> {
+        self.remove(&key)
+    }
+
+    #[deprecated(note = "use `contains_key` with a reference instead")]
+    fn contains_key_by_value(&self, key: K) -> Result<bool, MapError> where
+        Self: MapLookup<K, V, K> {
+        self.contains_key(&key)
+    }
+
+    #[deprecated(note = "use `contains_value` with a reference instead")]
+    fn contains_value_by_value(&self, value: V) -> Result<bool, MapError>
+        where V: Eq {
+        self.contains_value(&value)
+    }
+}
+
+/* how a map finds the entry for a key given in a borrowed form `Q`; each
+ * map states what it needs of `Q` in its own impl, so hashed maps ask for
+ * `Hash + Eq` and ordered maps for `Ord` rather than every key needing all
+ * of them */
+pub trait MapLookup<K, V, Q: ?Sized> {
+    fn lookup(&self, key: &Q) -> Option<&V>;
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V>;
+    fn lookup_remove(&mut self, key: &Q) -> Option<V>;
+}
+
+/* a map kept in key order; the neighbour queries fail with `KeyNotFound`
+ * when no key meets them, and `first`/`last`/`pop_*` when the map is empty */
+pub trait OrderedMap<K: Ord, V>: Map<K, V> + Sized {
+    type Range<'a>: Iterator<Item = (&'a K, &'a V)> where Self: 'a, K: 'a,
+        V: 'a;
+
+    fn first(&self) -> Result<(&K, &V), MapError>;
+    fn last(&self) -> Result<(&K, &V), MapError>;
+    /* the greatest key at or below `key` */
+    fn floor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized;
+    /* the least key at or above `key` */
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized;
+    /* the greatest key strictly below `key` */
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized;
+    /* the least key strictly above `key` */
+    fn successor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized;
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q>;
+    fn pop_first(&mut self) -> Result<(K, V), MapError>;
+    fn pop_last(&mut self) -> Result<(K, V), MapError>;
+    /* moves the entries with keys at or above `key` into a new map */
+    fn split_off<Q>(&mut self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Ord + ?Sized;
+}
+
+/* object-safe subset of `Map` for use as `Box<dyn DynMap<K, V>>` */
+pub trait DynMap<K, V> {
+    fn get(&self, key: &K) -> Result<&V, MapError>;
+    fn get_mut(&mut self, key: &K) -> Result<&mut V, MapError>;
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError>;
+    fn remove(&mut self, key: &K) -> Result<(), MapError>;
+    fn size(&self) -> Result<usize, MapError>;
+    fn contains_key(&self, key: &K) -> Result<bool, MapError>;
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq;
     fn clear(&mut self) -> Result<(), MapError>;
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_>;
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_>;
 }
 
+impl<K, V, M> DynMap<K, V> for M where K: Eq,
+    M: Map<K, V> + MapLookup<K, V, K> {
+    fn get(&self, key: &K) -> Result<&V, MapError> {
+        Map::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Result<&mut V, MapError> {
+        Map::get_mut(self, key)
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        Map::set(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Result<(), MapError> {
+        Map::remove(self, key)
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Map::size(self)
+    }
+
+    fn contains_key(&self, key: &K) -> Result<bool, MapError> {
+        Map::contains_key(self, key)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Map::contains_value(self, value)
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        Map::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&K, &V)> + '_> {
+        Box::new(Map::iter(self))
+    }
+
+    fn iter_mut(&mut self) -> Box<dyn Iterator<Item = (&K, &mut V)> + '_> {
+        Box::new(Map::iter_mut(self))
+    }
+}
@@ -0,0 +1,768 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::fmt;
+use crate::bst::{self, Counts, above, below, entry, find, find_mut};
+use crate::map::{Map, MapError, MapLookup, OrderedMap};
+
+type Node<K, V> = bst::Node<K, V, Counts>;
+type Link<K, V> = bst::Link<K, V, Counts>;
+
+pub type Iter<'a, K, V> = bst::Iter<'a, K, V, Counts>;
+pub type IterMut<'a, K, V> = bst::IterMut<'a, K, V, Counts>;
+pub type IntoIter<K, V> = bst::IntoIter<K, V, Counts>;
+
+/* an AVL tree whose nodes also count the entries beneath them, so on top
+ * of the ordered map operations it answers "how many keys are below this
+ * one" (`rank`), "which key is k-th" (`select`) and "how many keys lie in
+ * this range" (`count_range`) in O(log n) */
+#[derive(Clone, Debug)]
+pub struct OrderStatMap<K, V> {
+    root: Link<K, V>
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.meta.height)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.meta.size)
+}
+
+fn update<K, V>(node: &mut Node<K, V>) {
+    node.meta = Counts {
+        height: 1 + height(&node.left).max(height(&node.right)),
+        size: 1 + size(&node.left) + size(&node.right)
+    };
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut right: Box<Node<K, V>> = match node.right.take() {
+        Some(right) => right,
+        None => return node
+    };
+
+    node.right = right.left.take();
+    update(&mut node);
+    right.left = Some(node);
+    update(&mut right);
+
+    right
+}
+
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut left: Box<Node<K, V>> = match node.left.take() {
+        Some(left) => left,
+        None => return node
+    };
+
+    node.left = left.right.take();
+    update(&mut node);
+    left.right = Some(node);
+    update(&mut left);
+
+    left
+}
+
+/* restores the AVL condition at `node` once its subtrees are balanced and
+ * differ in height by at most two */
+fn balance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    update(&mut node);
+
+    let left: usize = height(&node.left);
+    let right: usize = height(&node.right);
+
+    if left > right + 1 {
+        if node.left.as_ref()
+            .is_some_and(|l| height(&l.left) < height(&l.right)) {
+            node.left = node.left.take().map(rotate_left);
+        }
+
+        rotate_right(node)
+    } else if right > left + 1 {
+        if node.right.as_ref()
+            .is_some_and(|r| height(&r.right) < height(&r.left)) {
+            node.right = node.right.take().map(rotate_right);
+        }
+
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+/* the flag is false when `key` was already present and only its value
+ * was replaced, so no height on the path changed */
+fn insert<K: Ord, V>(link: Link<K, V>, key: K, value: V)
+    -> (Box<Node<K, V>>, bool) {
+    let mut node: Box<Node<K, V>> = match link {
+        Some(node) => node,
+        None => return (Box::new(Node {
+            key,
+            value,
+            meta: Counts {
+                height: 1,
+                size: 1
+            },
+            left: None,
+            right: None
+        }), true)
+    };
+
+    match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, new) = insert(node.left.take(), key, value);
+
+            node.left = Some(left);
+            (balance(node), new)
+        },
+        Ordering::Greater => {
+            let (right, new) = insert(node.right.take(), key, value);
+
+            node.right = Some(right);
+            (balance(node), new)
+        },
+        Ordering::Equal => {
+            node.value = value;
+            (node, false)
+        }
+    }
+}
+
+/* joins `left`, `mid` and `right`, whose keys are in that order, by
+ * walking down the inner edge of the taller tree to a subtree that `mid`
+ * can take alongside the shorter one; the cost is the difference in their
+ * heights */
+fn join<K, V>(left: Link<K, V>, mut mid: Box<Node<K, V>>, right: Link<K, V>)
+    -> Box<Node<K, V>> {
+    let (lh, rh): (usize, usize) = (height(&left), height(&right));
+
+    match (left, right) {
+        (Some(mut node), right) if lh > rh + 1 => {
+            node.right = Some(join(node.right.take(), mid, right));
+            balance(node)
+        },
+        (left, Some(mut node)) if rh > lh + 1 => {
+            node.left = Some(join(left, mid, node.left.take()));
+            balance(node)
+        },
+        (left, right) => {
+            mid.left = left;
+            mid.right = right;
+            update(&mut mid);
+            mid
+        }
+    }
+}
+
+/* splits `link` into the keys below `key` and the rest; the pieces cut off
+ * on the way down are joined back up level by level, and as each join
+ * costs the height difference of its inputs the total stays O(log n) */
+fn split<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Link<K, V>)
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+    let mut node: Box<Node<K, V>> = match link {
+        Some(node) => node,
+        None => return (None, None)
+    };
+    let left: Link<K, V> = node.left.take();
+    let right: Link<K, V> = node.right.take();
+
+    if node.key.borrow() < key {
+        let (below, rest) = split(right, key);
+
+        (Some(join(left, node, below)), rest)
+    } else {
+        let (below, rest) = split(left, key);
+
+        (below, Some(join(rest, node, right)))
+    }
+}
+
+/* returns the rest of the subtree and its least node */
+fn remove_min<K, V>(mut node: Box<Node<K, V>>)
+    -> (Link<K, V>, Box<Node<K, V>>) {
+    match node.left.take() {
+        Some(left) => {
+            let (rest, min) = remove_min(left);
+
+            node.left = rest;
+            (Some(balance(node)), min)
+        },
+        None => (node.right.take(), node)
+    }
+}
+
+/* returns the rest of the subtree and its greatest node */
+fn remove_max<K, V>(mut node: Box<Node<K, V>>)
+    -> (Link<K, V>, Box<Node<K, V>>) {
+    match node.right.take() {
+        Some(right) => {
+            let (rest, max) = remove_max(right);
+
+            node.right = rest;
+            (Some(balance(node)), max)
+        },
+        None => (node.left.take(), node)
+    }
+}
+
+fn remove<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Option<(K, V)>)
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+    let mut node: Box<Node<K, V>> = match link {
+        Some(node) => node,
+        None => return (None, None)
+    };
+
+    match key.cmp(node.key.borrow()) {
+        Ordering::Less => {
+            let (left, res) = remove(node.left.take(), key);
+
+            node.left = left;
+            (Some(balance(node)), res)
+        },
+        Ordering::Greater => {
+            let (right, res) = remove(node.right.take(), key);
+
+            node.right = right;
+            (Some(balance(node)), res)
+        },
+        Ordering::Equal => {
+            let left: Link<K, V> = node.left.take();
+
+            let rest: Link<K, V> = match node.right.take() {
+                Some(right) => {
+                    let (right, mut min) = remove_min(right);
+
+                    min.left = left;
+                    min.right = right;
+                    Some(balance(min))
+                },
+                None => left
+            };
+
+            (rest, Some((node.key, node.value)))
+        }
+    }
+}
+
+/* the number of keys below `key` (or, if `inclusive`, at most `key`) */
+fn count_below<K, V, Q>(mut link: &Link<K, V>, key: &Q, inclusive: bool)
+    -> usize where K: Borrow<Q>, Q: Ord + ?Sized {
+    let mut res: usize = 0;
+
+    while let Some(node) = link {
+        let k: &Q = node.key.borrow();
+
+        if k < key || (inclusive && k == key) {
+            res += size(&node.left) + 1;
+            link = &node.right;
+        } else {
+            link = &node.left;
+        }
+    }
+
+    res
+}
+
+impl<K, V> OrderStatMap<K, V> {
+    pub fn new() -> Self {
+        OrderStatMap {
+            root: None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.root, self.len())
+    }
+
+    /* the entry with exactly `k` keys below it */
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut link: &Link<K, V> = &self.root;
+
+        while let Some(node) = link {
+            let left: usize = size(&node.left);
+
+            match k.cmp(&left) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    link = &node.right;
+                },
+                Ordering::Equal => return Some((&node.key, &node.value))
+            }
+        }
+
+        None
+    }
+}
+
+impl<K: Ord, V> OrderStatMap<K, V> {
+    /* the number of keys below `key`, which must be present */
+    pub fn rank<Q>(&self, key: &Q) -> Option<usize> where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let mut res: usize = 0;
+        let mut link: &Link<K, V> = &self.root;
+
+        while let Some(node) = link {
+            match key.cmp(node.key.borrow()) {
+                Ordering::Less => link = &node.left,
+                Ordering::Greater => {
+                    res += size(&node.left) + 1;
+                    link = &node.right;
+                },
+                Ordering::Equal => return Some(res + size(&node.left))
+            }
+        }
+
+        None
+    }
+
+    /* the number of keys in `range`, whether or not its ends are keys */
+    pub fn count_range<Q, R>(&self, range: R) -> usize where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        let start: usize = match range.start_bound() {
+            Bound::Included(start) => count_below(&self.root, start, false),
+            Bound::Excluded(start) => count_below(&self.root, start, true),
+            Bound::Unbounded => 0
+        };
+        let end: usize = match range.end_bound() {
+            Bound::Included(end) => count_below(&self.root, end, true),
+            Bound::Excluded(end) => count_below(&self.root, end, false),
+            Bound::Unbounded => self.len()
+        };
+
+        end.saturating_sub(start)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> bool {
+        let (root, new) = insert(self.root.take(), key, value);
+
+        self.root = Some(root);
+        new
+    }
+}
+
+impl<K, V> Default for OrderStatMap<K, V> {
+    fn default() -> Self {
+        OrderStatMap::new()
+    }
+}
+
+impl<K, V> PartialEq for OrderStatMap<K, V> where K: Eq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for OrderStatMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for OrderStatMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let len: usize = self.len();
+
+        IntoIter::new(self.root.take(), len)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a OrderStatMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for OrderStatMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: OrderStatMap<K, V> = OrderStatMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for OrderStatMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Map<K, V> for OrderStatMap<K, V> where K: Ord {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        OrderStatMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len())
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        self.root = None;
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        OrderStatMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len: usize = self.len();
+
+        IterMut::new(&mut self.root, len)
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for OrderStatMap<K, V> where
+    K: Ord + Borrow<Q>, Q: Ord + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        find(&self.root, key).map(|node| &node.value)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        find_mut(&mut self.root, key).map(|node| &mut node.value)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        let (root, res) = remove(self.root.take(), key);
+
+        self.root = root;
+        res.map(|(_, value)| value)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> for OrderStatMap<K, V> where K: Ord {
+    type Range<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+
+    fn first(&self) -> Result<(&K, &V), MapError> {
+        self.select(0).ok_or(MapError::KeyNotFound)
+    }
+
+    fn last(&self) -> Result<(&K, &V), MapError> {
+        match self.len() {
+            0 => Err(MapError::KeyNotFound),
+            len => self.select(len - 1).ok_or(MapError::KeyNotFound)
+        }
+    }
+
+    fn floor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(below(&self.root, key, true))
+    }
+
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(above(&self.root, key, true))
+    }
+
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(below(&self.root, key, false))
+    }
+
+    fn successor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(above(&self.root, key, false))
+    }
+
+    /* the counts give the length up front, so the iterator just stops
+     * after that many entries */
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        let mut res: Iter<'_, K, V> = Iter {
+            stack: Vec::new(),
+            remaining: self.count_range((range.start_bound(),
+                                          range.end_bound()))
+        };
+        let mut link: &Link<K, V> = &self.root;
+
+        while let Some(node) = link {
+            let after_start: bool = match range.start_bound() {
+                Bound::Included(start) => node.key.borrow() >= start,
+                Bound::Excluded(start) => node.key.borrow() > start,
+                Bound::Unbounded => true
+            };
+
+            if after_start {
+                res.stack.push(node);
+                link = &node.left;
+            } else {
+                link = &node.right;
+            }
+        }
+
+        res
+    }
+
+    fn pop_first(&mut self) -> Result<(K, V), MapError> {
+        let (root, min) = match self.root.take() {
+            Some(root) => remove_min(root),
+            None => return Err(MapError::KeyNotFound)
+        };
+
+        self.root = root;
+        Ok((min.key, min.value))
+    }
+
+    fn pop_last(&mut self) -> Result<(K, V), MapError> {
+        let (root, max) = match self.root.take() {
+            Some(root) => remove_max(root),
+            None => return Err(MapError::KeyNotFound)
+        };
+
+        self.root = root;
+        Ok((max.key, max.value))
+    }
+
+    fn split_off<Q>(&mut self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let (below, rest) = split(self.root.take(), key);
+
+        self.root = below;
+
+        OrderStatMap {
+            root: rest
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShiftRng;
+
+    /* the AVL condition and the cached heights and sizes; returns the
+     * height */
+    fn check(link: &Link<u64, u64>) -> usize {
+        match link {
+            Some(node) => {
+                let left: usize = check(&node.left);
+                let right: usize = check(&node.right);
+
+                assert!(left.abs_diff(right) <= 1);
+                assert_eq!(node.meta.height, 1 + left.max(right));
+                assert_eq!(node.meta.size, 1 + size(&node.left) +
+                           size(&node.right));
+
+                node.meta.height
+            },
+            None => 0
+        }
+    }
+
+    #[test]
+    fn test_rank_normal_against_sorted() -> Result<(), MapError> {
+        let mut actual_map: OrderStatMap<u64, u64> = OrderStatMap::new();
+        let mut expected_keys: Vec<u64> = Vec::new();
+        let mut rng: XorShiftRng = XorShiftRng::new(4);
+
+        for _ in 0..2000 {
+            let key: u64 = rng.next_u64() % 1000;
+
+            if rng.next_u64() % 3 == 0 {
+                assert_eq!(actual_map.remove(&key).is_ok(),
+                           expected_keys.contains(&key));
+                expected_keys.retain(|k| *k != key);
+            } else {
+                actual_map.set(key, key * 2)?;
+
+                if !expected_keys.contains(&key) {
+                    expected_keys.push(key);
+                }
+            }
+        }
+
+        expected_keys.sort();
+        check(&actual_map.root);
+
+        assert_eq!(actual_map.size()?, expected_keys.len());
+
+        for (i, key) in expected_keys.iter().enumerate() {
+            assert_eq!(actual_map.rank(key), Some(i));
+            assert_eq!(actual_map.select(i), Some((key, &(key * 2))));
+        }
+
+        assert_eq!(actual_map.select(expected_keys.len()), None);
+        assert_eq!(actual_map.rank(&1000), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal_sorted_input() -> Result<(), MapError> {
+        let actual_map: OrderStatMap<u64, u64> =
+            (0..4096).map(|i| (i, i)).collect();
+
+        assert_eq!(check(&actual_map.root), 13);
+        assert_eq!(actual_map.iter().len(), 4096);
+        assert_eq!(actual_map.get(&100)?, &100);
+        assert_eq!(actual_map.get(&4096), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_range_normal() -> Result<(), MapError> {
+        let actual_map: OrderStatMap<u64, u64> =
+            (0..100).step_by(10).map(|i| (i, i)).collect();
+
+        assert_eq!(actual_map.count_range(15..45), 3);
+        assert_eq!(actual_map.count_range(20..=40), 3);
+        assert_eq!(actual_map.count_range(20..40), 2);
+        assert_eq!(actual_map.count_range(..), 10);
+        assert_eq!(actual_map.count_range(91..), 0);
+        assert_eq!(actual_map.count_range((Bound::Excluded(50),
+                                           Bound::Excluded(20))), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_normal() -> Result<(), MapError> {
+        let actual_map: OrderStatMap<u64, u64> =
+            (0..100).step_by(10).map(|i| (i, i)).collect();
+        let collect = |iter: Iter<u64, u64>| -> Vec<u64> {
+            iter.map(|(k, _)| *k).collect()
+        };
+
+        assert_eq!(collect(actual_map.range(15..45)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(..15)), vec![0, 10]);
+        assert_eq!(collect(actual_map.range(85..)), vec![90]);
+        assert_eq!(actual_map.range(20..=40).len(), 3);
+        assert_eq!(collect(actual_map.range(41..49)), Vec::<u64>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_floor_normal_neighbours() -> Result<(), MapError> {
+        let actual_map: OrderStatMap<u64, u64> =
+            (10..100).step_by(10).map(|i| (i, i)).collect();
+
+        assert_eq!(actual_map.first()?, (&10, &10));
+        assert_eq!(actual_map.last()?, (&90, &90));
+        assert_eq!(actual_map.floor(&35)?, (&30, &30));
+        assert_eq!(actual_map.ceiling(&35)?, (&40, &40));
+        assert_eq!(actual_map.predecessor(&30)?, (&20, &20));
+        assert_eq!(actual_map.successor(&30)?, (&40, &40));
+        assert_eq!(actual_map.floor(&5), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.successor(&90), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_first_normal() -> Result<(), MapError> {
+        let mut actual_map: OrderStatMap<u64, u64> =
+            (0..100).map(|i| (i * 37 % 100, i)).collect();
+
+        for i in 0..50 {
+            assert_eq!(actual_map.pop_first()?.0, i);
+            assert_eq!(actual_map.pop_last()?.0, 99 - i);
+            check(&actual_map.root);
+        }
+
+        assert_eq!(actual_map.pop_first(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.last(), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), MapError> {
+        let mut actual_map: OrderStatMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+
+        let actual_rest: OrderStatMap<u64, u64> = actual_map.split_off(&60);
+
+        check(&actual_map.root);
+        check(&actual_rest.root);
+
+        assert_eq!(actual_map.size()?, 60);
+        assert_eq!(actual_rest.rank(&60), Some(0));
+        assert_eq!(actual_rest.select(39), Some((&99, &99)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal_every_key() -> Result<(), MapError> {
+        let mut rng: XorShiftRng = XorShiftRng::new(8);
+        let expected_map: OrderStatMap<u64, u64> =
+            (0..300).map(|_| (rng.next_u64() % 500, 0)).collect();
+
+        for key in (0..510).step_by(7) {
+            let mut actual_map: OrderStatMap<u64, u64> = expected_map.clone();
+            let actual_rest: OrderStatMap<u64, u64> =
+                actual_map.split_off(&key);
+
+            check(&actual_map.root);
+            check(&actual_rest.root);
+
+            assert_eq!(actual_map.len(), expected_map.count_range(..key));
+            assert!(actual_map.last().is_ok_and(|(k, _)| *k < key) ||
+                    actual_map.is_empty());
+            assert!(actual_rest.first().is_ok_and(|(k, _)| *k >= key) ||
+                    actual_rest.is_empty());
+            assert_eq!(actual_map.iter().chain(actual_rest.iter())
+                           .collect::<Vec<_>>(),
+                       expected_map.iter().collect::<Vec<_>>());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), MapError> {
+        let mut actual_map: OrderStatMap<u64, u64> =
+            (0..5).rev().map(|i| (i, i)).collect();
+
+        for (key, value) in actual_map.iter_mut() {
+            *value = key * 10;
+        }
+
+        assert_eq!(actual_map.to_string(),
+                   "{0: 0, 1: 10, 2: 20, 3: 30, 4: 40, }");
+        assert_eq!(actual_map.into_iter().len(), 5);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use std::borrow::Borrow;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+use std::fmt;
+use crate::map::{Map, MapLookup, OrderedMap};
+use crate::orderstatmap::{self, OrderStatMap};
+use crate::set::{Set, SetError, SetLookup};
+
+/* an ordered set over an `OrderStatMap` with unit values, with the same
+ * O(log n) `rank`, `select` and `count_range` */
+#[derive(Clone, Debug, Default)]
+pub struct OrderStatSet<T> {
+    map: OrderStatMap<T, ()>
+}
+
+pub struct Iter<'a, T> {
+    iter: orderstatmap::Iter<'a, T, ()>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(elem, _)| elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IntoIter<T> {
+    iter: orderstatmap::IntoIter<T, ()>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(elem, _)| elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> OrderStatSet<T> {
+    pub fn new() -> Self {
+        OrderStatSet {
+            map: OrderStatMap::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.map.iter()
+        }
+    }
+
+    /* the element with exactly `k` elements below it */
+    pub fn select(&self, k: usize) -> Option<&T> {
+        self.map.select(k).map(|(elem, _)| elem)
+    }
+}
+
+impl<T: Ord> OrderStatSet<T> {
+    /* the number of elements below `elem`, which must be present */
+    pub fn rank<Q>(&self, elem: &Q) -> Option<usize> where T: Borrow<Q>,
+        Q: Ord + ?Sized {
+        self.map.rank(elem)
+    }
+
+    pub fn count_range<Q, R>(&self, range: R) -> usize where T: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        self.map.count_range(range)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().ok().map(|(elem, _)| elem)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().ok().map(|(elem, _)| elem)
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, T> where T: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        Iter {
+            iter: self.map.range(range)
+        }
+    }
+}
+
+impl<T> PartialEq for OrderStatSet<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T> Eq for OrderStatSet<T> where T: Eq {}
+
+impl<T> IntoIterator for OrderStatSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.map.into_iter()
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OrderStatSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OrderStatSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        OrderStatSet {
+            map: iter.into_iter().map(|elem| (elem, ())).collect()
+        }
+    }
+}
+
+impl<T> fmt::Display for OrderStatSet<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for elem in self.iter() {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<T> Set<T> for OrderStatSet<T> where T: Ord {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        OrderStatSet::new()
+    }
+
+    fn add(&mut self, elem: T) -> Result<(), SetError> {
+        self.map.set(elem, ()).map_err(|_| SetError::Impossible)
+    }
+
+    fn size(&self) -> Result<usize, SetError> {
+        Ok(self.map.len())
+    }
+
+    fn clear(&mut self) -> Result<(), SetError> {
+        self.map = OrderStatMap::new();
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        OrderStatSet::iter(self)
+    }
+}
+
+impl<T, Q> SetLookup<T, Q> for OrderStatSet<T> where T: Ord + Borrow<Q>,
+    Q: Ord + ?Sized {
+    fn lookup(&self, elem: &Q) -> bool {
+        self.map.lookup(elem).is_some()
+    }
+
+    fn lookup_remove(&mut self, elem: &Q) -> bool {
+        self.map.lookup_remove(elem).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_normal_leaderboard() -> Result<(), SetError> {
+        /* (negated score, player), so the best score ranks first */
+        let mut actual_board: OrderStatSet<(i64, &str)> = OrderStatSet::new();
+
+        actual_board.add((-30, "carol"))?;
+        actual_board.add((-50, "alice"))?;
+        actual_board.add((-40, "bob"))?;
+        actual_board.add((-10, "dave"))?;
+
+        assert_eq!(actual_board.rank(&(-40, "bob")), Some(1));
+        assert_eq!(actual_board.select(0), Some(&(-50, "alice")));
+        assert_eq!(actual_board.count_range((-45, "")..(-20, "")), 2);
+
+        /* bob improves */
+        actual_board.remove(&(-40, "bob"))?;
+        actual_board.add((-60, "bob"))?;
+
+        assert_eq!(actual_board.rank(&(-60, "bob")), Some(0));
+        assert_eq!(actual_board.rank(&(-50, "alice")), Some(1));
+        assert_eq!(actual_board.rank(&(-40, "bob")), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_normal_duplicates() -> Result<(), SetError> {
+        let mut actual_set: OrderStatSet<u64> =
+            vec![5, 3, 5, 1, 3].into_iter().collect();
+
+        actual_set.add(1)?;
+        actual_set.remove(&7)?;
+
+        assert_eq!(actual_set.size()?, 3);
+        assert_eq!(actual_set.to_string(), "{1, 3, 5, }");
+        assert_eq!(actual_set.first(), Some(&1));
+        assert_eq!(actual_set.last(), Some(&5));
+        assert_eq!(actual_set.range(2..).copied().collect::<Vec<u64>>(),
+                   vec![3, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), SetError> {
+        let mut actual_set: Box<dyn crate::set::DynSet<u64>> =
+            Box::new(OrderStatSet::new());
+
+        actual_set.add(2)?;
+        actual_set.add(1)?;
+
+        assert!(actual_set.contains(&1)?);
+        assert_eq!(actual_set.iter().copied().collect::<Vec<u64>>(),
+                   vec![1, 2]);
+
+        actual_set.clear()?;
+
+        assert_eq!(actual_set.size()?, 0);
+        Ok(())
+    }
+}
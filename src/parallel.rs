@@ -0,0 +1,504 @@
+use std::thread;
+
+use crate::arraylist::ArrayList;
+use crate::list::{List, ListError};
+
+/* below this many elements the parallel routines fall back to sequential
+ * code, as spawning threads would cost more than it saves */
+pub const PARALLEL_THRESHOLD: usize = 4096;
+
+fn num_threads() -> usize {
+    match thread::available_parallelism() {
+        Ok(n) => n.get(),
+        Err(_) => 1
+    }
+}
+
+/* how many times we may split the work in two before we stop spawning */
+fn max_depth() -> usize {
+    let mut depth: usize = 0;
+
+    while (1 << depth) < num_threads() {
+        depth += 1;
+    }
+
+    depth
+}
+
+/* splits `len` elements into chunks such that every thread gets at least
+ * `PARALLEL_THRESHOLD` elements */
+fn chunk_size(len: usize) -> usize {
+    let per_thread: usize = len.div_ceil(num_threads());
+
+    per_thread.max(PARALLEL_THRESHOLD)
+}
+
+fn merge<T: Clone>(elems: &mut [T], mid: usize,
+    cmp: fn(a: &T, b: &T) -> bool) {
+    let left: Vec<T> = elems[..mid].to_vec();
+    let mut i: usize = 0;
+    let mut j: usize = mid;
+    let mut k: usize = 0;
+
+    while i < left.len() && j < elems.len() {
+        if cmp(&left[i], &elems[j]) {
+            elems[k] = left[i].clone();
+            i += 1;
+        } else {
+            elems.swap(k, j);
+            j += 1;
+        }
+
+        k += 1;
+    }
+
+    while i < left.len() {
+        elems[k] = left[i].clone();
+        i += 1;
+        k += 1;
+    }
+}
+
+fn mergesort_slice<T>(elems: &mut [T], cmp: fn(a: &T, b: &T) -> bool,
+    depth: usize) where T: Clone + Send {
+    if elems.len() <= 1 {
+        return;
+    }
+
+    let mid: usize = elems.len() / 2;
+
+    {
+        let (left, right) = elems.split_at_mut(mid);
+
+        if depth > 0 && left.len() + right.len() > PARALLEL_THRESHOLD {
+            thread::scope(|s| {
+                s.spawn(|| mergesort_slice(left, cmp, depth - 1));
+                mergesort_slice(right, cmp, depth - 1);
+            });
+        } else {
+            mergesort_slice(left, cmp, 0);
+            mergesort_slice(right, cmp, 0);
+        }
+    }
+
+    merge(elems, mid, cmp);
+}
+
+/* picks the median of the first, middle and last elements as the pivot so
+ * that already-sorted input doesn't degrade to quadratic time */
+fn median_of_three<T>(elems: &[T], cmp: fn(a: &T, b: &T) -> bool) -> usize {
+    let a: usize = 0;
+    let b: usize = elems.len() / 2;
+    let c: usize = elems.len() - 1;
+
+    if cmp(&elems[a], &elems[b]) {
+        if cmp(&elems[b], &elems[c]) {
+            b
+        } else if cmp(&elems[a], &elems[c]) {
+            c
+        } else {
+            a
+        }
+    } else if cmp(&elems[a], &elems[c]) {
+        a
+    } else if cmp(&elems[b], &elems[c]) {
+        c
+    } else {
+        b
+    }
+}
+
+/* three-way partition so that runs of equal elements are never recursed
+ * into; returns the bounds of the run equal to the pivot */
+fn partition<T>(elems: &mut [T], cmp: fn(a: &T, b: &T) -> bool) ->
+    (usize, usize) {
+    let pivot_pos: usize = median_of_three(elems, cmp);
+    elems.swap(0, pivot_pos);
+
+    /* the pivot stays parked at the front while the rest is partitioned */
+    let (head, rest) = elems.split_at_mut(1);
+    let pivot: &T = &head[0];
+    let mut lt: usize = 0;
+    let mut i: usize = 0;
+    let mut gt: usize = rest.len();
+
+    while i < gt {
+        if !cmp(pivot, &rest[i]) { /* strictly less than pivot */
+            rest.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if !cmp(&rest[i], pivot) { /* strictly greater than pivot */
+            gt -= 1;
+            rest.swap(i, gt);
+        } else {
+            i += 1;
+        }
+    }
+
+    /* move the pivot to the start of its run of equal elements */
+    elems.swap(0, lt);
+
+    (lt, gt + 1)
+}
+
+/* recurses only into the smaller side and loops on the larger one, so the
+ * stack stays O(log n) deep however badly the pivots fall */
+fn quicksort_slice<T>(mut elems: &mut [T], cmp: fn(a: &T, b: &T) -> bool,
+    depth: usize) where T: Send {
+    while elems.len() > 1 {
+        let (lt, gt) = partition(elems, cmp);
+        let (left, rest) = std::mem::take(&mut elems).split_at_mut(lt);
+        let right: &mut [T] = &mut rest[gt - lt..];
+
+        if depth > 0 && left.len() + right.len() > PARALLEL_THRESHOLD {
+            thread::scope(|s| {
+                s.spawn(|| quicksort_slice(left, cmp, depth - 1));
+                quicksort_slice(right, cmp, depth - 1);
+            });
+
+            return;
+        }
+
+        if left.len() < right.len() {
+            quicksort_slice(left, cmp, 0);
+            elems = right;
+        } else {
+            quicksort_slice(right, cmp, 0);
+            elems = left;
+        }
+    }
+}
+
+pub fn par_mergesort<T>(list: &mut ArrayList<T>,
+    cmp: fn(a: &T, b: &T) -> bool) -> Result<(), ListError> where
+    T: Clone + Send {
+    mergesort_slice(list.as_mut_slice(), cmp, max_depth());
+    Ok(())
+}
+
+pub fn par_quicksort<T>(list: &mut ArrayList<T>,
+    cmp: fn(a: &T, b: &T) -> bool) -> Result<(), ListError> where
+    T: Send {
+    quicksort_slice(list.as_mut_slice(), cmp, max_depth());
+    Ok(())
+}
+
+pub fn par_find_all<T>(list: &ArrayList<T>, elem: &T) ->
+    Result<Option<Vec<usize>>, ListError> where
+    T: Eq + Sync {
+    if list.length()? <= PARALLEL_THRESHOLD {
+        return list.find_all(elem);
+    }
+
+    let size: usize = chunk_size(list.length()?);
+    let mut res: Vec<usize> = Vec::new();
+
+    thread::scope(|s| {
+        let handles: Vec<_> = list.as_slice()
+            .chunks(size)
+            .enumerate()
+            .map(|(n, chunk)| s.spawn(move || {
+                let mut found: Vec<usize> = Vec::new();
+
+                for (i, curr) in chunk.iter().enumerate() {
+                    if curr == elem {
+                        found.push(n * size + i);
+                    }
+                }
+
+                found
+            }))
+            .collect();
+
+        for handle in handles {
+            res.extend(handle.join().unwrap());
+        }
+    });
+
+    Ok(Some(res))
+}
+
+pub fn par_count<T>(list: &ArrayList<T>, elem: &T) ->
+    Result<usize, ListError> where T: Eq + Sync {
+    if list.length()? <= PARALLEL_THRESHOLD {
+        return list.count(elem);
+    }
+
+    let size: usize = chunk_size(list.length()?);
+
+    let count: usize = thread::scope(|s| {
+        let handles: Vec<_> = list.as_slice()
+            .chunks(size)
+            .map(|chunk| s.spawn(move || {
+                chunk.iter().filter(|curr| *curr == elem).count()
+            }))
+            .collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+    });
+
+    Ok(count)
+}
+
+pub fn par_map<T, U, F>(list: &ArrayList<T>, f: F) ->
+    Result<ArrayList<U>, ListError> where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync {
+    let mut res: ArrayList<U> = ArrayList::new();
+
+    if list.length()? <= PARALLEL_THRESHOLD {
+        for elem in list.as_slice().iter() {
+            res.append(f(elem))?;
+        }
+
+        return Ok(res);
+    }
+
+    let size: usize = chunk_size(list.length()?);
+    let f: &F = &f;
+
+    let chunks: Vec<Vec<U>> = thread::scope(|s| {
+        let handles: Vec<_> = list.as_slice()
+            .chunks(size)
+            .map(|chunk| s.spawn(move || {
+                chunk.iter().map(f).collect::<Vec<U>>()
+            }))
+            .collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+
+    res.extend(chunks.into_iter().flatten());
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cmp_leq<T: Eq + Ord>(a: &T, b: &T) -> bool {
+        a <= b
+    }
+
+    fn cmp_geq<T: Eq + Ord>(a: &T, b: &T) -> bool {
+        a >= b
+    }
+
+    /* deterministic pseudo-random input with plenty of duplicates */
+    fn scrambled(n: u64) -> Result<ArrayList<u64>, ListError> {
+        let mut list: ArrayList<u64> = ArrayList::new();
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+        for _i in 0..n {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            list.append(state % 1000)?;
+        }
+
+        Ok(list)
+    }
+
+    fn sorted(list: &ArrayList<u64>) -> Result<ArrayList<u64>, ListError> {
+        let mut elems: Vec<u64> = list.as_slice().to_vec();
+
+        elems.sort();
+
+        Ok(ArrayList::from(elems))
+    }
+
+    #[test]
+    fn test_par_mergesort_normal_small() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        actual_list.append(33)?;
+        actual_list.append(12)?;
+        actual_list.append(0)?;
+        actual_list.append(1)?;
+        actual_list.append(4)?;
+
+        let mut expected_list: ArrayList<u64> = ArrayList::new();
+
+        expected_list.append(0)?;
+        expected_list.append(1)?;
+        expected_list.append(4)?;
+        expected_list.append(12)?;
+        expected_list.append(33)?;
+
+        let actual_res: Result<(), ListError> =
+            par_mergesort(&mut actual_list, cmp_leq);
+        let expected_res: Result<(), ListError> = Ok(());
+
+        assert_eq!(actual_list, expected_list);
+        assert_eq!(actual_res, expected_res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_mergesort_normal_large() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = scrambled(50000)?;
+        let expected_list: ArrayList<u64> = sorted(&actual_list)?;
+
+        par_mergesort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_mergesort_normal_descending() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = scrambled(20000)?;
+
+        par_mergesort(&mut actual_list, cmp_geq)?;
+
+        let elems: &[u64] = actual_list.as_slice();
+
+        for i in 1..elems.len() {
+            assert!(elems[i - 1] >= elems[i]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_mergesort_normal_empty() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+        let expected_list: ArrayList<u64> = ArrayList::new();
+
+        par_mergesort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_quicksort_normal_small() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        actual_list.append(33)?;
+        actual_list.append(12)?;
+        actual_list.append(0)?;
+        actual_list.append(1)?;
+        actual_list.append(4)?;
+
+        let mut expected_list: ArrayList<u64> = ArrayList::new();
+
+        expected_list.append(0)?;
+        expected_list.append(1)?;
+        expected_list.append(4)?;
+        expected_list.append(12)?;
+        expected_list.append(33)?;
+
+        let actual_res: Result<(), ListError> =
+            par_quicksort(&mut actual_list, cmp_leq);
+        let expected_res: Result<(), ListError> = Ok(());
+
+        assert_eq!(actual_list, expected_list);
+        assert_eq!(actual_res, expected_res);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_quicksort_normal_large() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = scrambled(50000)?;
+        let expected_list: ArrayList<u64> = sorted(&actual_list)?;
+
+        par_quicksort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_quicksort_normal_all_equal() -> Result<(), ListError> {
+        let mut actual_list: ArrayList<u64> = ArrayList::new();
+
+        for _i in 0..20000 {
+            actual_list.append(7)?;
+        }
+
+        let expected_list: ArrayList<u64> = actual_list.clone();
+
+        par_quicksort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_find_all_normal() -> Result<(), ListError> {
+        let list: ArrayList<u64> = scrambled(50000)?;
+
+        let actual_res: Result<Option<Vec<usize>>, ListError> =
+            par_find_all(&list, &42);
+        let expected_res: Result<Option<Vec<usize>>, ListError> =
+            list.find_all(&42);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_count_normal() -> Result<(), ListError> {
+        let list: ArrayList<u64> = scrambled(50000)?;
+
+        let actual_res: Result<usize, ListError> = par_count(&list, &42);
+        let expected_res: Result<usize, ListError> = list.count(&42);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_count_normal_small() -> Result<(), ListError> {
+        let mut list: ArrayList<u64> = ArrayList::new();
+
+        list.append(1)?;
+        list.append(2)?;
+        list.append(1)?;
+
+        let actual_res: Result<usize, ListError> = par_count(&list, &1);
+        let expected_res: Result<usize, ListError> = Ok(2);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_map_normal() -> Result<(), ListError> {
+        let list: ArrayList<u64> = scrambled(50000)?;
+        let mut expected_list: ArrayList<u64> = ArrayList::new();
+
+        for elem in list.as_slice().iter() {
+            expected_list.append(elem * 2)?;
+        }
+
+        let actual_list: ArrayList<u64> = par_map(&list, |x| x * 2)?;
+
+        assert_eq!(actual_list, expected_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_quicksort_normal_not_clone() -> Result<(), ListError> {
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+        struct Opaque(u64);
+
+        let mut actual_list: ArrayList<Opaque> =
+            scrambled(5000)?.into_iter().map(Opaque).collect();
+
+        par_quicksort(&mut actual_list, cmp_leq)?;
+
+        let elems: &[Opaque] = actual_list.as_slice();
+
+        for i in 1..elems.len() {
+            assert!(elems[i - 1] <= elems[i]);
+        }
+
+        Ok(())
+    }
+}
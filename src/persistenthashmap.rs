@@ -0,0 +1,643 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::fmt;
+use std::slice;
+use std::sync::Arc;
+use crate::map::{Map, MapError, MapLookup};
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+#[derive(Clone)]
+enum Node<K, V> {
+    /* `children` holds one entry per set bit of `bitmap`, in bit order */
+    Branch {
+        bitmap: u32,
+        children: Vec<Arc<Node<K, V>>>
+    },
+    /* all entries share `hash`; more than one only on a full collision */
+    Leaf {
+        hash: u64,
+        entries: Vec<(K, V)>
+    }
+}
+
+fn hash_of<Q: Hash + ?Sized>(key: &Q) -> u64 {
+    let mut hasher: DefaultHasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/* the bit for `hash` at depth `shift`, and its slot among `bitmap`'s set
+ * bits */
+fn slot(bitmap: u32, hash: u64, shift: u32) -> (u32, usize) {
+    let frag: u64 = hash.checked_shr(shift).unwrap_or(0) & MASK;
+    let bit: u32 = 1 << frag;
+
+    (bit, (bitmap & (bit - 1)).count_ones() as usize)
+}
+
+impl<K, V> Node<K, V> {
+    fn empty() -> Self {
+        Node::Branch {
+            bitmap: 0,
+            children: Vec::new()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Branch { children, .. } => children.is_empty(),
+            Node::Leaf { entries, .. } => entries.is_empty()
+        }
+    }
+
+    fn get<Q>(&self, hash: u64, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Eq + ?Sized {
+        let mut node: &Node<K, V> = self;
+        let mut shift: u32 = 0;
+
+        loop {
+            match node {
+                Node::Branch { bitmap, children } => {
+                    let (bit, idx) = slot(*bitmap, hash, shift);
+
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+
+                    node = &children[idx];
+                    shift += BITS;
+                },
+                Node::Leaf { hash: leaf_hash, entries } => {
+                    if *leaf_hash != hash {
+                        return None;
+                    }
+
+                    return entries.iter()
+                        .find(|(k, _)| k.borrow() == key)
+                        .map(|(_, v)| v);
+                }
+            }
+        }
+    }
+}
+
+/* inserts in place, copying each node on the path that is shared with
+ * another version; returns whether the key was new */
+fn insert<K, V>(node: &mut Arc<Node<K, V>>, shift: u32, hash: u64, key: K,
+                value: V) -> bool where K: Eq + Clone, V: Clone {
+    let node: &mut Node<K, V> = Arc::make_mut(node);
+
+    if let Node::Leaf { hash: leaf_hash, entries } = node {
+        if *leaf_hash == hash {
+            return match entries.iter_mut().find(|(k, _)| *k == key) {
+                Some(entry) => {
+                    entry.1 = value;
+                    false
+                },
+                None => {
+                    entries.push((key, value));
+                    true
+                }
+            };
+        }
+
+        /* push the existing leaf one level down and retry as a branch */
+        let old: Node<K, V> = std::mem::replace(node, Node::empty());
+        let (bit, _) = match &old {
+            Node::Leaf { hash, .. } => slot(0, *hash, shift),
+            Node::Branch { .. } => (0, 0)
+        };
+
+        *node = Node::Branch {
+            bitmap: bit,
+            children: vec![Arc::new(old)]
+        };
+    }
+
+    match node {
+        Node::Branch { bitmap, children } => {
+            let (bit, idx) = slot(*bitmap, hash, shift);
+
+            if *bitmap & bit == 0 {
+                *bitmap |= bit;
+                children.insert(idx, Arc::new(Node::Leaf {
+                    hash,
+                    entries: vec![(key, value)]
+                }));
+                return true;
+            }
+
+            insert(&mut children[idx], shift + BITS, hash, key, value)
+        },
+        Node::Leaf { .. } => false
+    }
+}
+
+/* removes in place, pruning emptied nodes and pulling lone leaves back up
+ * so the trie stays as shallow as its contents allow */
+fn remove<K, V, Q>(node: &mut Arc<Node<K, V>>, shift: u32, hash: u64,
+                   key: &Q) where K: Borrow<Q> + Clone, V: Clone,
+    Q: Eq + ?Sized {
+    match Arc::make_mut(node) {
+        Node::Branch { bitmap, children } => {
+            let (bit, idx) = slot(*bitmap, hash, shift);
+
+            if *bitmap & bit == 0 {
+                return;
+            }
+
+            remove(&mut children[idx], shift + BITS, hash, key);
+
+            let replacement: Option<Arc<Node<K, V>>> = match &*children[idx] {
+                Node::Branch { children: grandchildren, .. }
+                    if grandchildren.len() == 1 &&
+                        matches!(*grandchildren[0], Node::Leaf { .. }) =>
+                    Some(grandchildren[0].clone()),
+                _ => None
+            };
+
+            if children[idx].is_empty() {
+                children.remove(idx);
+                *bitmap &= !bit;
+            } else if let Some(leaf) = replacement {
+                children[idx] = leaf;
+            }
+        },
+        Node::Leaf { entries, .. } => {
+            entries.retain(|(k, _)| k.borrow() != key);
+        }
+    }
+}
+
+fn get_mut<'a, K, V, Q>(node: &'a mut Arc<Node<K, V>>, shift: u32, hash: u64,
+                        key: &Q) -> Option<&'a mut V> where
+    K: Borrow<Q> + Clone, V: Clone, Q: Eq + ?Sized {
+    match Arc::make_mut(node) {
+        Node::Branch { bitmap, children } => {
+            let (bit, idx) = slot(*bitmap, hash, shift);
+
+            if *bitmap & bit == 0 {
+                return None;
+            }
+
+            get_mut(&mut children[idx], shift + BITS, hash, key)
+        },
+        Node::Leaf { entries, .. } => entries.iter_mut()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+}
+
+fn leaves_mut<'a, K, V>(node: &'a mut Arc<Node<K, V>>,
+                        res: &mut Vec<&'a mut [(K, V)]>) where K: Clone,
+    V: Clone {
+    match Arc::make_mut(node) {
+        Node::Branch { children, .. } => {
+            for child in children.iter_mut() {
+                leaves_mut(child, res);
+            }
+        },
+        Node::Leaf { entries, .. } => res.push(entries.as_mut_slice())
+    }
+}
+
+/* an immutable hash array mapped trie: each level consumes five bits of the
+ * key's hash and stores only the children that exist, so updates copy the
+ * O(log32 n) nodes on one path and share everything else through `Arc` */
+pub struct PersistentHashMap<K, V> {
+    root: Arc<Node<K, V>>,
+    len: usize
+}
+
+pub struct Iter<'a, K, V> {
+    stack: Vec<slice::Iter<'a, Arc<Node<K, V>>>>,
+    entries: slice::Iter<'a, (K, V)>,
+    remaining: usize
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, v)) = self.entries.next() {
+                self.remaining -= 1;
+                return Some((k, v));
+            }
+
+            match self.stack.last_mut()?.next() {
+                Some(child) => match &**child {
+                    Node::Branch { children, .. } =>
+                        self.stack.push(children.iter()),
+                    Node::Leaf { entries, .. } =>
+                        self.entries = entries.iter()
+                },
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+type EntryMut<'a, K, V> = fn(&'a mut (K, V)) -> (&'a K, &'a mut V);
+
+pub type IterMut<'a, K, V> = std::iter::Map<
+    std::iter::Flatten<std::vec::IntoIter<&'a mut [(K, V)]>>,
+    EntryMut<'a, K, V>>;
+
+impl<K, V> PersistentHashMap<K, V> {
+    pub fn new() -> Self {
+        PersistentHashMap {
+            root: Arc::new(Node::empty()),
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let (stack, entries) = match &*self.root {
+            Node::Branch { children, .. } => (vec![children.iter()], [].iter()),
+            Node::Leaf { entries, .. } => (Vec::new(), entries.iter())
+        };
+
+        Iter {
+            stack,
+            entries,
+            remaining: self.len
+        }
+    }
+
+    /* whether both versions share the same trie */
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root)
+    }
+}
+
+impl<K, V> PersistentHashMap<K, V> where K: Eq + Hash + Clone, V: Clone {
+    /* a new version mapping `key` to `value` */
+    pub fn insert(&self, key: K, value: V) -> Self {
+        let mut res: PersistentHashMap<K, V> = self.clone();
+
+        if insert(&mut res.root, 0, hash_of(&key), key, value) {
+            res.len += 1;
+        }
+
+        res
+    }
+
+    /* a new version without `key`, or `self` again if it had no such key */
+    pub fn without<Q>(&self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized {
+        let mut res: PersistentHashMap<K, V> = self.clone();
+        let hash: u64 = hash_of(key);
+
+        if self.root.get(hash, key).is_some() {
+            remove(&mut res.root, 0, hash, key);
+            res.len -= 1;
+        }
+
+        res
+    }
+}
+
+impl<K, V> Clone for PersistentHashMap<K, V> {
+    fn clone(&self) -> Self {
+        PersistentHashMap {
+            root: self.root.clone(),
+            len: self.len
+        }
+    }
+}
+
+impl<K, V> Default for PersistentHashMap<K, V> {
+    fn default() -> Self {
+        PersistentHashMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for PersistentHashMap<K, V> where K: fmt::Debug,
+    V: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> PartialEq for PersistentHashMap<K, V> where K: Eq + Hash,
+    V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        self.ptr_eq(other) || self.iter().all(|(k, v)| {
+            other.root.get(hash_of(k), k) == Some(v)
+        })
+    }
+}
+
+impl<K, V> Eq for PersistentHashMap<K, V> where K: Eq + Hash, V: Eq {}
+
+impl<K, V> IntoIterator for PersistentHashMap<K, V> where K: Clone,
+    V: Clone {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<(K, V)>>()
+            .into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a PersistentHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for PersistentHashMap<K, V> where
+    K: Eq + Hash + Clone, V: Clone {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: PersistentHashMap<K, V> = PersistentHashMap::new();
+
+        for (key, value) in iter {
+            if insert(&mut res.root, 0, hash_of(&key), key, value) {
+                res.len += 1;
+            }
+        }
+
+        res
+    }
+}
+
+/* the mutating methods rebind this handle to a new version, leaving any
+ * other clone of the map untouched */
+impl<K, V> Map<K, V> for PersistentHashMap<K, V> where K: Eq + Hash + Clone,
+    V: Clone {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        PersistentHashMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        if insert(&mut self.root, 0, hash_of(&key), key, value) {
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        *self = PersistentHashMap::new();
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        PersistentHashMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let mut slices: Vec<&mut [(K, V)]> = Vec::new();
+        leaves_mut(&mut self.root, &mut slices);
+
+        let entry: EntryMut<'_, K, V> = |(k, v)| (&*k, v);
+        slices.into_iter().flatten().map(entry)
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for PersistentHashMap<K, V> where
+    K: Eq + Hash + Clone + Borrow<Q>, V: Clone, Q: Eq + Hash + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.root.get(hash_of(key), key)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        let hash: u64 = hash_of(key);
+
+        self.root.get(hash, key)?; /* don't copy for a miss */
+        get_mut(&mut self.root, 0, hash, key)
+    }
+
+    /* the value may still be shared with other versions, so it is cloned
+     * out before the path to it is copied and pruned */
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        let hash: u64 = hash_of(key);
+        let value: V = self.root.get(hash, key)?.clone();
+
+        remove(&mut self.root, 0, hash, key);
+        self.len -= 1;
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* every key hashes alike, forcing the collision buckets */
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct Colliding(u64);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            0u64.hash(state);
+        }
+    }
+
+    fn depth<K, V>(node: &Node<K, V>) -> usize {
+        match node {
+            Node::Branch { children, .. } =>
+                1 + children.iter().map(|c| depth(c)).max().unwrap_or(0),
+            Node::Leaf { .. } => 0
+        }
+    }
+
+    #[test]
+    fn test_insert_normal_versions() -> Result<(), MapError> {
+        let empty: PersistentHashMap<u64, String> = PersistentHashMap::new();
+        let a: PersistentHashMap<u64, String> =
+            empty.insert(1, String::from("one"));
+        let b: PersistentHashMap<u64, String> =
+            a.insert(1, String::from("uno")).insert(2, String::from("two"));
+
+        assert_eq!(empty.size()?, 0);
+        assert_eq!(a.get(&1)?, "one");
+        assert_eq!(b.get(&1)?, "uno");
+        assert_eq!(b.size()?, 2);
+        assert_eq!(a.get(&2), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_many() -> Result<(), MapError> {
+        let actual_map: PersistentHashMap<u64, u64> =
+            (0..20_000).map(|i| (i, i * 2)).collect();
+
+        assert_eq!(actual_map.size()?, 20_000);
+        assert!(depth(&actual_map.root) <= 8);
+
+        for i in (0..20_000).step_by(101) {
+            assert_eq!(actual_map.get(&i)?, &(i * 2));
+        }
+
+        assert!(!actual_map.contains_key(&20_000)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_shares_structure() -> Result<(), MapError> {
+        let original: PersistentHashMap<u64, u64> =
+            (0..1000).map(|i| (i, i)).collect();
+        let actual_map: PersistentHashMap<u64, u64> = original.insert(7, 0);
+
+        if let (Node::Branch { children: a, .. },
+                Node::Branch { children: b, .. }) =
+            (&*original.root, &*actual_map.root) {
+            let shared: usize = a.iter()
+                .zip(b.iter())
+                .filter(|(x, y)| Arc::ptr_eq(x, y))
+                .count();
+
+            assert_eq!(shared, a.len() - 1);
+        }
+
+        assert_eq!(original.get(&7)?, &7);
+        assert_eq!(actual_map.get(&7)?, &0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_without_normal() -> Result<(), MapError> {
+        let original: PersistentHashMap<u64, u64> =
+            (0..500).map(|i| (i, i)).collect();
+        let mut actual_map: PersistentHashMap<u64, u64> = original.clone();
+
+        for i in 0..499 {
+            actual_map = actual_map.without(&i);
+        }
+
+        assert_eq!(actual_map.size()?, 1);
+        assert_eq!(actual_map.get(&499)?, &499);
+        assert_eq!(depth(&actual_map.root), 1);
+        assert_eq!(original.size()?, 500);
+        assert!(actual_map.without(&1000).ptr_eq(&actual_map));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collisions_normal() -> Result<(), MapError> {
+        let mut actual_map: PersistentHashMap<Colliding, u64> =
+            PersistentHashMap::new();
+
+        for i in 0..10 {
+            actual_map = actual_map.insert(Colliding(i), i);
+        }
+
+        actual_map = actual_map.without(&Colliding(3));
+
+        assert_eq!(actual_map.size()?, 9);
+        assert_eq!(actual_map.get(&Colliding(9))?, &9);
+        assert_eq!(actual_map.get(&Colliding(3)), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_normal_mutation_is_local() -> Result<(), MapError> {
+        let snapshot: PersistentHashMap<String, u64> = vec![
+            (String::from("a"), 1), (String::from("b"), 2)
+        ].into_iter().collect();
+        let mut actual_map: PersistentHashMap<String, u64> = snapshot.clone();
+
+        *actual_map.get_mut("a")? += 10;
+        actual_map.set(String::from("c"), 3)?;
+        actual_map.remove("b")?;
+
+        for (_, value) in actual_map.iter_mut() {
+            *value *= 2;
+        }
+
+        assert_eq!(actual_map.get("a")?, &22);
+        assert_eq!(actual_map.get("c")?, &6);
+        assert_eq!(actual_map.remove("b"), Err(MapError::KeyNotFound));
+        assert_eq!(snapshot.get("a")?, &1);
+        assert_eq!(snapshot.get("b")?, &2);
+        assert!(snapshot.contains_value(&2)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal() -> Result<(), MapError> {
+        let actual_map: PersistentHashMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+        let mut actual_keys: Vec<u64> =
+            actual_map.iter().map(|(k, _)| *k).collect();
+
+        actual_keys.sort();
+
+        assert_eq!(actual_keys, (0..100).collect::<Vec<u64>>());
+        assert_eq!(actual_map.iter().len(), 100);
+        assert_eq!(actual_map, actual_map.clone().into_iter().collect());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_threads_normal_snapshot() -> Result<(), MapError> {
+        let snapshot: PersistentHashMap<u64, u64> =
+            (0..1000).map(|i| (i, i)).collect();
+
+        let totals: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let version: PersistentHashMap<u64, u64> =
+                        snapshot.without(&i);
+                    scope.spawn(move || version.iter().map(|(_, v)| v).sum())
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap_or(0)).collect()
+        });
+
+        assert_eq!(totals, vec![499_500, 499_499, 499_498, 499_497]);
+        Ok(())
+    }
+}
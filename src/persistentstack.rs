@@ -0,0 +1,220 @@
+use std::fmt;
+use std::fmt::Display;
+use crate::conslist::{ConsList, Iter, IterMut, IntoIter};
+use crate::stack::{Stack, StackError};
+
+/* a stack over a `ConsList`, so cloning is O(1) and pushes onto one clone
+ * never disturb another; the cells below the top are shared between all
+ * the versions that pushed onto them */
+pub struct PersistentStack<T> {
+    list: ConsList<T>
+}
+
+impl<T> PersistentStack<T> {
+    pub fn new() -> Self {
+        PersistentStack {
+            list: ConsList::new()
+        }
+    }
+
+    pub fn as_list(&self) -> &ConsList<T> {
+        &self.list
+    }
+}
+
+impl<T> Clone for PersistentStack<T> {
+    fn clone(&self) -> Self {
+        PersistentStack {
+            list: self.list.clone()
+        }
+    }
+}
+
+impl<T> Default for PersistentStack<T> {
+    fn default() -> Self {
+        PersistentStack::new()
+    }
+}
+
+impl<T> fmt::Debug for PersistentStack<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.list.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for PersistentStack<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<T> Eq for PersistentStack<T> where T: Eq {}
+
+impl<T> From<ConsList<T>> for PersistentStack<T> {
+    /* the head of the list becomes the top of the stack */
+    fn from(list: ConsList<T>) -> Self {
+        PersistentStack {
+            list
+        }
+    }
+}
+
+impl<T> From<PersistentStack<T>> for ConsList<T> {
+    fn from(stack: PersistentStack<T>) -> Self {
+        stack.list
+    }
+}
+
+impl<T: Clone> IntoIterator for PersistentStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+impl<T> Display for PersistentStack<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.list)
+    }
+}
+
+impl<T: Clone> Stack<T> for PersistentStack<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        PersistentStack::new()
+    }
+
+    fn push(&mut self, elem: T) -> Result<(), StackError> {
+        self.list = self.list.cons(elem);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<T, StackError> {
+        match self.list.pop_head() {
+            Some(elem) => Ok(elem),
+            None => Err(StackError::OutOfBounds)
+        }
+    }
+
+    fn peek(&self) -> Result<&T, StackError> {
+        match self.list.head() {
+            Some(elem) => Ok(elem),
+            None => Err(StackError::OutOfBounds)
+        }
+    }
+
+    fn depth(&self) -> Result<usize, StackError> {
+        Ok(self.list.length())
+    }
+
+    fn clear(&mut self) -> Result<(), StackError> {
+        self.list = ConsList::new();
+        Ok(())
+    }
+
+    /* from the top of the stack down */
+    fn iter(&self) -> Self::Iter<'_> {
+        self.list.iter()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.list.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_normal_versions() -> Result<(), StackError> {
+        let mut base: PersistentStack<u64> = PersistentStack::new();
+
+        base.push(1)?;
+        base.push(2)?;
+
+        let mut a: PersistentStack<u64> = base.clone();
+        let mut b: PersistentStack<u64> = base.clone();
+
+        a.push(3)?;
+        b.push(4)?;
+
+        assert_eq!(a.iter().cloned().collect::<Vec<u64>>(), vec![3, 2, 1]);
+        assert_eq!(b.iter().cloned().collect::<Vec<u64>>(), vec![4, 2, 1]);
+        assert!(a.as_list().tail().is_some_and(|t| t.ptr_eq(base.as_list())));
+        assert!(b.as_list().tail().is_some_and(|t| t.ptr_eq(base.as_list())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_normal_backtracking() -> Result<(), StackError> {
+        let mut actual_stack: PersistentStack<u64> = PersistentStack::new();
+
+        actual_stack.push(1)?;
+        actual_stack.push(2)?;
+
+        let checkpoint: PersistentStack<u64> = actual_stack.clone();
+
+        assert_eq!(actual_stack.pop()?, 2);
+        assert_eq!(actual_stack.pop()?, 1);
+        assert_eq!(actual_stack.pop(), Err(StackError::OutOfBounds));
+
+        actual_stack = checkpoint;
+
+        assert_eq!(actual_stack.peek()?, &2);
+        assert_eq!(actual_stack.depth()?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), StackError> {
+        let mut base: PersistentStack<u64> = PersistentStack::new();
+
+        base.push(1)?;
+        base.push(2)?;
+
+        let mut actual_stack: PersistentStack<u64> = base.clone();
+
+        for elem in actual_stack.iter_mut() {
+            *elem += 10;
+        }
+
+        assert_eq!(actual_stack.into_iter().collect::<Vec<u64>>(),
+                   vec![12, 11]);
+        assert_eq!(base.peek()?, &2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), StackError> {
+        let mut actual_stack: Box<dyn crate::stack::DynStack<u64>> =
+            Box::new(PersistentStack::new());
+
+        actual_stack.push(7)?;
+        actual_stack.push(8)?;
+
+        assert_eq!(actual_stack.pop()?, 8);
+        assert_eq!(actual_stack.depth()?, 1);
+
+        actual_stack.clear()?;
+
+        assert_eq!(actual_stack.peek(), Err(StackError::OutOfBounds));
+        Ok(())
+    }
+}
@@ -0,0 +1,656 @@
+use std::borrow::Borrow;
+use std::ops::Index;
+use std::iter::FromIterator;
+use std::fmt;
+use std::fmt::Display;
+use std::sync::Arc;
+use crate::list::ListError;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+#[derive(Clone)]
+enum Node<T> {
+    Branch(Vec<Arc<Node<T>>>),
+    Leaf(Vec<T>)
+}
+
+fn new_path<T>(level: usize, node: Arc<Node<T>>) -> Arc<Node<T>> {
+    if level == 0 {
+        node
+    } else {
+        Arc::new(Node::Branch(vec![new_path(level - BITS, node)]))
+    }
+}
+
+/* hangs a full leaf off the rightmost edge of the trie, copying (rather
+ * than mutating) any node on the way that is shared with another version */
+fn push_tail<T: Clone>(node: &mut Arc<Node<T>>, level: usize, len: usize,
+                       leaf: Arc<Node<T>>) {
+    if let Node::Branch(children) = Arc::make_mut(node) {
+        let subidx: usize = ((len - 1) >> level) & MASK;
+
+        if level == BITS {
+            children.push(leaf);
+        } else if subidx < children.len() {
+            push_tail(&mut children[subidx], level - BITS, len, leaf);
+        } else {
+            children.push(new_path(level - BITS, leaf));
+        }
+    }
+}
+
+/* drops the rightmost leaf, returning whether `node` was left empty */
+fn pop_tail<T: Clone>(node: &mut Arc<Node<T>>, level: usize, len: usize)
+    -> bool {
+    match Arc::make_mut(node) {
+        Node::Branch(children) => {
+            let subidx: usize = ((len - 2) >> level) & MASK;
+
+            if level > BITS {
+                if subidx < children.len() &&
+                    pop_tail(&mut children[subidx], level - BITS, len) {
+                    children.truncate(subidx);
+                }
+            } else {
+                children.truncate(subidx);
+            }
+
+            children.is_empty()
+        },
+        Node::Leaf(_) => true
+    }
+}
+
+fn assoc<T: Clone>(node: &mut Arc<Node<T>>, level: usize, pos: usize,
+                   elem: T) {
+    match Arc::make_mut(node) {
+        Node::Branch(children) => {
+            let subidx: usize = (pos >> level) & MASK;
+            assoc(&mut children[subidx], level - BITS, pos, elem);
+        },
+        Node::Leaf(elems) => elems[pos & MASK] = elem
+    }
+}
+
+/* an immutable vector stored as a 32-way trie of `Arc`-shared nodes plus a
+ * separate tail leaf, after Clojure's; every update returns a new version
+ * that copies only the O(log32 n) nodes on the path it touched and shares
+ * the rest with `self` */
+pub struct PersistentVector<T> {
+    root: Arc<Node<T>>,
+    tail: Arc<Vec<T>>,
+    shift: usize,
+    len: usize
+}
+
+pub struct Iter<'a, T> {
+    vector: &'a PersistentVector<T>,
+    front: usize,
+    back: usize
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        self.vector.get(self.front - 1).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.vector.get(self.back).ok()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<T> PersistentVector<T> {
+    pub fn new() -> Self {
+        PersistentVector {
+            root: Arc::new(Node::Branch(Vec::new())),
+            tail: Arc::new(Vec::new()),
+            shift: BITS,
+            len: 0
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, pos: usize) -> Result<&T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        if pos >= self.tail_offset() {
+            return Ok(&self.tail[pos - self.tail_offset()]);
+        }
+
+        let mut node: &Node<T> = &self.root;
+        let mut level: usize = self.shift;
+
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    match children.get((pos >> level) & MASK) {
+                        Some(child) => node = child,
+                        None => return Err(ListError::Impossible)
+                    }
+
+                    level = level.saturating_sub(BITS);
+                },
+                Node::Leaf(elems) => {
+                    return match elems.get(pos & MASK) {
+                        Some(elem) => Ok(elem),
+                        None => Err(ListError::Impossible)
+                    };
+                }
+            }
+        }
+    }
+
+    pub fn contains<Q>(&self, elem: &Q) -> bool where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter().any(|curr| curr.borrow() == elem)
+    }
+
+    pub fn find<Q>(&self, elem: &Q) -> Option<usize> where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter().position(|curr| curr.borrow() == elem)
+    }
+
+    pub fn find_all<Q>(&self, elem: &Q) -> Vec<usize> where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter()
+            .enumerate()
+            .filter(|(_, curr)| (*curr).borrow() == elem)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn count<Q>(&self, elem: &Q) -> usize where T: Borrow<Q>,
+        Q: Eq + ?Sized {
+        self.iter().filter(|curr| (*curr).borrow() == elem).count()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vector: self,
+            front: 0,
+            back: self.len
+        }
+    }
+
+    /* whether both versions share the same underlying trie */
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root) &&
+            Arc::ptr_eq(&self.tail, &other.tail)
+    }
+
+    fn tail_offset(&self) -> usize {
+        self.len - self.tail.len()
+    }
+}
+
+impl<T: Clone> PersistentVector<T> {
+    pub fn set(&self, pos: usize, elem: T) -> Result<Self, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let mut res: PersistentVector<T> = self.clone();
+        res.set_mut(pos, elem);
+
+        Ok(res)
+    }
+
+    pub fn append(&self, elem: T) -> Self {
+        let mut res: PersistentVector<T> = self.clone();
+        res.push_mut(elem);
+        res
+    }
+
+    /* the version without the last element */
+    pub fn pop(&self) -> Result<Self, ListError> {
+        if self.len == 0 { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let mut res: PersistentVector<T> = self.clone();
+        res.pop_mut();
+
+        Ok(res)
+    }
+
+    /* a 32-way trie can't shift elements between leaves, so this rebuilds
+     * the suffix after `pos` in O(n - pos) */
+    pub fn insert(&self, pos: usize, elem: T) -> Result<Self, ListError> {
+        if pos > self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let suffix: Vec<T> = self.iter().skip(pos).cloned().collect();
+        let mut res: PersistentVector<T> = self.truncate(pos);
+
+        res.push_mut(elem);
+
+        for curr in suffix {
+            res.push_mut(curr);
+        }
+
+        Ok(res)
+    }
+
+    /* O(n - pos), as with `insert` */
+    pub fn remove(&self, pos: usize) -> Result<Self, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let suffix: Vec<T> = self.iter().skip(pos + 1).cloned().collect();
+        let mut res: PersistentVector<T> = self.truncate(pos);
+
+        for curr in suffix {
+            res.push_mut(curr);
+        }
+
+        Ok(res)
+    }
+
+    /* shares all of `self` and copies `other` in O(m) */
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut res: PersistentVector<T> = self.clone();
+
+        for elem in other.iter() {
+            res.push_mut(elem.clone());
+        }
+
+        res
+    }
+
+    fn truncate(&self, len: usize) -> Self {
+        let mut res: PersistentVector<T> = self.clone();
+
+        while res.len > len {
+            res.pop_mut();
+        }
+
+        res
+    }
+
+    fn set_mut(&mut self, pos: usize, elem: T) {
+        let tail_offset: usize = self.tail_offset();
+
+        if pos >= tail_offset {
+            Arc::make_mut(&mut self.tail)[pos - tail_offset] = elem;
+        } else {
+            assoc(&mut self.root, self.shift, pos, elem);
+        }
+    }
+
+    fn push_mut(&mut self, elem: T) {
+        if self.tail.len() < WIDTH {
+            Arc::make_mut(&mut self.tail).push(elem);
+            self.len += 1;
+            return;
+        }
+
+        let mut new_tail: Vec<T> = Vec::with_capacity(WIDTH);
+        new_tail.push(elem);
+
+        let full: Arc<Vec<T>> =
+            std::mem::replace(&mut self.tail, Arc::new(new_tail));
+        let leaf: Arc<Node<T>> =
+            Arc::new(Node::Leaf(Arc::unwrap_or_clone(full)));
+
+        if (self.len >> BITS) > (1 << self.shift) { /* root overflow */
+            let path: Arc<Node<T>> = new_path(self.shift, leaf);
+            let old_root: Arc<Node<T>> = self.root.clone();
+
+            self.root = Arc::new(Node::Branch(vec![old_root, path]));
+            self.shift += BITS;
+        } else {
+            push_tail(&mut self.root, self.shift, self.len, leaf);
+        }
+
+        self.len += 1;
+    }
+
+    fn pop_mut(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+
+        if self.len == 1 || self.tail.len() > 1 {
+            Arc::make_mut(&mut self.tail).pop();
+            self.len -= 1;
+            return;
+        }
+
+        /* the tail is about to empty, so the last leaf becomes the tail */
+        let new_tail: Vec<T> = self.leaf_for(self.len - 2);
+
+        pop_tail(&mut self.root, self.shift, self.len);
+
+        if self.shift > BITS {
+            let only_child: Option<Arc<Node<T>>> = match &*self.root {
+                Node::Branch(children) if children.len() == 1 =>
+                    Some(children[0].clone()),
+                _ => None
+            };
+
+            if let Some(child) = only_child {
+                self.root = child;
+                self.shift -= BITS;
+            }
+        }
+
+        self.tail = Arc::new(new_tail);
+        self.len -= 1;
+    }
+
+    fn leaf_for(&self, pos: usize) -> Vec<T> {
+        let mut node: &Node<T> = &self.root;
+        let mut level: usize = self.shift;
+
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    match children.get((pos >> level) & MASK) {
+                        Some(child) => node = child,
+                        None => return Vec::new()
+                    }
+
+                    level = level.saturating_sub(BITS);
+                },
+                Node::Leaf(elems) => return elems.clone()
+            }
+        }
+    }
+}
+
+impl<T> Clone for PersistentVector<T> {
+    fn clone(&self) -> Self {
+        PersistentVector {
+            root: self.root.clone(),
+            tail: self.tail.clone(),
+            shift: self.shift,
+            len: self.len
+        }
+    }
+}
+
+impl<T> Default for PersistentVector<T> {
+    fn default() -> Self {
+        PersistentVector::new()
+    }
+}
+
+impl<T> fmt::Debug for PersistentVector<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> PartialEq for PersistentVector<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        self.ptr_eq(other) || self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for PersistentVector<T> where T: Eq {}
+
+impl<'a, T> IntoIterator for &'a PersistentVector<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PersistentVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res: PersistentVector<T> = PersistentVector::new();
+
+        for elem in iter {
+            res.push_mut(elem);
+        }
+
+        res
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for PersistentVector<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T> Index<usize> for PersistentVector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> Display for PersistentVector<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in self.iter() {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(vector: &PersistentVector<u64>) -> Vec<u64> {
+        vector.iter().cloned().collect()
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_new_normal() -> Result<(), ListError> {
+        let actual_vector: PersistentVector<u64> = PersistentVector::new();
+
+        assert_eq!(actual_vector.length(), 0);
+        assert!(actual_vector.is_empty());
+        assert_eq!(actual_vector.get(0), Err(ListError::OutOfBounds));
+        assert_send_sync::<PersistentVector<u64>>();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_normal_deep() -> Result<(), ListError> {
+        let mut actual_vector: PersistentVector<u64> = PersistentVector::new();
+
+        for i in 0..40_000 {
+            actual_vector = actual_vector.append(i);
+        }
+
+        assert_eq!(actual_vector.length(), 40_000);
+        assert_eq!(actual_vector.shift, 3 * BITS);
+
+        for i in (0..40_000).step_by(997) {
+            assert_eq!(actual_vector.get(i as usize)?, &i);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_normal_versions() -> Result<(), ListError> {
+        let original: PersistentVector<u64> = (0..100).collect();
+        let actual_vector: PersistentVector<u64> = original.append(100);
+
+        assert_eq!(contents(&original), (0..100).collect::<Vec<u64>>());
+        assert_eq!(contents(&actual_vector), (0..101).collect::<Vec<u64>>());
+        assert!(Arc::ptr_eq(&original.root, &actual_vector.root));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal() -> Result<(), ListError> {
+        let original: PersistentVector<u64> = (0..2000).collect();
+        let actual_vector: PersistentVector<u64> =
+            original.set(5, 0)?.set(1999, 0)?;
+
+        assert_eq!(original[5], 5);
+        assert_eq!(original[1999], 1999);
+        assert_eq!(actual_vector[5], 0);
+        assert_eq!(actual_vector[1999], 0);
+        assert_eq!(actual_vector.count(&0), 3);
+
+        /* untouched subtrees are shared between the versions */
+        if let (Node::Branch(a), Node::Branch(b)) =
+            (&*original.root, &*actual_vector.root) {
+            assert!(!Arc::ptr_eq(&a[0], &b[0]));
+            assert!(Arc::ptr_eq(&a[1], &b[1]));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_error_out_of_bounds() -> Result<(), ListError> {
+        let actual_vector: PersistentVector<u64> = (0..3).collect();
+
+        assert_eq!(actual_vector.set(3, 0).err(),
+                   Some(ListError::OutOfBounds));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_normal() -> Result<(), ListError> {
+        let original: PersistentVector<u64> = (0..1100).collect();
+        let mut actual_vector: PersistentVector<u64> = original.clone();
+
+        for len in (0..1100).rev() {
+            actual_vector = actual_vector.pop()?;
+
+            assert_eq!(actual_vector.length(), len);
+
+            if len % 31 == 0 && len > 0 {
+                assert_eq!(actual_vector.get(len - 1)?, &(len as u64 - 1));
+            }
+        }
+
+        assert_eq!(actual_vector.shift, BITS);
+        assert_eq!(actual_vector.pop().err(), Some(ListError::OutOfBounds));
+        assert_eq!(contents(&original), (0..1100).collect::<Vec<u64>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal() -> Result<(), ListError> {
+        let original: PersistentVector<u64> = (0..100).collect();
+        let actual_vector: PersistentVector<u64> = original.insert(40, 1000)?;
+        let mut expected_elems: Vec<u64> = (0..100).collect();
+
+        expected_elems.insert(40, 1000);
+
+        assert_eq!(contents(&actual_vector), expected_elems);
+        assert_eq!(original.length(), 100);
+        assert_eq!(original.insert(101, 0).err(),
+                   Some(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), ListError> {
+        let original: PersistentVector<u64> = (0..100).collect();
+        let actual_vector: PersistentVector<u64> = original.remove(0)?;
+
+        assert_eq!(contents(&actual_vector), (1..100).collect::<Vec<u64>>());
+        assert_eq!(original[0], 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat_normal() -> Result<(), ListError> {
+        let a: PersistentVector<u64> = (0..50).collect();
+        let b: PersistentVector<u64> = (50..80).collect();
+
+        assert_eq!(contents(&a.concat(&b)), (0..80).collect::<Vec<u64>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal() -> Result<(), ListError> {
+        let actual_vector: PersistentVector<u64> =
+            PersistentVector::from(vec![4, 1, 4, 2]);
+
+        assert_eq!(actual_vector.find(&4), Some(0));
+        assert_eq!(actual_vector.find_all(&4), vec![0, 2]);
+        assert!(!actual_vector.contains(&3));
+        assert_eq!(actual_vector.iter().rev().nth(1), Some(&4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_threads_normal_shared_snapshot() -> Result<(), ListError> {
+        let snapshot: PersistentVector<u64> = (0..10_000).collect();
+
+        let sums: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let version: PersistentVector<u64> = snapshot.set(i, 0)
+                        .unwrap_or_default();
+                    scope.spawn(move || version.iter().sum::<u64>())
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap_or(0)).collect()
+        });
+
+        for (i, sum) in sums.into_iter().enumerate() {
+            assert_eq!(sum, 49_995_000 - i as u64);
+        }
+
+        assert_eq!(snapshot.iter().sum::<u64>(), 49_995_000);
+        Ok(())
+    }
+}
@@ -1,17 +1,142 @@
-use std::fmt::{Display, Debug};
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum PriorityQueueError {
-    OutOfBounds
+    OutOfBounds,
+    AllocationFailed
 }
 
-pub trait PriorityQueue<T: Sized + Eq + Clone + Ord + Display + Debug>: Eq + 
-    Clone + IntoIterator {
+impl fmt::Display for PriorityQueueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriorityQueueError::OutOfBounds =>
+                write!(f, "priority queue is empty"),
+            PriorityQueueError::AllocationFailed =>
+                write!(f, "memory allocation failed")
+        }
+    }
+}
+
+impl Error for PriorityQueueError {}
+
+pub trait PriorityQueue<T: Ord>: IntoIterator {
+    type Iter<'a>: Iterator<Item = &'a T> + ExactSizeIterator where
+        Self: 'a, T: 'a;
+
     fn new() -> Self;
+    fn with_capacity(capacity: usize) -> Self;
     fn push(&mut self, elem: T) -> Result<(), PriorityQueueError>;
     fn pop(&mut self) -> Result<T, PriorityQueueError>;
     fn peek(&self) -> Result<&T, PriorityQueueError>;
-    fn find(&self, elem: T) -> Result<Option<usize>, PriorityQueueError>;
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, PriorityQueueError>
+        where T: Borrow<Q>, Q: Eq + ?Sized;
     fn length(&self) -> Result<usize, PriorityQueueError>;
+    fn iter(&self) -> Self::Iter<'_>;
+
+    #[deprecated(note = "use `find` with a reference instead")]
+    fn find_by_value(&self, elem: T) ->
+        Result<Option<usize>, PriorityQueueError> {
+        self.find(&elem)
+    }
+}
+
+/* capacity management for queues backed by contiguous storage, split out
+ * of `PriorityQueue` the same way `list::Reserve` is out of `List`; every
+ * method is object safe, so `dyn Reserve` needs no separate trait */
+pub trait Reserve {
+    fn capacity(&self) -> Result<usize, PriorityQueueError>;
+    fn reserve(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError>;
+    fn reserve_exact(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError>;
+    fn try_reserve(&mut self, additional: usize) ->
+        Result<(), PriorityQueueError>;
+    fn shrink_to_fit(&mut self) -> Result<(), PriorityQueueError>;
+}
+
+/* object-safe subset of `PriorityQueue` for use as
+ * `Box<dyn DynPriorityQueue<T>>` */
+pub trait DynPriorityQueue<T> {
+    fn push(&mut self, elem: T) -> Result<(), PriorityQueueError>;
+    fn pop(&mut self) -> Result<T, PriorityQueueError>;
+    fn peek(&self) -> Result<&T, PriorityQueueError>;
+    fn find(&self, elem: &T) -> Result<Option<usize>, PriorityQueueError>;
+    fn length(&self) -> Result<usize, PriorityQueueError>;
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+}
+
+impl<T, P> DynPriorityQueue<T> for P where T: Ord, P: PriorityQueue<T> {
+    fn push(&mut self, elem: T) -> Result<(), PriorityQueueError> {
+        PriorityQueue::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Result<T, PriorityQueueError> {
+        PriorityQueue::pop(self)
+    }
+
+    fn peek(&self) -> Result<&T, PriorityQueueError> {
+        PriorityQueue::peek(self)
+    }
+
+    fn find(&self, elem: &T) -> Result<Option<usize>, PriorityQueueError> {
+        PriorityQueue::find(self, elem)
+    }
+
+    fn length(&self) -> Result<usize, PriorityQueueError> {
+        PriorityQueue::length(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(PriorityQueue::iter(self))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::heap::Heap;
+
+    fn make_priority_queue(kind: &str) -> Box<dyn DynPriorityQueue<u64>> {
+        match kind {
+            "heap" => Box::new(Heap::new()),
+            _ => unreachable!()
+        }
+    }
+
+    #[test]
+    fn test_dyn_priority_queue_normal() -> Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Box<dyn DynPriorityQueue<u64>> =
+            make_priority_queue("heap");
+
+        for i in 1..10 {
+            actual_priority_queue.push(i)?;
+        }
+
+        assert_eq!(actual_priority_queue.length()?, 9);
+        assert_eq!(actual_priority_queue.peek()?, &9);
+        assert_eq!(actual_priority_queue.pop()?, 9);
+        assert_eq!(actual_priority_queue.pop()?, 8);
+        assert_eq!(actual_priority_queue.iter().count(), 7);
+        assert!(actual_priority_queue.find(&3)?.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_priority_queue_error_out_of_bounds() ->
+        Result<(), PriorityQueueError> {
+        let mut actual_priority_queue: Box<dyn DynPriorityQueue<u64>> =
+            make_priority_queue("heap");
+
+        let actual_res: Result<u64, PriorityQueueError> =
+            actual_priority_queue.pop();
+        let expected_res: Result<u64, PriorityQueueError> =
+            Err(PriorityQueueError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+}
@@ -1,10 +1,23 @@
+use std::error::Error;
+use std::fmt;
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 #[allow(dead_code)]
 pub enum QueueError {
     OutOfBounds
 }
 
-pub trait Queue<T: Sized + Eq + Clone>: Eq + Clone {
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueueError::OutOfBounds => write!(f, "queue is empty")
+        }
+    }
+}
+
+impl Error for QueueError {}
+
+pub trait Queue<T> {
     fn new() -> Self;
     fn push(&mut self, elem: T) -> Result<(), QueueError>;
     fn pop(&mut self) -> Result<T, QueueError>;
@@ -12,3 +25,28 @@ pub trait Queue<T: Sized + Eq + Clone>: Eq + Clone {
     fn length(&self) -> Result<usize, QueueError>;
 }
 
+/* object-safe subset of `Queue` for use as `Box<dyn DynQueue<T>>` */
+pub trait DynQueue<T> {
+    fn push(&mut self, elem: T) -> Result<(), QueueError>;
+    fn pop(&mut self) -> Result<T, QueueError>;
+    fn peek(&self) -> Result<&T, QueueError>;
+    fn length(&self) -> Result<usize, QueueError>;
+}
+
+impl<T, Q> DynQueue<T> for Q where Q: Queue<T> {
+    fn push(&mut self, elem: T) -> Result<(), QueueError> {
+        Queue::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Result<T, QueueError> {
+        Queue::pop(self)
+    }
+
+    fn peek(&self) -> Result<&T, QueueError> {
+        Queue::peek(self)
+    }
+
+    fn length(&self) -> Result<usize, QueueError> {
+        Queue::length(self)
+    }
+}
@@ -0,0 +1,1197 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::{Bound, RangeBounds};
+use std::fmt;
+use std::mem;
+use crate::map::{Map, MapError, MapLookup, OrderedMap};
+
+/* a string of bits, most significant first; the unused low bits of the
+ * last byte are kept clear, so the derived orderings are lexicographic on
+ * the bits, with a prefix sorting before its extensions */
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct BitString {
+    bytes: Vec<u8>,
+    len: usize
+}
+
+impl BitString {
+    pub fn new() -> Self {
+        BitString {
+            bytes: Vec::new(),
+            len: 0
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        BitString {
+            bytes: bytes.to_vec(),
+            len: bytes.len() * 8
+        }
+    }
+
+    /* the first `len` bits of `bytes`, if there are that many */
+    pub fn from_bits(bytes: &[u8], len: usize) -> Option<Self> {
+        if len > bytes.len() * 8 { /* bounds check */
+            return None;
+        }
+
+        let mut res: BitString = BitString {
+            bytes: bytes[..len.div_ceil(8)].to_vec(),
+            len
+        };
+
+        res.mask();
+        Some(res)
+    }
+
+    /* a CIDR prefix: a family bit (clear for IPv4, set for IPv6) and then
+     * the first `len` bits of the address, so both families can share one
+     * table; `None` if `len` is longer than the address */
+    pub fn ip(addr: IpAddr, len: u8) -> Option<Self> {
+        let (family, octets): (bool, Vec<u8>) = match addr {
+            IpAddr::V4(addr) => (false, addr.octets().to_vec()),
+            IpAddr::V6(addr) => (true, addr.octets().to_vec())
+        };
+        let addr: BitString = BitString::from_bits(&octets, len as usize)?;
+        let mut res: BitString = BitString::new();
+
+        res.push(family);
+
+        for i in 0..addr.len {
+            res.push(addr.bit(i));
+        }
+
+        Some(res)
+    }
+
+    /* the whole address, as a prefix covering just that host */
+    pub fn host(addr: IpAddr) -> Self {
+        let len: u8 = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128
+        };
+
+        BitString::ip(addr, len).unwrap_or_default()
+    }
+
+    /* the inverse of `ip` */
+    pub fn to_ip(&self) -> Option<(IpAddr, u8)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let width: usize = if self.bit(0) { 128 } else { 32 };
+
+        if self.len - 1 > width {
+            return None;
+        }
+
+        let mut octets: [u8; 16] = [0; 16];
+
+        for i in 1..self.len {
+            if self.bit(i) {
+                octets[(i - 1) / 8] |= 0x80 >> ((i - 1) % 8);
+            }
+        }
+
+        let addr: IpAddr = if width == 128 {
+            IpAddr::V6(Ipv6Addr::from(octets))
+        } else {
+            IpAddr::V4(Ipv4Addr::new(octets[0], octets[1], octets[2],
+                                     octets[3]))
+        };
+
+        Some((addr, (self.len - 1) as u8))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.bytes[i / 8] & (0x80 >> (i % 8)) != 0
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    pub fn starts_with(&self, prefix: &BitString) -> bool {
+        prefix.len <= self.len && self.common_prefix_len(prefix) == prefix.len
+    }
+
+    pub fn common_prefix_len(&self, other: &BitString) -> usize {
+        let max: usize = self.len.min(other.len);
+
+        for (i, (a, b)) in self.bytes.iter().zip(other.bytes.iter())
+            .enumerate() {
+            if a != b {
+                return max.min(i * 8 + (a ^ b).leading_zeros() as usize);
+            }
+        }
+
+        max
+    }
+
+    /* the first `len` bits */
+    fn prefix(&self, len: usize) -> BitString {
+        let mut res: BitString = BitString {
+            bytes: self.bytes[..len.div_ceil(8)].to_vec(),
+            len
+        };
+
+        res.mask();
+        res
+    }
+
+    fn mask(&mut self) {
+        if self.len % 8 != 0 {
+            if let Some(last) = self.bytes.last_mut() {
+                *last &= 0xff << (8 - self.len % 8);
+            }
+        }
+    }
+}
+
+impl fmt::Display for BitString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.len {
+            write!(f, "{}", self.bit(i) as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+type Link<V> = Option<Box<Node<V>>>;
+
+#[derive(Clone, Debug)]
+struct Node<V> {
+    /* the whole key up to this node, not just the bits it adds */
+    key: BitString,
+    value: Option<V>,
+    children: [Link<V>; 2]
+}
+
+/* a path-compressed binary trie (a PATRICIA tree) over `BitString` keys:
+ * a node is kept only where a key ends or where keys part ways, so n keys
+ * take at most 2n - 1 nodes whatever their length; lookups cost O(key
+ * length), and a node without a value always has both children */
+#[derive(Clone, Debug)]
+pub struct RadixMap<V> {
+    root: Link<V>,
+    len: usize
+}
+
+pub struct Iter<'a, V> {
+    stack: Vec<&'a Node<V>>
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (&'a BitString, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node: &'a Node<V> = self.stack.pop()?;
+
+            self.stack.extend(node.children.iter().rev().flatten()
+                .map(|child| &**child));
+
+            if let Some(value) = node.value.as_ref() {
+                return Some((&node.key, value));
+            }
+        }
+    }
+}
+
+/* a preorder walk like `Iter`, cut off after the entry holding `last` */
+pub struct Range<'a, V> {
+    iter: Iter<'a, V>,
+    last: Option<&'a BitString>
+}
+
+impl<'a, V> Iterator for Range<'a, V> {
+    type Item = (&'a BitString, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last: &'a BitString = self.last?;
+        let (key, value) = self.iter.next()?;
+
+        match key.cmp(last) {
+            Ordering::Less => Some((key, value)),
+            Ordering::Equal => {
+                self.last = None;
+                Some((key, value))
+            },
+            Ordering::Greater => { /* the range was empty */
+                self.last = None;
+                None
+            }
+        }
+    }
+}
+
+pub struct IterMut<'a, V> {
+    stack: Vec<&'a mut Node<V>>
+}
+
+impl<'a, V> Iterator for IterMut<'a, V> {
+    type Item = (&'a BitString, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Node { key, value, children } = self.stack.pop()?;
+
+            self.stack.extend(children.iter_mut().rev().flatten()
+                .map(|child| &mut **child));
+
+            if let Some(value) = value.as_mut() {
+                return Some((&*key, value));
+            }
+        }
+    }
+}
+
+pub struct IntoIter<V> {
+    stack: Vec<Box<Node<V>>>
+}
+
+impl<V> Iterator for IntoIter<V> {
+    type Item = (BitString, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut node: Box<Node<V>> = self.stack.pop()?;
+            let [left, right] = mem::take(&mut node.children);
+
+            self.stack.extend(right);
+            self.stack.extend(left);
+
+            if let Some(value) = node.value.take() {
+                return Some((mem::take(&mut node.key), value));
+            }
+        }
+    }
+}
+
+/* a long run of nested prefixes is a deep chain, so it is taken apart a
+ * level at a time rather than by the recursive drop glue */
+impl<V> Drop for Node<V> {
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<V>>> = Vec::new();
+
+        stack.extend(self.children.iter_mut().filter_map(|c| c.take()));
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.children.iter_mut().filter_map(|c| c.take()));
+        }
+    }
+}
+
+/* once `link`'s node has lost its value or a child, replaces it with its
+ * only child, or with nothing, if it no longer marks a key or a fork */
+fn collapse<V>(link: &mut Link<V>) {
+    let node: &mut Box<Node<V>> = match link.as_mut() {
+        Some(node) => node,
+        None => return
+    };
+
+    if node.value.is_some() {
+        return;
+    }
+
+    match &mut node.children {
+        [Some(_), Some(_)] => {},
+        [child @ Some(_), None] | [None, child @ Some(_)] => {
+            let child: Link<V> = child.take();
+
+            *link = child;
+        },
+        [None, None] => *link = None
+    }
+}
+
+/* the node holding the greatest key below `node`, which is a leaf and
+ * so has a value */
+fn last<V>(mut node: &Node<V>) -> &Node<V> {
+    loop {
+        match &node.children {
+            [_, Some(child)] | [Some(child), None] => node = child,
+            [None, None] => return node
+        }
+    }
+}
+
+impl<V> RadixMap<V> {
+    pub fn new() -> Self {
+        RadixMap {
+            root: None,
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /* in the order of the keys, a prefix before its extensions */
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            stack: self.root.iter().map(|node| &**node).collect()
+        }
+    }
+
+    /* the entry with the longest key that is a prefix of `key` */
+    pub fn longest_prefix_match(&self, key: &BitString)
+        -> Option<(&BitString, &V)> {
+        self.iter_covering(key).last()
+    }
+
+    /* the most specific route to `addr` */
+    pub fn longest_match_ip(&self, addr: IpAddr) -> Option<(&BitString, &V)> {
+        self.longest_prefix_match(&BitString::host(addr))
+    }
+
+    /* the entries whose keys are prefixes of `key` (the routes covering
+     * it), shortest first */
+    pub fn iter_covering(&self, key: &BitString)
+        -> std::vec::IntoIter<(&BitString, &V)> {
+        let mut res: Vec<(&BitString, &V)> = Vec::new();
+        let mut link: &Link<V> = &self.root;
+
+        while let Some(node) = link {
+            if !key.starts_with(&node.key) {
+                break;
+            }
+
+            if let Some(value) = node.value.as_ref() {
+                res.push((&node.key, value));
+            }
+
+            if node.key.len == key.len {
+                break;
+            }
+
+            link = &node.children[key.bit(node.key.len) as usize];
+        }
+
+        res.into_iter()
+    }
+
+    /* the entries whose keys start with `key` (the prefixes it covers), in
+     * order */
+    pub fn iter_covered(&self, key: &BitString) -> Iter<'_, V> {
+        let mut link: &Link<V> = &self.root;
+
+        while let Some(node) = link {
+            if node.key.starts_with(key) {
+                return Iter {
+                    stack: vec![node]
+                };
+            }
+
+            if !key.starts_with(&node.key) {
+                break;
+            }
+
+            link = &node.children[key.bit(node.key.len) as usize];
+        }
+
+        Iter {
+            stack: Vec::new()
+        }
+    }
+
+    fn find(&self, key: &BitString) -> Option<&Node<V>> {
+        let mut node: &Node<V> = self.root.as_ref()?;
+
+        loop {
+            if !key.starts_with(&node.key) {
+                return None;
+            }
+
+            if node.key.len == key.len {
+                return Some(node);
+            }
+
+            node = node.children[key.bit(node.key.len) as usize].as_ref()?;
+        }
+    }
+
+    fn find_mut(&mut self, key: &BitString) -> Option<&mut Node<V>> {
+        let mut node: &mut Node<V> = self.root.as_mut()?;
+
+        loop {
+            if !key.starts_with(&node.key) {
+                return None;
+            }
+
+            if node.key.len == key.len {
+                return Some(node);
+            }
+
+            node = node.children[key.bit(node.key.len) as usize].as_mut()?;
+        }
+    }
+
+    /* the stack an `Iter` would hold just before yielding the least key
+     * above `key` (or, if `inclusive`, at least `key`). a node's key sorts
+     * before everything under it and its 0 side before its 1 side, so the
+     * walk only needs to compare keys, and whatever it skips on the 1 side
+     * is left on the stack to carry on with */
+    fn seek<Q>(&self, key: &Q, inclusive: bool) -> Vec<&Node<V>> where
+        BitString: Borrow<Q>, Q: Ord + ?Sized {
+        let mut res: Vec<&Node<V>> = Vec::new();
+        let mut link: &Link<V> = &self.root;
+
+        while let Some(node) = link {
+            let k: &Q = node.key.borrow();
+
+            match k.cmp(key) {
+                Ordering::Greater => {
+                    res.push(node);
+                    break;
+                },
+                Ordering::Equal if inclusive => {
+                    res.push(node);
+                    break;
+                },
+                Ordering::Equal => {
+                    res.extend(node.children.iter().rev().flatten()
+                        .map(|child| &**child));
+                    break;
+                },
+                Ordering::Less => {}
+            }
+
+            let [left, right] = &node.children;
+            let past_left: bool = match right {
+                Some(right) => {
+                    let k: &Q = right.key.borrow();
+                    k <= key
+                },
+                None => false
+            };
+
+            if past_left {
+                link = right;
+            } else {
+                res.extend(right.iter().map(|child| &**child));
+                link = left;
+            }
+        }
+
+        res
+    }
+
+    /* the node holding the greatest key below `key` (or, if `inclusive`, at
+     * most `key`) */
+    fn seek_below<Q>(&self, key: &Q, inclusive: bool) -> Option<&Node<V>>
+        where BitString: Borrow<Q>, Q: Ord + ?Sized {
+        /* the best node so far, and whether the answer is the last key
+         * under it rather than its own */
+        let mut res: Option<(&Node<V>, bool)> = None;
+        let mut link: &Link<V> = &self.root;
+
+        while let Some(node) = link {
+            let k: &Q = node.key.borrow();
+
+            match k.cmp(key) {
+                Ordering::Greater => break,
+                Ordering::Equal => {
+                    if inclusive && node.value.is_some() {
+                        res = Some((node, false));
+                    }
+
+                    break;
+                },
+                Ordering::Less => {}
+            }
+
+            let [left, right] = &node.children;
+            let past_left: bool = match right {
+                Some(right) => {
+                    let k: &Q = right.key.borrow();
+                    k <= key
+                },
+                None => false
+            };
+
+            if past_left {
+                if let Some(left) = left {
+                    res = Some((left, true));
+                } else if node.value.is_some() {
+                    res = Some((node, false));
+                }
+
+                link = right;
+            } else {
+                if node.value.is_some() {
+                    res = Some((node, false));
+                }
+
+                link = left;
+            }
+        }
+
+        res.map(|(node, under)| if under { last(node) } else { node })
+    }
+
+    fn entry(node: Option<&Node<V>>) -> Result<(&BitString, &V), MapError> {
+        match node.and_then(|node| Some((&node.key, node.value.as_ref()?))) {
+            Some(entry) => Ok(entry),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    /* `false` when `key` already ended at a node with a value, which is
+     * then replaced */
+    fn insert(&mut self, key: BitString, value: V) -> bool {
+        let mut link: &mut Link<V> = &mut self.root;
+
+        /* walks down while `key` extends the node's key, then either lands
+         * on it, hangs it off an empty slot, or forks where they differ */
+        while let Some((common, len)) = link.as_ref()
+            .map(|node| (node.key.common_prefix_len(&key), node.key.len)) {
+            if common == len && common < key.len {
+                match link {
+                    Some(node) => {
+                        link = &mut node.children[key.bit(common) as usize];
+                        continue;
+                    },
+                    None => break
+                }
+            }
+
+            if common == len {
+                let node: &mut Box<Node<V>> = match link.as_mut() {
+                    Some(node) => node,
+                    None => break
+                };
+                let new: bool = node.value.is_none();
+
+                node.value = Some(value);
+                self.len += new as usize;
+                return new;
+            }
+
+            let old: Link<V> = link.take();
+            let side: usize = match old.as_ref() {
+                Some(old) => old.key.bit(common) as usize,
+                None => 0
+            };
+
+            let mut fork: Box<Node<V>> = Box::new(Node {
+                key: key.prefix(common),
+                value: None,
+                children: [None, None]
+            });
+
+            fork.children[side] = old;
+
+            if common == key.len {
+                fork.value = Some(value);
+            } else {
+                fork.children[1 - side] = Some(Box::new(Node {
+                    key,
+                    value: Some(value),
+                    children: [None, None]
+                }));
+            }
+
+            *link = Some(fork);
+            self.len += 1;
+            return true;
+        }
+
+        *link = Some(Box::new(Node {
+            key,
+            value: Some(value),
+            children: [None, None]
+        }));
+
+        self.len += 1;
+        true
+    }
+
+    fn take(&mut self, key: &BitString) -> Option<V> {
+        /* the branch taken below each node on the way to `key` */
+        let mut path: Vec<usize> = Vec::new();
+        let mut node: &Node<V> = self.root.as_ref()?;
+
+        while node.key.len < key.len {
+            if !key.starts_with(&node.key) {
+                return None;
+            }
+
+            path.push(key.bit(node.key.len) as usize);
+            node = node.children[*path.last()?].as_ref()?;
+        }
+
+        if node.key != *key {
+            return None;
+        }
+
+        let mut parent: &mut Link<V> = &mut self.root;
+        let last: Option<usize> = path.pop();
+
+        for side in path {
+            parent = &mut parent.as_mut()?.children[side];
+        }
+
+        let res: Option<V> = match last {
+            Some(side) => {
+                let target: &mut Link<V> = &mut parent.as_mut()?.children[side];
+                let res: Option<V> = target.as_mut()?.value.take();
+
+                collapse(target);
+                collapse(parent);
+                res
+            },
+            None => {
+                let res: Option<V> = parent.as_mut()?.value.take();
+
+                collapse(parent);
+                res
+            }
+        };
+
+        if res.is_some() {
+            self.len -= 1;
+        }
+
+        res
+    }
+}
+
+impl<V> Default for RadixMap<V> {
+    fn default() -> Self {
+        RadixMap::new()
+    }
+}
+
+impl<V> PartialEq for RadixMap<V> where V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<V> Eq for RadixMap<V> where V: Eq {}
+
+impl<V> IntoIterator for RadixMap<V> {
+    type Item = (BitString, V);
+    type IntoIter = IntoIter<V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        IntoIter {
+            stack: self.root.take().into_iter().collect()
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a RadixMap<V> {
+    type Item = (&'a BitString, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<V> FromIterator<(BitString, V)> for RadixMap<V> {
+    fn from_iter<I: IntoIterator<Item = (BitString, V)>>(iter: I) -> Self {
+        let mut res: RadixMap<V> = RadixMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<V> fmt::Display for RadixMap<V> where V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<V> Map<BitString, V> for RadixMap<V> {
+    type Iter<'a> = Iter<'a, V> where V: 'a;
+    type IterMut<'a> = IterMut<'a, V> where V: 'a;
+
+    fn new() -> Self {
+        RadixMap::new()
+    }
+
+    fn set(&mut self, key: BitString, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        self.root = None;
+        self.len = 0;
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        RadixMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            stack: self.root.iter_mut().map(|node| &mut **node).collect()
+        }
+    }
+}
+
+impl<V, Q> MapLookup<BitString, V, Q> for RadixMap<V> where
+    Q: Borrow<BitString> + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.find(key.borrow()).and_then(|node| node.value.as_ref())
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        self.find_mut(key.borrow()).and_then(|node| node.value.as_mut())
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        self.take(key.borrow())
+    }
+}
+
+impl<V> OrderedMap<BitString, V> for RadixMap<V> {
+    type Range<'a> = Range<'a, V> where V: 'a;
+
+    fn first(&self) -> Result<(&BitString, &V), MapError> {
+        self.iter().next().ok_or(MapError::KeyNotFound)
+    }
+
+    fn last(&self) -> Result<(&BitString, &V), MapError> {
+        RadixMap::entry(self.root.as_deref().map(last))
+    }
+
+    fn floor<Q>(&self, key: &Q) -> Result<(&BitString, &V), MapError> where
+        BitString: Borrow<Q>, Q: Ord + ?Sized {
+        RadixMap::entry(self.seek_below(key, true))
+    }
+
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&BitString, &V), MapError> where
+        BitString: Borrow<Q>, Q: Ord + ?Sized {
+        Iter { stack: self.seek(key, true) }.next()
+            .ok_or(MapError::KeyNotFound)
+    }
+
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&BitString, &V), MapError>
+        where BitString: Borrow<Q>, Q: Ord + ?Sized {
+        RadixMap::entry(self.seek_below(key, false))
+    }
+
+    fn successor<Q>(&self, key: &Q) -> Result<(&BitString, &V), MapError>
+        where BitString: Borrow<Q>, Q: Ord + ?Sized {
+        Iter { stack: self.seek(key, false) }.next()
+            .ok_or(MapError::KeyNotFound)
+    }
+
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where
+        BitString: Borrow<Q>, Q: Ord + ?Sized, R: RangeBounds<Q> {
+        let stack: Vec<&Node<V>> = match range.start_bound() {
+            Bound::Included(start) => self.seek(start, true),
+            Bound::Excluded(start) => self.seek(start, false),
+            Bound::Unbounded => self.iter().stack
+        };
+        let end: Option<&Node<V>> = match range.end_bound() {
+            Bound::Included(end) => self.seek_below(end, true),
+            Bound::Excluded(end) => self.seek_below(end, false),
+            Bound::Unbounded => self.root.as_deref().map(last)
+        };
+
+        Range {
+            iter: Iter { stack },
+            last: end.map(|node| &node.key)
+        }
+    }
+
+    fn pop_first(&mut self) -> Result<(BitString, V), MapError> {
+        let key: BitString = self.first()?.0.clone();
+        let value: Option<V> = self.take(&key);
+
+        value.map(|value| (key, value)).ok_or(MapError::KeyNotFound)
+    }
+
+    fn pop_last(&mut self) -> Result<(BitString, V), MapError> {
+        let key: BitString = OrderedMap::last(self)?.0.clone();
+        let value: Option<V> = self.take(&key);
+
+        value.map(|value| (key, value)).ok_or(MapError::KeyNotFound)
+    }
+
+    /* the moved keys are taken out one at a time, so this costs their
+     * total length rather than the depth of a single cut */
+    fn split_off<Q>(&mut self, key: &Q) -> Self where BitString: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let moved: Vec<BitString> = self
+            .range((Bound::Included(key), Bound::Unbounded))
+            .map(|(k, _)| k.clone())
+            .collect();
+        let mut res: RadixMap<V> = RadixMap::new();
+
+        for key in moved {
+            if let Some(value) = self.take(&key) {
+                res.insert(key, value);
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use crate::rng::XorShiftRng;
+
+    fn cidr(text: &str) -> BitString {
+        let (addr, len) = text.split_once('/').unwrap_or((text, ""));
+        let addr: IpAddr = addr.parse().unwrap_or(IpAddr::V4(0.into()));
+
+        match len.parse() {
+            Ok(len) => BitString::ip(addr, len).unwrap_or_default(),
+            Err(_) => BitString::host(addr)
+        }
+    }
+
+    fn bits(text: &str) -> BitString {
+        let mut res: BitString = BitString::new();
+
+        for c in text.chars() {
+            res.push(c == '1');
+        }
+
+        res
+    }
+
+    /* every valueless node forks; returns the number of nodes */
+    fn check<V>(link: &Link<V>) -> usize {
+        match link {
+            Some(node) => {
+                if node.value.is_none() {
+                    assert!(node.children.iter().all(|c| c.is_some()));
+                }
+
+                for (side, child) in node.children.iter().enumerate() {
+                    if let Some(child) = child {
+                        assert!(child.key.starts_with(&node.key));
+                        assert!(child.key.len > node.key.len);
+                        assert_eq!(child.key.bit(node.key.len), side == 1);
+                    }
+                }
+
+                1 + check(&node.children[0]) + check(&node.children[1])
+            },
+            None => 0
+        }
+    }
+
+    #[test]
+    fn test_bit_string_normal() {
+        let actual_bits: BitString = BitString::from_bits(&[0xff, 0xff], 12)
+            .unwrap_or_default();
+
+        assert_eq!(actual_bits, bits("111111111111"));
+        assert_eq!(actual_bits.common_prefix_len(&bits("1111110")), 6);
+        assert!(actual_bits.starts_with(&bits("111")));
+        assert!(!bits("111").starts_with(&actual_bits));
+        assert!(bits("0") < bits("00"));
+        assert!(bits("01") < bits("1"));
+        assert_eq!(BitString::from_bits(&[0xff], 9), None);
+        assert_eq!(bits("0110").to_string(), "0110");
+
+        let actual_set: std::collections::HashSet<BitString> =
+            vec![bits("01"), BitString::from_bits(&[0x7f], 2)
+                 .unwrap_or_default()].into_iter().collect();
+
+        assert_eq!(actual_set.len(), 1);
+    }
+
+    #[test]
+    fn test_ip_normal_round_trip() {
+        let v4: IpAddr = "10.1.2.3".parse().unwrap_or(IpAddr::V4(0.into()));
+        let v6: IpAddr = "2001:db8::1".parse()
+            .unwrap_or(IpAddr::V4(0.into()));
+
+        assert_eq!(BitString::ip(v4, 16).and_then(|b| b.to_ip()),
+                   Some(("10.1.0.0".parse().unwrap_or(v4), 16)));
+        assert_eq!(BitString::host(v6).to_ip(), Some((v6, 128)));
+        assert_eq!(BitString::ip(v4, 33), None);
+        assert_eq!(BitString::host(v4).len(), 33);
+    }
+
+    #[test]
+    fn test_longest_match_ip_normal() -> Result<(), MapError> {
+        let actual_routes: RadixMap<&str> = vec![
+            (cidr("0.0.0.0/0"), "default"),
+            (cidr("10.0.0.0/8"), "corp"),
+            (cidr("10.1.0.0/16"), "lab"),
+            (cidr("10.1.2.0/24"), "rack"),
+            (cidr("2001:db8::/32"), "v6")
+        ].into_iter().collect();
+
+        let route = |addr: &str| -> Option<&str> {
+            let addr: IpAddr = addr.parse().ok()?;
+            actual_routes.longest_match_ip(addr).map(|(_, v)| *v)
+        };
+
+        assert_eq!(route("10.1.2.3"), Some("rack"));
+        assert_eq!(route("10.1.3.3"), Some("lab"));
+        assert_eq!(route("10.200.0.1"), Some("corp"));
+        assert_eq!(route("192.168.0.1"), Some("default"));
+        assert_eq!(route("2001:db8::1"), Some("v6"));
+        assert_eq!(route("2001:db9::1"), None);
+        assert_eq!(check(&actual_routes.root), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_covering_normal() -> Result<(), MapError> {
+        let actual_routes: RadixMap<u64> = vec![
+            (cidr("10.0.0.0/8"), 8),
+            (cidr("10.1.0.0/16"), 16),
+            (cidr("10.1.2.0/24"), 24),
+            (cidr("10.2.0.0/16"), 17),
+            (cidr("11.0.0.0/8"), 9)
+        ].into_iter().collect();
+
+        let covering: Vec<u64> = actual_routes
+            .iter_covering(&cidr("10.1.2.128/25"))
+            .map(|(_, v)| *v)
+            .collect();
+        let covered: Vec<u64> = actual_routes
+            .iter_covered(&cidr("10.0.0.0/8"))
+            .map(|(_, v)| *v)
+            .collect();
+
+        assert_eq!(covering, vec![8, 16, 24]);
+        assert_eq!(covered, vec![8, 16, 24, 17]);
+        assert_eq!(actual_routes.iter_covered(&cidr("10.0.0.0/7")).count(), 5);
+        assert_eq!(actual_routes.iter_covered(&cidr("10.3.0.0/16")).count(),
+                   0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal_compressed() -> Result<(), MapError> {
+        let mut actual_map: RadixMap<u64> = RadixMap::new();
+        let mut expected_keys: Vec<BitString> = Vec::new();
+        let mut rng: XorShiftRng = XorShiftRng::new(6);
+
+        for i in 0..2000 {
+            let bytes: [u8; 8] = rng.next_u64().to_be_bytes();
+            let len: usize = (rng.next_u64() % 65) as usize;
+            let key: BitString = BitString::from_bits(&bytes, len)
+                .unwrap_or_default();
+
+            actual_map.set(key.clone(), i)?;
+            expected_keys.push(key);
+        }
+
+        expected_keys.sort();
+        expected_keys.dedup();
+
+        let actual_keys: Vec<BitString> = actual_map.iter()
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        assert_eq!(actual_keys, expected_keys);
+        assert_eq!(actual_map.size()?, expected_keys.len());
+        assert!(check(&actual_map.root) < 2 * expected_keys.len());
+
+        for key in expected_keys.iter().step_by(2) {
+            actual_map.remove(key)?;
+        }
+
+        assert!(check(&actual_map.root) < 2 * actual_map.len());
+        assert_eq!(actual_map.remove(&expected_keys[0]),
+                   Err(MapError::KeyNotFound));
+        assert!(actual_map.contains_key(&expected_keys[1])?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal_collapses() -> Result<(), MapError> {
+        let mut actual_map: RadixMap<u64> =
+            vec![(bits("0"), 0), (bits("00"), 1), (bits("01"), 2)]
+                .into_iter()
+                .collect();
+
+        assert_eq!(check(&actual_map.root), 3);
+
+        actual_map.remove(&bits("0"))?;
+
+        assert_eq!(check(&actual_map.root), 3);
+        assert_eq!(actual_map.remove(&bits("0")), Err(MapError::KeyNotFound));
+
+        actual_map.remove(&bits("01"))?;
+
+        assert_eq!(check(&actual_map.root), 1);
+        assert_eq!(actual_map.get(&bits("00"))?, &1);
+        assert_eq!(actual_map.remove(&bits("000")),
+                   Err(MapError::KeyNotFound));
+
+        actual_map.remove(&bits("00"))?;
+
+        assert!(actual_map.root.is_none());
+        assert!(actual_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal_nested_chain() -> Result<(), MapError> {
+        let mut actual_map: RadixMap<usize> = RadixMap::new();
+        let mut key: BitString = BitString::new();
+
+        for i in 0..3000 {
+            key.push(i % 3 == 0);
+            actual_map.set(key.clone(), i)?;
+        }
+
+        assert_eq!(actual_map.longest_prefix_match(&key).map(|(_, v)| *v),
+                   Some(2999));
+        assert_eq!(actual_map.iter_covering(&key).len(), 3000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_floor_normal_matches_btree() -> Result<(), MapError> {
+        let mut rng: XorShiftRng = XorShiftRng::new(11);
+        let mut random_key = || -> BitString {
+            let bytes: [u8; 8] = rng.next_u64().to_be_bytes();
+            let len: usize = (rng.next_u64() % 12) as usize;
+
+            BitString::from_bits(&bytes, len).unwrap_or_default()
+        };
+        let expected_map: BTreeMap<BitString, u64> =
+            (0..200).map(|i| (random_key(), i)).collect();
+        let actual_map: RadixMap<u64> = expected_map.iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+
+        assert_eq!(actual_map.first().ok(), expected_map.iter().next());
+        assert_eq!(actual_map.last().ok(), expected_map.iter().next_back());
+
+        for _ in 0..500 {
+            let key: BitString = random_key();
+
+            assert_eq!(actual_map.floor(&key).ok(),
+                       expected_map.range(..=&key).next_back());
+            assert_eq!(actual_map.predecessor(&key).ok(),
+                       expected_map.range(..&key).next_back());
+            assert_eq!(actual_map.ceiling(&key).ok(),
+                       expected_map.range(&key..).next());
+            assert_eq!(actual_map.successor(&key).ok(),
+                       expected_map.range((Bound::Excluded(&key),
+                                           Bound::Unbounded)).next());
+        }
+
+        for _ in 0..200 {
+            let (a, b) = (random_key(), random_key());
+            let (start, end) = if a <= b { (a, b) } else { (b, a) };
+
+            assert!(actual_map.range(&start..&end)
+                    .eq(expected_map.range(&start..&end)));
+            assert!(actual_map.range((Bound::Excluded(&start),
+                                      Bound::Included(&end)))
+                    .eq(expected_map.range((Bound::Excluded(&start),
+                                            Bound::Included(&end)))));
+            assert_eq!(actual_map.range(&end..&start).count(), 0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), MapError> {
+        let mut actual_map: RadixMap<u64> =
+            vec![(bits("0"), 0), (bits("00"), 1), (bits("01"), 2),
+                 (bits("1"), 3), (bits("110"), 4)]
+                .into_iter()
+                .collect();
+
+        let mut actual_split: RadixMap<u64> =
+            actual_map.split_off(&bits("01"));
+
+        assert_eq!(actual_map.iter().map(|(_, v)| *v).collect::<Vec<u64>>(),
+                   vec![0, 1]);
+        assert_eq!(actual_split.iter().map(|(_, v)| *v).collect::<Vec<u64>>(),
+                   vec![2, 3, 4]);
+        assert!(check(&actual_map.root) < 2 * actual_map.len());
+        assert_eq!(actual_split.pop_first()?, (bits("01"), 2));
+        assert_eq!(actual_split.pop_last()?, (bits("110"), 4));
+        assert_eq!(actual_split.pop_last()?, (bits("1"), 3));
+        assert_eq!(actual_split.pop_last(), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), MapError> {
+        let mut actual_map: Box<dyn crate::map::DynMap<BitString, u64>> =
+            Box::new(RadixMap::new());
+
+        actual_map.set(bits("1"), 1)?;
+        actual_map.set(bits("0"), 0)?;
+
+        for (_, value) in actual_map.iter_mut() {
+            *value += 10;
+        }
+
+        assert_eq!(actual_map.get(&bits("0"))?, &10);
+        assert_eq!(actual_map.iter().map(|(_, v)| *v).collect::<Vec<u64>>(),
+                   vec![10, 11]);
+
+        Ok(())
+    }
+}
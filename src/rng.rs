@@ -0,0 +1,58 @@
+/* a small deterministic xorshift64* generator for the randomised containers,
+ * so that their shape (and hence their tests) can be reproduced from a
+ * seed; not suitable for anything needing unpredictability */
+#[derive(Clone, Debug, PartialEq)]
+pub struct XorShiftRng {
+    state: u64
+}
+
+const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl XorShiftRng {
+    /* xorshift has a fixed point at zero, so that seed is remapped */
+    pub fn new(seed: u64) -> Self {
+        XorShiftRng {
+            state: if seed == 0 { DEFAULT_SEED } else { seed }
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+impl Default for XorShiftRng {
+    fn default() -> Self {
+        XorShiftRng::new(DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_u64_normal_reproducible() {
+        let mut a: XorShiftRng = XorShiftRng::new(42);
+        let mut b: XorShiftRng = XorShiftRng::new(42);
+        let mut c: XorShiftRng = XorShiftRng::new(43);
+
+        let actual_a: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        let actual_b: Vec<u64> = (0..8).map(|_| b.next_u64()).collect();
+        let actual_c: Vec<u64> = (0..8).map(|_| c.next_u64()).collect();
+
+        assert_eq!(actual_a, actual_b);
+        assert_ne!(actual_a, actual_c);
+    }
+
+    #[test]
+    fn test_new_normal_zero_seed() {
+        let mut actual_rng: XorShiftRng = XorShiftRng::new(0);
+
+        assert_ne!(actual_rng.next_u64(), 0);
+        assert_eq!(XorShiftRng::new(0), XorShiftRng::default());
+    }
+}
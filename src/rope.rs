@@ -0,0 +1,1210 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::Display;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut, Range};
+use std::slice;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::list::{List, ListError};
+
+/* leaves hold chars rather than UTF-8 so that the rope can hand out `&char`
+ * and `&mut char` through its `List<char>` view */
+const MAX_LEAF: usize = 1024;
+
+/* a branch's newline count; atomic so that the counts under a rope whose
+ * chars were rewritten in bulk can be put right from `&self` */
+#[derive(Debug)]
+struct Count(AtomicUsize);
+
+impl Count {
+    fn new(n: usize) -> Self {
+        Count(AtomicUsize::new(n))
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, n: usize) {
+        self.0.store(n, Ordering::Relaxed);
+    }
+}
+
+impl Clone for Count {
+    fn clone(&self) -> Self {
+        Count::new(self.get())
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Leaf(Vec<char>),
+    Branch {
+        left: Arc<Node>,
+        right: Arc<Node>,
+        len: usize,
+        newlines: Count,
+        height: usize
+    }
+}
+
+impl Node {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(chars) => chars.len(),
+            Node::Branch { len, .. } => *len
+        }
+    }
+
+    fn newlines(&self) -> usize {
+        match self {
+            Node::Leaf(chars) => chars.iter().filter(|c| **c == '\n').count(),
+            Node::Branch { newlines, .. } => newlines.get()
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 0,
+            Node::Branch { height, .. } => *height
+        }
+    }
+}
+
+fn leaf(chars: Vec<char>) -> Arc<Node> {
+    Arc::new(Node::Leaf(chars))
+}
+
+fn branch(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    let len: usize = left.len() + right.len();
+    let newlines: usize = left.newlines() + right.newlines();
+    let height: usize = left.height().max(right.height()) + 1;
+
+    Arc::new(Node::Branch {
+        left,
+        right,
+        len,
+        newlines: Count::new(newlines),
+        height
+    })
+}
+
+fn children(node: &Arc<Node>) -> Option<(Arc<Node>, Arc<Node>)> {
+    match &**node {
+        Node::Leaf(_) => None,
+        Node::Branch { left, right, .. } => Some((left.clone(), right.clone()))
+    }
+}
+
+/* builds a perfectly balanced tree of full leaves */
+fn build(chars: &[char]) -> Arc<Node> {
+    if chars.len() <= MAX_LEAF {
+        return leaf(chars.to_vec());
+    }
+
+    let num_leaves: usize = chars.len().div_ceil(MAX_LEAF);
+    let mid: usize = (num_leaves / 2) * MAX_LEAF;
+
+    branch(build(&chars[..mid]), build(&chars[mid..]))
+}
+
+/* joins two trees whose heights differ by at most one, merging neighbouring
+ * leaves when they are small enough to share a chunk */
+fn pair(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    if let (Node::Leaf(a), Node::Leaf(b)) = (&*left, &*right) {
+        if a.len() + b.len() <= MAX_LEAF {
+            let mut chars: Vec<char> = Vec::with_capacity(a.len() + b.len());
+            chars.extend_from_slice(a);
+            chars.extend_from_slice(b);
+            return leaf(chars);
+        }
+    }
+
+    branch(left, right)
+}
+
+/* restores the AVL invariant for a pair of subtrees whose heights differ by
+ * at most two */
+fn balance(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    if right.height() > left.height() + 1 {
+        if let Some((rl, rr)) = children(&right) {
+            if rl.height() > rr.height() {
+                if let Some((rll, rlr)) = children(&rl) {
+                    return branch(branch(left, rll), branch(rlr, rr));
+                }
+            }
+
+            return branch(branch(left, rl), rr);
+        }
+    } else if left.height() > right.height() + 1 {
+        if let Some((ll, lr)) = children(&left) {
+            if lr.height() > ll.height() {
+                if let Some((lrl, lrr)) = children(&lr) {
+                    return branch(branch(ll, lrl), branch(lrr, right));
+                }
+            }
+
+            return branch(ll, branch(lr, right));
+        }
+    }
+
+    branch(left, right)
+}
+
+/* concatenation in O(|h(left) - h(right)|) by descending the spine of the
+ * taller tree until the heights meet */
+fn join(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    if left.len() == 0 {
+        return right;
+    }
+
+    if right.len() == 0 {
+        return left;
+    }
+
+    if left.height() > right.height() + 1 {
+        if let Some((l, c)) = children(&left) {
+            let joined: Arc<Node> = join(c, right);
+            return balance(l, joined);
+        }
+    } else if right.height() > left.height() + 1 {
+        if let Some((c, r)) = children(&right) {
+            let joined: Arc<Node> = join(left, c);
+            return balance(joined, r);
+        }
+    }
+
+    pair(left, right)
+}
+
+fn split(node: &Arc<Node>, pos: usize) -> (Arc<Node>, Arc<Node>) {
+    match &**node {
+        Node::Leaf(chars) => {
+            if pos == 0 {
+                (leaf(Vec::new()), node.clone())
+            } else if pos >= chars.len() {
+                (node.clone(), leaf(Vec::new()))
+            } else {
+                (leaf(chars[..pos].to_vec()), leaf(chars[pos..].to_vec()))
+            }
+        },
+        Node::Branch { left, right, .. } => {
+            let n: usize = left.len();
+
+            if pos < n {
+                let (a, b) = split(left, pos);
+                (a, join(b, right.clone()))
+            } else if pos == n {
+                (left.clone(), right.clone())
+            } else {
+                let (a, b) = split(right, pos - n);
+                (join(left.clone(), a), b)
+            }
+        }
+    }
+}
+
+fn get(node: &Node, pos: usize) -> Option<&char> {
+    match node {
+        Node::Leaf(chars) => chars.get(pos),
+        Node::Branch { left, right, .. } => {
+            if pos < left.len() {
+                get(left, pos)
+            } else {
+                get(right, pos - left.len())
+            }
+        }
+    }
+}
+
+fn get_mut(node: &mut Arc<Node>, pos: usize) -> Option<&mut char> {
+    match Arc::make_mut(node) {
+        Node::Leaf(chars) => chars.get_mut(pos),
+        Node::Branch { left, right, .. } => {
+            if pos < left.len() {
+                get_mut(left, pos)
+            } else {
+                let n: usize = left.len();
+                get_mut(right, pos - n)
+            }
+        }
+    }
+}
+
+/* a char written through `&mut char` whose branch counts are not yet
+ * fixed: its position and how many newlines it added (or took away) */
+type Pending = Option<(usize, isize)>;
+
+/* newlines under `node`, which starts at `offset`; leaves always count
+ * their chars, so only a branch over the pending char is off */
+fn newlines_in(node: &Node, offset: usize, pending: Pending) -> usize {
+    match (node, pending) {
+        (Node::Branch { len, newlines, .. }, Some((pos, delta)))
+            if pos >= offset && pos < offset + len =>
+            (newlines.get() as isize + delta) as usize,
+        _ => node.newlines()
+    }
+}
+
+/* position of the `k`th newline (counting from one) */
+fn nth_newline(node: &Node, offset: usize, k: usize, pending: Pending)
+    -> Option<usize> {
+    match node {
+        Node::Leaf(chars) => chars.iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '\n')
+            .nth(k - 1)
+            .map(|(i, _)| i),
+        Node::Branch { left, right, .. } => {
+            let in_left: usize = newlines_in(left, offset, pending);
+
+            if in_left >= k {
+                nth_newline(left, offset, k, pending)
+            } else {
+                nth_newline(right, offset + left.len(), k - in_left, pending)
+                    .map(|i| i + left.len())
+            }
+        }
+    }
+}
+
+fn newlines_before(node: &Node, offset: usize, pos: usize,
+                   pending: Pending) -> usize {
+    match node {
+        Node::Leaf(chars) =>
+            chars[..pos].iter().filter(|c| **c == '\n').count(),
+        Node::Branch { left, right, .. } => {
+            if pos <= left.len() {
+                newlines_before(left, offset, pos, pending)
+            } else {
+                newlines_in(left, offset, pending) +
+                    newlines_before(right, offset + left.len(),
+                                    pos - left.len(), pending)
+            }
+        }
+    }
+}
+
+/* brings the counts on the path down to `pos` back in line with its leaf */
+fn recount_path(node: &mut Arc<Node>, pos: usize) {
+    if let Node::Branch { left, right, newlines, .. } = Arc::make_mut(node) {
+        if pos < left.len() {
+            recount_path(left, pos);
+        } else {
+            let n: usize = left.len();
+            recount_path(right, pos - n);
+        }
+
+        newlines.set(left.newlines() + right.newlines());
+    }
+}
+
+/* only ever stores the true counts, so it is harmless for two threads to
+ * run it over the same nodes at once */
+fn recount(node: &Node) {
+    if let Node::Branch { left, right, newlines, .. } = node {
+        recount(left);
+        recount(right);
+
+        newlines.set(left.newlines() + right.newlines());
+    }
+}
+
+fn leaves<'a>(node: &'a Node, res: &mut Vec<&'a [char]>) {
+    match node {
+        Node::Leaf(chars) => res.push(chars.as_slice()),
+        Node::Branch { left, right, .. } => {
+            leaves(left, res);
+            leaves(right, res);
+        }
+    }
+}
+
+fn leaves_mut<'a>(node: &'a mut Arc<Node>, res: &mut Vec<&'a mut [char]>) {
+    match Arc::make_mut(node) {
+        Node::Leaf(chars) => res.push(chars.as_mut_slice()),
+        Node::Branch { left, right, .. } => {
+            leaves_mut(left, res);
+            leaves_mut(right, res);
+        }
+    }
+}
+
+/* a rope: a height-balanced tree of chunks supporting O(log n) edits by
+ * char index; nodes are shared between clones, slices and splits */
+#[derive(Debug)]
+pub struct Rope {
+    root: Arc<Node>,
+    /* the last char handed out by `get_mut`, with whether it was a newline;
+     * the next `&mut self` call recounts the path to it */
+    touched: Option<(usize, bool)>,
+    /* set by `chars_mut`, after which any count may be off; the first call
+     * that needs the counts recounts the whole tree, once */
+    lines_dirty: AtomicBool
+}
+
+pub struct Iter<'a> {
+    leaves: Vec<&'a [char]>,
+    front: slice::Iter<'a, char>,
+    back: slice::Iter<'a, char>,
+    next_leaf: usize,
+    remaining: usize
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(c) = self.front.next() {
+                self.remaining -= 1;
+                return Some(c);
+            }
+
+            if self.next_leaf < self.leaves.len() {
+                self.front = self.leaves[self.next_leaf].iter();
+                self.next_leaf += 1;
+            } else {
+                self.remaining -= 1;
+                return self.back.next();
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(c) = self.back.next_back() {
+                self.remaining -= 1;
+                return Some(c);
+            }
+
+            if self.leaves.len() > self.next_leaf {
+                self.back = self.leaves.pop().unwrap_or(&[]).iter();
+            } else {
+                self.remaining -= 1;
+                return self.front.next_back();
+            }
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+pub struct IterMut<'a> {
+    leaves: std::vec::IntoIter<&'a mut [char]>,
+    front: slice::IterMut<'a, char>,
+    back: slice::IterMut<'a, char>,
+    remaining: usize
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = &'a mut char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(c) = self.front.next() {
+                self.remaining -= 1;
+                return Some(c);
+            }
+
+            match self.leaves.next() {
+                Some(chars) => self.front = chars.iter_mut(),
+                None => {
+                    self.remaining -= 1;
+                    return self.back.next();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for IterMut<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            if let Some(c) = self.back.next_back() {
+                self.remaining -= 1;
+                return Some(c);
+            }
+
+            match self.leaves.next_back() {
+                Some(chars) => self.back = chars.iter_mut(),
+                None => {
+                    self.remaining -= 1;
+                    return self.front.next_back();
+                }
+            }
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for IterMut<'a> {}
+
+impl Rope {
+    pub fn new() -> Self {
+        Rope {
+            root: leaf(Vec::new()),
+            touched: None,
+            lines_dirty: AtomicBool::new(false)
+        }
+    }
+
+    pub fn len_chars(&self) -> usize {
+        self.root.len()
+    }
+
+    pub fn len_lines(&self) -> usize {
+        self.settle();
+        newlines_in(&self.root, 0, self.pending()) + 1
+    }
+
+    pub fn chars(&self) -> Iter<'_> {
+        let mut chunks: Vec<&[char]> = Vec::new();
+        leaves(&self.root, &mut chunks);
+
+        Iter {
+            leaves: chunks,
+            front: [].iter(),
+            back: [].iter(),
+            next_leaf: 0,
+            remaining: self.root.len()
+        }
+    }
+
+    pub fn chars_mut(&mut self) -> IterMut<'_> {
+        let remaining: usize = self.root.len();
+        let mut leaves: Vec<&mut [char]> = Vec::new();
+
+        self.touched = None;
+        *self.lines_dirty.get_mut() = true;
+        leaves_mut(&mut self.root, &mut leaves);
+
+        IterMut {
+            leaves: leaves.into_iter(),
+            front: [].iter_mut(),
+            back: [].iter_mut(),
+            remaining
+        }
+    }
+
+    pub fn insert_str(&mut self, pos: usize, text: &str) ->
+        Result<(), ListError> {
+        if pos > self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        let chars: Vec<char> = text.chars().collect();
+        let (left, right) = split(&self.root, pos);
+
+        self.root = join(join(left, build(&chars)), right);
+        Ok(())
+    }
+
+    pub fn remove_range(&mut self, range: Range<usize>) ->
+        Result<(), ListError> {
+        if range.start > range.end || range.end > self.root.len() {
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        let (left, rest) = split(&self.root, range.start);
+        let (_, right) = split(&rest, range.end - range.start);
+
+        self.root = join(left, right);
+        Ok(())
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Result<Rope, ListError> {
+        if range.start > range.end || range.end > self.root.len() {
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.settle();
+
+        let (_, rest) = split(&self.root, range.start);
+        let (middle, _) = split(&rest, range.end - range.start);
+
+        let mut res: Rope = Rope {
+            root: middle,
+            touched: None,
+            lines_dirty: AtomicBool::new(false)
+        };
+
+        /* splitting rebuilds only some of the branches over the touched
+         * char, so the slice's counts are fixed rather than tracked */
+        if let Some((pos, _)) = self.touched {
+            if range.contains(&pos) {
+                recount_path(&mut res.root, pos - range.start);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /* leaves `[0, pos)` in `self` and returns the rest */
+    pub fn split_off(&mut self, pos: usize) -> Result<Rope, ListError> {
+        if pos > self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        let (left, right) = split(&self.root, pos);
+        self.root = left;
+
+        Ok(Rope {
+            root: right,
+            touched: None,
+            lines_dirty: AtomicBool::new(false)
+        })
+    }
+
+    pub fn concat(&mut self, mut other: Rope) {
+        self.fix_lines();
+        other.fix_lines();
+
+        self.root = join(self.root.clone(), other.root);
+    }
+
+    /* index of the first char of line `line` (lines are zero-indexed and
+     * separated by '\n') */
+    pub fn line_to_char(&self, line: usize) -> Result<usize, ListError> {
+        if line >= self.len_lines() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        if line == 0 {
+            return Ok(0);
+        }
+
+        match nth_newline(&self.root, 0, line, self.pending()) {
+            Some(i) => Ok(i + 1),
+            None => Err(ListError::OutOfBounds)
+        }
+    }
+
+    /* the line containing char `pos`; `pos` may be one past the end */
+    pub fn char_to_line(&self, pos: usize) -> Result<usize, ListError> {
+        if pos > self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.settle();
+        Ok(newlines_before(&self.root, 0, pos, self.pending()))
+    }
+
+    /* line `line`, including its trailing '\n' if it has one */
+    pub fn line(&self, line: usize) -> Result<Rope, ListError> {
+        let start: usize = self.line_to_char(line)?;
+        let end: usize = if line + 1 < self.len_lines() {
+            self.line_to_char(line + 1)?
+        } else {
+            self.root.len()
+        };
+
+        self.slice(start..end)
+    }
+
+    fn pending(&self) -> Pending {
+        let (pos, was) = self.touched?;
+        let now: bool = get(&self.root, pos) == Some(&'\n');
+
+        Some((pos, now as isize - was as isize))
+    }
+
+    /* the flag is cleared only once the counts are in, so a query racing
+     * with the recount either sees it still set and recounts as well, or
+     * sees the finished counts */
+    fn settle(&self) {
+        if self.lines_dirty.load(Ordering::Acquire) {
+            recount(&self.root);
+            self.lines_dirty.store(false, Ordering::Release);
+        }
+    }
+
+    fn fix_lines(&mut self) {
+        if *self.lines_dirty.get_mut() {
+            recount(&self.root);
+            *self.lines_dirty.get_mut() = false;
+        } else if let Some((pos, _)) = self.touched {
+            recount_path(&mut self.root, pos);
+        }
+
+        self.touched = None;
+    }
+}
+
+impl Clone for Rope {
+    fn clone(&self) -> Self {
+        self.settle();
+
+        Rope {
+            root: self.root.clone(),
+            touched: self.touched,
+            lines_dirty: AtomicBool::new(false)
+        }
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Rope::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+
+        Rope {
+            root: build(&chars),
+            touched: None,
+            lines_dirty: AtomicBool::new(false)
+        }
+    }
+}
+
+impl From<String> for Rope {
+    fn from(text: String) -> Self {
+        Rope::from(text.as_str())
+    }
+}
+
+impl FromIterator<char> for Rope {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let chars: Vec<char> = iter.into_iter().collect();
+
+        Rope {
+            root: build(&chars),
+            touched: None,
+            lines_dirty: AtomicBool::new(false)
+        }
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len_chars() != other.len_chars() {
+            return false;
+        }
+
+        self.chars().zip(other.chars()).all(|(a, b)| a == b)
+    }
+}
+
+impl Eq for Rope {}
+
+impl IntoIterator for Rope {
+    type Item = char;
+    type IntoIter = std::vec::IntoIter<char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars().cloned().collect::<Vec<char>>().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Rope {
+    type Item = &'a char;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars()
+    }
+}
+
+impl Index<usize> for Rope {
+    type Output = char;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl IndexMut<usize> for Rope {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.chars() {
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl List<char> for Rope {
+    type Iter<'a> = Iter<'a>;
+    type IterMut<'a> = IterMut<'a>;
+
+    fn new() -> Self {
+        Rope::new()
+    }
+
+    fn with_capacity(_capacity: usize) -> Self {
+        Rope::new()
+    }
+
+    fn get(&self, pos: usize) -> Result<&char, ListError> {
+        if pos >= self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        match get(&self.root, pos) {
+            Some(c) => Ok(c),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut char, ListError> {
+        if pos >= self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        match get_mut(&mut self.root, pos) {
+            Some(c) => {
+                self.touched = Some((pos, *c == '\n'));
+                Ok(c)
+            },
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn set(&mut self, pos: usize, elem: char) -> Result<(), ListError> {
+        if pos >= self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        let old: char = match get_mut(&mut self.root, pos) {
+            Some(c) => std::mem::replace(c, elem),
+            None => return Err(ListError::Impossible)
+        };
+
+        if old == '\n' || elem == '\n' {
+            recount_path(&mut self.root, pos);
+        }
+
+        Ok(())
+    }
+
+    fn insert(&mut self, pos: usize, elem: char) -> Result<(), ListError> {
+        if pos > self.root.len() { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        self.fix_lines();
+
+        let (left, right) = split(&self.root, pos);
+        self.root = join(join(left, leaf(vec![elem])), right);
+
+        Ok(())
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<char, ListError> {
+        let elem: char = *List::get(self, pos)?;
+
+        self.remove_range(pos..pos + 1)?;
+        Ok(elem)
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        Ok(self.root.len())
+    }
+
+    fn append(&mut self, elem: char) -> Result<(), ListError> {
+        let len: usize = self.root.len();
+        List::insert(self, len, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        let tmp_a: char = *List::get(self, a)?;
+        let tmp_b: char = *List::get(self, b)?;
+
+        List::set(self, a, tmp_b)?;
+        List::set(self, b, tmp_a)
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        char: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(self.chars().any(|c| c.borrow() == elem))
+    }
+
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where char: Borrow<Q>, Q: Eq + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+
+        for (i, c) in self.chars().enumerate() {
+            if c.borrow() == elem {
+                res.push(i);
+            }
+        }
+
+        Ok(Some(res))
+    }
+
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        char: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(self.chars().position(|c| c.borrow() == elem))
+    }
+
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        char: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(self.chars().filter(|c| (*c).borrow() == elem).count())
+    }
+
+    fn clear(&mut self) -> Result<(), ListError> {
+        *self = Rope::new();
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.chars()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.chars_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bubblesort::bubblesort;
+    use crate::rng::XorShiftRng;
+
+    fn cmp_leq<T: Eq + Ord>(a: &T, b: &T) -> bool {
+        a <= b
+    }
+
+    /* longer than a single leaf so that the tree has some structure */
+    fn lorem(lines: usize) -> String {
+        let mut text: String = String::new();
+
+        for i in 0..lines {
+            text.push_str(&format!("line {} lorem ipsum dolor sit amet\n", i));
+        }
+
+        text
+    }
+
+    #[test]
+    fn test_from_normal() -> Result<(), ListError> {
+        let text: String = lorem(200);
+        let actual_rope: Rope = Rope::from(text.as_str());
+
+        assert_eq!(actual_rope.to_string(), text);
+        assert_eq!(actual_rope.len_chars(), text.chars().count());
+        assert_eq!(actual_rope.len_lines(), 201);
+        assert!(actual_rope.root.height() > 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_str_normal() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from("hello world");
+
+        actual_rope.insert_str(5, ", cruel")?;
+        actual_rope.insert_str(0, "> ")?;
+        actual_rope.insert_str(actual_rope.len_chars(), "!")?;
+
+        assert_eq!(actual_rope.to_string(), "> hello, cruel world!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_str_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from("abc");
+
+        let actual_res: Result<(), ListError> = actual_rope.insert_str(4, "d");
+        let expected_res: Result<(), ListError> = Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        assert_eq!(actual_rope.to_string(), "abc");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_range_normal() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from("héllo wörld");
+
+        actual_rope.remove_range(1..7)?;
+
+        assert_eq!(actual_rope.to_string(), "hörld");
+        assert_eq!(actual_rope.remove_range(3..9),
+                   Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slice_normal() -> Result<(), ListError> {
+        let text: String = lorem(200);
+        let actual_rope: Rope = Rope::from(text.as_str());
+        let expected_text: String = text.chars().skip(1500).take(2000)
+            .collect();
+
+        assert_eq!(actual_rope.slice(1500..3500)?.to_string(), expected_text);
+        assert_eq!(actual_rope.to_string(), text);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), ListError> {
+        let text: String = lorem(100);
+        let mut actual_rope: Rope = Rope::from(text.as_str());
+
+        let actual_tail: Rope = actual_rope.split_off(1234)?;
+
+        assert_eq!(actual_rope.to_string(), &text[..1234]);
+        assert_eq!(actual_tail.to_string(), &text[1234..]);
+
+        actual_rope.concat(actual_tail);
+
+        assert_eq!(actual_rope.to_string(), text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat_normal_balanced() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::new();
+        let mut expected_text: String = String::new();
+
+        for i in 0..500 {
+            let text: String = lorem(i % 7 + 1);
+
+            actual_rope.concat(Rope::from(text.as_str()));
+            expected_text.push_str(&text);
+        }
+
+        assert_eq!(actual_rope.to_string(), expected_text);
+        assert!(actual_rope.root.height() <= 20);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_to_char_normal() -> Result<(), ListError> {
+        let actual_rope: Rope = Rope::from("ab\ncde\n\nf");
+
+        assert_eq!(actual_rope.len_lines(), 4);
+        assert_eq!(actual_rope.line_to_char(0)?, 0);
+        assert_eq!(actual_rope.line_to_char(1)?, 3);
+        assert_eq!(actual_rope.line_to_char(2)?, 7);
+        assert_eq!(actual_rope.line_to_char(3)?, 8);
+        assert_eq!(actual_rope.line_to_char(4), Err(ListError::OutOfBounds));
+        assert_eq!(actual_rope.line(1)?.to_string(), "cde\n");
+        assert_eq!(actual_rope.line(3)?.to_string(), "f");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_char_to_line_normal() -> Result<(), ListError> {
+        let text: String = lorem(300);
+        let actual_rope: Rope = Rope::from(text.as_str());
+
+        for line in [0, 1, 150, 299] {
+            let start: usize = actual_rope.line_to_char(line)?;
+
+            assert_eq!(actual_rope.char_to_line(start)?, line);
+            assert_eq!(actual_rope.char_to_line(start + 3)?, line);
+        }
+
+        assert_eq!(actual_rope.char_to_line(actual_rope.len_chars())?, 300);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edits_normal_against_string() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::new();
+        let mut expected_chars: Vec<char> = Vec::new();
+        let mut state: usize = 12345;
+
+        for i in 0..3000 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345) %
+                (1 << 31);
+            let pos: usize = state % (expected_chars.len() + 1);
+
+            if i % 3 == 2 && pos < expected_chars.len() {
+                let end: usize = (pos + 5).min(expected_chars.len());
+                actual_rope.remove_range(pos..end)?;
+                expected_chars.drain(pos..end);
+            } else {
+                actual_rope.insert_str(pos, "ab\nc")?;
+                expected_chars.splice(pos..pos, "ab\nc".chars());
+            }
+        }
+
+        let expected_text: String = expected_chars.iter().collect();
+        let expected_lines: usize =
+            expected_chars.iter().filter(|c| **c == '\n').count() + 1;
+
+        assert_eq!(actual_rope.to_string(), expected_text);
+        assert_eq!(actual_rope.len_lines(), expected_lines);
+        assert!(actual_rope.root.height() <= 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_normal() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from("dcba");
+
+        List::append(&mut actual_rope, 'e')?;
+        List::insert(&mut actual_rope, 0, 'f')?;
+
+        assert_eq!(List::length(&actual_rope)?, 6);
+        assert_eq!(List::get(&actual_rope, 1)?, &'d');
+        assert_eq!(actual_rope[5], 'e');
+        assert_eq!(List::find(&actual_rope, &'b')?, Some(3));
+        assert_eq!(List::remove(&mut actual_rope, 0)?, 'f');
+
+        bubblesort(&mut actual_rope, cmp_leq)?;
+
+        assert_eq!(actual_rope.to_string(), "abcde");
+        assert_eq!(List::iter(&actual_rope).rev().collect::<String>(),
+                   "edcba");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_mut_normal_newline() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from("ab cd ef");
+
+        *List::get_mut(&mut actual_rope, 2)? = '\n';
+
+        assert_eq!(actual_rope.len_lines(), 2);
+        assert_eq!(actual_rope.char_to_line(4)?, 1);
+
+        List::set(&mut actual_rope, 5, '\n')?;
+
+        assert_eq!(actual_rope.line_to_char(2)?, 6);
+        assert_eq!(actual_rope.line(1)?.to_string(), "cd\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_mut_normal_against_string() -> Result<(), ListError> {
+        let mut rng: XorShiftRng = XorShiftRng::new(11);
+        let mut expected_chars: Vec<char> = lorem(100).chars().collect();
+        let mut actual_rope: Rope = expected_chars.iter().cloned().collect();
+
+        for _ in 0..300 {
+            let pos: usize = rng.next_u64() as usize % expected_chars.len();
+            let c: char = if rng.next_u64() % 2 == 0 { '\n' } else { 'x' };
+
+            actual_rope[pos] = c;
+            expected_chars[pos] = c;
+
+            let newlines: Vec<usize> = (0..expected_chars.len())
+                .filter(|i| expected_chars[*i] == '\n')
+                .collect();
+            let line: usize = rng.next_u64() as usize % (newlines.len() + 1);
+            let probe: usize = rng.next_u64() as usize % expected_chars.len();
+
+            assert_eq!(actual_rope.len_lines(), newlines.len() + 1);
+            assert_eq!(actual_rope.line_to_char(line)?,
+                       if line == 0 { 0 } else { newlines[line - 1] + 1 });
+            assert_eq!(actual_rope.char_to_line(probe)?,
+                       newlines.iter().filter(|i| **i < probe).count());
+            assert_eq!(actual_rope.slice(pos..expected_chars.len())?
+                           .len_lines(),
+                       newlines.iter().filter(|i| **i >= pos).count() + 1);
+
+            if rng.next_u64() % 8 == 0 {
+                actual_rope.insert_str(pos, "a\nb")?;
+                expected_chars.splice(pos..pos, "a\nb".chars());
+            }
+        }
+
+        assert_eq!(actual_rope.chars().cloned().collect::<Vec<char>>(),
+                   expected_chars);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal_newlines() -> Result<(), ListError> {
+        let mut actual_rope: Rope = Rope::from(lorem(100).as_str());
+
+        for c in List::iter_mut(&mut actual_rope) {
+            if *c == ' ' {
+                *c = '\n';
+            }
+        }
+
+        assert!(actual_rope.lines_dirty.load(Ordering::Acquire));
+        assert_eq!(actual_rope.len_lines(), 701);
+        assert!(!actual_rope.lines_dirty.load(Ordering::Acquire));
+        assert_eq!(actual_rope.line_to_char(7)?, 34);
+        assert_eq!(actual_rope.char_to_line(34)?, 7);
+        assert_eq!(actual_rope.line_to_char(701),
+                   Err(ListError::OutOfBounds));
+
+        actual_rope.insert_str(0, "\n")?;
+
+        assert_eq!(actual_rope.len_lines(), 702);
+        assert_eq!(actual_rope.line(2)?.to_string(), "0\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), ListError> {
+        let text: String = lorem(50);
+        let mut actual_rope: Rope = Rope::from(text.as_str());
+        let shared_rope: Rope = actual_rope.clone();
+
+        for c in List::iter_mut(&mut actual_rope) {
+            *c = c.to_ascii_uppercase();
+        }
+
+        assert_eq!(actual_rope.to_string(), text.to_ascii_uppercase());
+        assert_eq!(shared_rope.to_string(), text);
+        assert_eq!(List::iter(&actual_rope).len(), text.len());
+
+        Ok(())
+    }
+}
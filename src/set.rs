@@ -1,5 +1,5 @@
-use std::fmt::{Debug, Display};
-use std::hash::Hash;
+use std::error::Error;
+use std::fmt;
 
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
 #[allow(dead_code)]
@@ -9,13 +9,90 @@ pub enum SetError {
 
 impl Eq for SetError {}
 
-pub trait Set<T: Sized + Clone + Eq + Display + Debug>: Clone + Eq + Debug +
-    Display + IntoIterator {
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetError::Impossible => write!(f, "impossible state reached")
+        }
+    }
+}
+
+impl Error for SetError {}
+
+pub trait Set<T: Eq>: IntoIterator {
+    type Iter<'a>: Iterator<Item = &'a T> where Self: 'a, T: 'a;
+
     fn new() -> Self;
     fn add(&mut self, elem: T) -> Result<(), SetError>;
-    fn remove(&mut self, elem: T) -> Result<(), SetError>;
-    fn contains(&self, elem: T) -> Result<bool, SetError>;
     fn size(&self) -> Result<usize, SetError>;
     fn clear(&mut self) -> Result<(), SetError>;
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /* removing an element that is not there leaves the set as it was */
+    fn remove<Q>(&mut self, elem: &Q) -> Result<(), SetError> where
+        Self: SetLookup<T, Q>, Q: ?Sized {
+        self.lookup_remove(elem);
+        Ok(())
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, SetError> where
+        Self: SetLookup<T, Q>, Q: ?Sized {
+        Ok(self.lookup(elem))
+    }
+
+    #[deprecated(note = "use `remove` with a reference instead")]
+    fn remove_by_value(&mut self, elem: T) -> Result<(), SetError> where
+        Self: SetLookup<T, T> {
+        self.remove(&elem)
+    }
+
+    #[deprecated(note = "use `contains` with a reference instead")]
+    fn contains_by_value(&self, elem: T) -> Result<bool, SetError> where
+        Self: SetLookup<T, T> {
+        self.contains(&elem)
+    }
 }
 
+/* how a set finds an element given in a borrowed form `Q`; as with
+ * `MapLookup`, the bound on `Q` belongs to each set's own impl */
+pub trait SetLookup<T, Q: ?Sized> {
+    fn lookup(&self, elem: &Q) -> bool;
+    /* returns whether `elem` was there to remove */
+    fn lookup_remove(&mut self, elem: &Q) -> bool;
+}
+
+/* object-safe subset of `Set` for use as `Box<dyn DynSet<T>>` */
+pub trait DynSet<T> {
+    fn add(&mut self, elem: T) -> Result<(), SetError>;
+    fn remove(&mut self, elem: &T) -> Result<(), SetError>;
+    fn contains(&self, elem: &T) -> Result<bool, SetError>;
+    fn size(&self) -> Result<usize, SetError>;
+    fn clear(&mut self) -> Result<(), SetError>;
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+}
+
+impl<T, S> DynSet<T> for S where T: Eq, S: Set<T> + SetLookup<T, T> {
+    fn add(&mut self, elem: T) -> Result<(), SetError> {
+        Set::add(self, elem)
+    }
+
+    fn remove(&mut self, elem: &T) -> Result<(), SetError> {
+        Set::remove(self, elem)
+    }
+
+    fn contains(&self, elem: &T) -> Result<bool, SetError> {
+        Set::contains(self, elem)
+    }
+
+    fn size(&self) -> Result<usize, SetError> {
+        Set::size(self)
+    }
+
+    fn clear(&mut self) -> Result<(), SetError> {
+        Set::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(Set::iter(self))
+    }
+}
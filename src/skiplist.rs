@@ -0,0 +1,654 @@
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut};
+use std::iter::FromIterator;
+use std::fmt;
+use std::fmt::Display;
+use crate::list::{List, ListError, Reserve};
+use crate::rng::XorShiftRng;
+
+pub(crate) const MAX_LEVEL: usize = 32;
+
+const HEAD: usize = 0;
+const TAIL: usize = 1;
+
+/* a tower height drawn with P(h > k) = 4^-k, capped at `max` */
+pub(crate) fn random_level(rng: &mut XorShiftRng, max: usize) -> usize {
+    let zeros: usize = rng.next_u64().trailing_zeros() as usize;
+    (1 + zeros / 2).min(max)
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    elem: Option<T>,
+    next: Vec<usize>,
+    /* `width[l]` is how many positions `next[l]` lies ahead of this node */
+    width: Vec<usize>
+}
+
+/* an indexable skip list: every link records the number of positions it
+ * skips, so a descent can steer by position instead of by key and get,
+ * insert and remove are all O(log n) expected; nodes live in an arena
+ * with `HEAD` and `TAIL` sentinels, and removed slots are reused */
+#[derive(Clone, Debug)]
+pub struct SkipList<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    len: usize,
+    rng: XorShiftRng
+}
+
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    order: std::vec::IntoIter<usize>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next()?;
+        self.nodes[i].elem.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next_back()?;
+        self.nodes[i].elem.as_ref()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    elems: Vec<Option<&'a mut T>>,
+    order: std::vec::IntoIter<usize>
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next()?;
+        self.elems[i].take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next_back()?;
+        self.elems[i].take()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<T> SkipList<T> {
+    pub fn with_seed(seed: u64) -> Self {
+        SkipList {
+            nodes: vec![
+                Node {
+                    elem: None,
+                    next: vec![TAIL; MAX_LEVEL],
+                    width: vec![1; MAX_LEVEL]
+                },
+                Node {
+                    elem: None,
+                    next: Vec::new(),
+                    width: Vec::new()
+                }
+            ],
+            free: Vec::new(),
+            len: 0,
+            rng: XorShiftRng::new(seed)
+        }
+    }
+
+    /* arena index of the node at `pos`, counting the head as position 0 */
+    fn node_at(&self, pos: usize) -> usize {
+        let mut node: usize = HEAD;
+        let mut steps: usize = 0;
+
+        for lvl in (0..MAX_LEVEL).rev() {
+            while steps + self.nodes[node].width[lvl] <= pos {
+                steps += self.nodes[node].width[lvl];
+                node = self.nodes[node].next[lvl];
+            }
+        }
+
+        node
+    }
+
+    /* for each level, the last node before position `pos + 1` and its
+     * position */
+    fn predecessors(&self, pos: usize)
+        -> ([usize; MAX_LEVEL], [usize; MAX_LEVEL]) {
+        let mut chain: [usize; MAX_LEVEL] = [HEAD; MAX_LEVEL];
+        let mut steps_at: [usize; MAX_LEVEL] = [0; MAX_LEVEL];
+        let mut node: usize = HEAD;
+        let mut steps: usize = 0;
+
+        for lvl in (0..MAX_LEVEL).rev() {
+            while steps + self.nodes[node].width[lvl] <= pos {
+                steps += self.nodes[node].width[lvl];
+                node = self.nodes[node].next[lvl];
+            }
+
+            chain[lvl] = node;
+            steps_at[lvl] = steps;
+        }
+
+        (chain, steps_at)
+    }
+
+    fn alloc(&mut self, elem: T, height: usize) -> usize {
+        let node: Node<T> = Node {
+            elem: Some(elem),
+            next: vec![TAIL; height],
+            width: vec![0; height]
+        };
+
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /* arena indices of the elements in list order */
+    fn order(&self) -> Vec<usize> {
+        let mut res: Vec<usize> = Vec::with_capacity(self.len);
+        let mut node: usize = self.nodes[HEAD].next[0];
+
+        while node != TAIL {
+            res.push(node);
+            node = self.nodes[node].next[0];
+        }
+
+        res
+    }
+}
+
+impl<T> Default for SkipList<T> {
+    fn default() -> Self {
+        SkipList::with_seed(0)
+    }
+}
+
+impl<T> PartialEq for SkipList<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        List::iter(self).zip(List::iter(other)).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for SkipList<T> where T: Eq {}
+
+impl<T> IntoIterator for SkipList<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.order()
+            .into_iter()
+            .filter_map(|i| self.nodes[i].elem.take())
+            .collect::<Vec<T>>()
+            .into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SkipList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter_mut(self)
+    }
+}
+
+impl<T> FromIterator<T> for SkipList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res: SkipList<T> = SkipList::default();
+
+        res.extend(iter);
+        res
+    }
+}
+
+impl<T> Extend<T> for SkipList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        /* appending only fails on a position past the end, which `len`
+         * never is */
+        for elem in iter {
+            self.append(elem).expect("append at the end is in bounds");
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for SkipList<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T> Index<usize> for SkipList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> IndexMut<usize> for SkipList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl<T> Display for SkipList<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in List::iter(self) {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+impl<T> List<T> for SkipList<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        SkipList::default()
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        let mut res: SkipList<T> = SkipList::default();
+        res.nodes.reserve(capacity);
+        res
+    }
+
+    fn get(&self, pos: usize) -> Result<&T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        match self.nodes[self.node_at(pos + 1)].elem.as_ref() {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let node: usize = self.node_at(pos + 1);
+
+        match self.nodes[node].elem.as_mut() {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        *self.get_mut(pos)? = elem;
+        Ok(())
+    }
+
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        if pos > self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (chain, steps_at) = self.predecessors(pos);
+        let height: usize = random_level(&mut self.rng, MAX_LEVEL);
+        let node: usize = self.alloc(elem, height);
+
+        for lvl in 0..height {
+            let prev: usize = chain[lvl];
+            let offset: usize = pos - steps_at[lvl];
+
+            self.nodes[node].next[lvl] = self.nodes[prev].next[lvl];
+            self.nodes[node].width[lvl] = self.nodes[prev].width[lvl] - offset;
+            self.nodes[prev].next[lvl] = node;
+            self.nodes[prev].width[lvl] = offset + 1;
+        }
+
+        for (lvl, prev) in chain.iter().enumerate().skip(height) {
+            self.nodes[*prev].width[lvl] += 1;
+        }
+
+        self.len += 1;
+        Ok(())
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (chain, _) = self.predecessors(pos);
+        let node: usize = self.nodes[chain[0]].next[0];
+        let height: usize = self.nodes[node].next.len();
+
+        for (lvl, prev) in chain.iter().enumerate().take(height) {
+            self.nodes[*prev].next[lvl] = self.nodes[node].next[lvl];
+            self.nodes[*prev].width[lvl] += self.nodes[node].width[lvl] - 1;
+        }
+
+        for (lvl, prev) in chain.iter().enumerate().skip(height) {
+            self.nodes[*prev].width[lvl] -= 1;
+        }
+
+        self.nodes[node].next.clear();
+        self.nodes[node].width.clear();
+        self.free.push(node);
+        self.len -= 1;
+
+        match self.nodes[node].elem.take() {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        Ok(self.len)
+    }
+
+    fn append(&mut self, elem: T) -> Result<(), ListError> {
+        let len: usize = self.len;
+        self.insert(len, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        if a >= self.len || b >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let a: usize = self.node_at(a + 1);
+        let b: usize = self.node_at(b + 1);
+
+        let elem_a: Option<T> = self.nodes[a].elem.take();
+        let elem_b: Option<T> = std::mem::replace(&mut self.nodes[b].elem,
+                                                  elem_a);
+        self.nodes[a].elem = elem_b;
+
+        Ok(())
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).any(|curr| curr.borrow() == elem))
+    }
+
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+
+        for (i, curr_elem) in List::iter(self).enumerate() {
+            if curr_elem.borrow() == elem {
+                res.push(i);
+            }
+        }
+
+        Ok(Some(res))
+    }
+
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).position(|curr| curr.borrow() == elem))
+    }
+
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).filter(|curr| (*curr).borrow() == elem).count())
+    }
+
+    /* keeps the generator's state so that a cleared list doesn't replay
+     * the shapes it has already produced */
+    fn clear(&mut self) -> Result<(), ListError> {
+        let rng: XorShiftRng = self.rng.clone();
+
+        *self = SkipList::default();
+        self.rng = rng;
+
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            nodes: &self.nodes,
+            order: self.order().into_iter()
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let order: Vec<usize> = self.order();
+
+        IterMut {
+            elems: self.nodes.iter_mut().map(|node| node.elem.as_mut())
+                .collect(),
+            order: order.into_iter()
+        }
+    }
+}
+
+impl<T> Reserve for SkipList<T> {
+    fn capacity(&self) -> Result<usize, ListError> {
+        Ok(self.nodes.capacity() - 2)
+    }
+
+    fn reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        self.nodes.reserve(additional.saturating_sub(self.free.len()));
+        Ok(())
+    }
+
+    fn reserve_exact(&mut self, additional: usize) -> Result<(), ListError> {
+        self.nodes.reserve_exact(additional.saturating_sub(self.free.len()));
+        Ok(())
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), ListError> {
+        match self.nodes.try_reserve(additional.saturating_sub(self.free.len()))
+        {
+            Ok(()) => Ok(()),
+            Err(_) => Err(ListError::AllocationFailed)
+        }
+    }
+
+    /* freed slots are still linked by index, so only spare capacity past
+     * the end of the arena can be released */
+    fn shrink_to_fit(&mut self) -> Result<(), ListError> {
+        self.nodes.shrink_to_fit();
+        self.free.shrink_to_fit();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bubblesort::bubblesort;
+
+    fn cmp_leq<T: Eq + Ord>(a: &T, b: &T) -> bool {
+        a <= b
+    }
+
+    fn contents(list: &SkipList<u64>) -> Vec<u64> {
+        List::iter(list).cloned().collect()
+    }
+
+    /* each link's width matches the positions it actually skips */
+    fn assert_widths(list: &SkipList<u64>) {
+        let order: Vec<usize> = list.order();
+        let position = |node: usize| -> usize {
+            if node == TAIL {
+                order.len() + 1
+            } else {
+                order.iter().position(|i| *i == node).unwrap_or(0) + 1
+            }
+        };
+
+        for (pos, node) in std::iter::once(HEAD).chain(order.clone())
+            .enumerate() {
+            for (lvl, next) in list.nodes[node].next.iter().enumerate() {
+                assert_eq!(list.nodes[node].width[lvl], position(*next) - pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_normal() -> Result<(), ListError> {
+        let actual_list: SkipList<u64> = SkipList::new();
+
+        assert_eq!(actual_list.length()?, 0);
+        assert_eq!(actual_list.get(0), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_positions() -> Result<(), ListError> {
+        let mut actual_list: SkipList<u64> = SkipList::with_seed(7);
+        let mut expected_elems: Vec<u64> = Vec::new();
+        let mut rng: XorShiftRng = XorShiftRng::new(99);
+
+        for i in 0..2000 {
+            let pos: usize =
+                (rng.next_u64() % (expected_elems.len() as u64 + 1)) as usize;
+
+            actual_list.insert(pos, i)?;
+            expected_elems.insert(pos, i);
+        }
+
+        assert_eq!(contents(&actual_list), expected_elems);
+
+        for (i, elem) in expected_elems.iter().enumerate().step_by(37) {
+            assert_eq!(actual_list.get(i)?, elem);
+        }
+
+        assert_widths(&actual_list);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_list: SkipList<u64> = SkipList::from(vec![1, 2]);
+
+        let actual_res: Result<(), ListError> = actual_list.insert(3, 4);
+        let expected_res: Result<(), ListError> = Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), ListError> {
+        let mut actual_list: SkipList<u64> = (0..500).collect();
+        let mut expected_elems: Vec<u64> = (0..500).collect();
+        let mut rng: XorShiftRng = XorShiftRng::new(5);
+
+        while !expected_elems.is_empty() {
+            let pos: usize =
+                (rng.next_u64() % expected_elems.len() as u64) as usize;
+
+            assert_eq!(actual_list.remove(pos)?, expected_elems.remove(pos));
+        }
+
+        assert_widths(&actual_list);
+        assert_eq!(actual_list.remove(0), Err(ListError::OutOfBounds));
+
+        /* freed slots are reused */
+        actual_list.extend(0..10);
+
+        assert_eq!(actual_list.nodes.len(), 502);
+        assert_eq!(contents(&actual_list), (0..10).collect::<Vec<u64>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_seed_normal_reproducible() -> Result<(), ListError> {
+        let mut a: SkipList<u64> = SkipList::with_seed(3);
+        let mut b: SkipList<u64> = SkipList::with_seed(3);
+
+        for i in 0..100 {
+            a.append(i)?;
+            b.append(i)?;
+        }
+
+        let heights = |list: &SkipList<u64>| -> Vec<usize> {
+            list.order().iter().map(|i| list.nodes[*i].next.len()).collect()
+        };
+
+        assert_eq!(heights(&a), heights(&b));
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_normal() -> Result<(), ListError> {
+        let mut actual_list: SkipList<u64> =
+            SkipList::from(vec![5, 3, 9, 1, 7]);
+
+        actual_list.swap(0, 4)?;
+
+        assert_eq!(contents(&actual_list), vec![7, 3, 9, 1, 5]);
+
+        bubblesort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(contents(&actual_list), vec![1, 3, 5, 7, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal() -> Result<(), ListError> {
+        let mut actual_list: SkipList<u64> = (0..50).collect();
+
+        actual_list.remove(0)?;
+        actual_list.insert(0, 100)?;
+
+        for elem in actual_list.iter_mut() {
+            *elem += 1;
+        }
+
+        assert_eq!(List::iter(&actual_list).len(), 50);
+        assert_eq!(List::iter(&actual_list).next_back(), Some(&50));
+        assert_eq!(List::iter(&actual_list).next(), Some(&101));
+        assert_eq!(actual_list.into_iter().sum::<u64>(), 101 + 1274);
+
+        Ok(())
+    }
+}
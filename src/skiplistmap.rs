@@ -0,0 +1,723 @@
+use std::borrow::Borrow;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::fmt;
+use crate::map::{Map, MapError, MapLookup, OrderedMap};
+use crate::rng::XorShiftRng;
+use crate::skiplist::{random_level, MAX_LEVEL};
+
+const HEAD: usize = 0;
+
+#[derive(Clone, Debug)]
+struct Node<K, V> {
+    entry: Option<(K, V)>,
+    next: Vec<Option<usize>>
+}
+
+/* an ordered map over a skip list: each node carries a tower of forward
+ * links whose height is drawn from a seedable generator, giving O(log n)
+ * expected lookups and updates; nodes live in an arena headed by a
+ * sentinel, and removed slots are reused */
+#[derive(Clone, Debug)]
+pub struct SkipListMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    free: Vec<usize>,
+    level: usize,
+    len: usize,
+    rng: XorShiftRng
+}
+
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    next: Option<usize>,
+    end: Option<usize>,
+    remaining: Option<usize>
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node: usize = self.next?;
+
+        if self.next == self.end {
+            return None;
+        }
+
+        self.next = self.nodes[node].next[0];
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+
+        self.nodes[node].entry.as_ref().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (0, None)
+        }
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    entries: Vec<Option<&'a mut (K, V)>>,
+    order: std::vec::IntoIter<usize>
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next()?;
+        self.entries[i].take().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+impl<K, V> SkipListMap<K, V> {
+    pub fn with_seed(seed: u64) -> Self {
+        SkipListMap {
+            nodes: vec![Node {
+                entry: None,
+                next: vec![None; MAX_LEVEL]
+            }],
+            free: Vec::new(),
+            level: 1,
+            len: 0,
+            rng: XorShiftRng::new(seed)
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            next: self.nodes[HEAD].next[0],
+            end: None,
+            remaining: Some(self.len)
+        }
+    }
+
+    fn key(&self, node: usize) -> Option<&K> {
+        self.nodes[node].entry.as_ref().map(|(k, _)| k)
+    }
+
+    /* arena indices of the entries in key order */
+    fn order(&self) -> Vec<usize> {
+        let mut res: Vec<usize> = Vec::with_capacity(self.len);
+        let mut node: Option<usize> = self.nodes[HEAD].next[0];
+
+        while let Some(i) = node {
+            res.push(i);
+            node = self.nodes[i].next[0];
+        }
+
+        res
+    }
+}
+
+impl<K: Ord, V> SkipListMap<K, V> {
+    /* entries with keys in `range`, in ascending order */
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V> where
+        K: Borrow<Q>, Q: Ord + ?Sized, R: RangeBounds<Q> {
+        let next: Option<usize> = match range.start_bound() {
+            Bound::Included(start) => self.first_after(start, true),
+            Bound::Excluded(start) => self.first_after(start, false),
+            Bound::Unbounded => self.nodes[HEAD].next[0]
+        };
+        let end: Option<usize> = match range.end_bound() {
+            Bound::Included(end) => self.first_after(end, false),
+            Bound::Excluded(end) => self.first_after(end, true),
+            Bound::Unbounded => None
+        };
+
+        /* an empty or inverted range */
+        let next: Option<usize> = match (next, end) {
+            (Some(a), Some(b)) if self.key(a) > self.key(b) => end,
+            _ => next
+        };
+
+        Iter {
+            nodes: &self.nodes,
+            next,
+            end,
+            remaining: None
+        }
+    }
+
+    /* for each level, the last node whose key is below `key` */
+    fn predecessors<Q>(&self, key: &Q) -> [usize; MAX_LEVEL] where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut update: [usize; MAX_LEVEL] = [HEAD; MAX_LEVEL];
+        let mut node: usize = HEAD;
+
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.nodes[node].next[lvl] {
+                match self.key(next) {
+                    Some(k) if k.borrow() < key => node = next,
+                    _ => break
+                }
+            }
+
+            update[lvl] = node;
+        }
+
+        update
+    }
+
+    /* the last node whose key is below (or, if `inclusive`, at most)
+     * `key`, which is the head if there is none */
+    fn last_before<Q>(&self, key: &Q, inclusive: bool) -> usize where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut node: usize = HEAD;
+
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.nodes[node].next[lvl] {
+                match self.key(next) {
+                    Some(k) if k.borrow() < key ||
+                        (inclusive && k.borrow() == key) => node = next,
+                    _ => break
+                }
+            }
+        }
+
+        node
+    }
+
+    /* the first node whose key is at least (or, if not `inclusive`,
+     * strictly above) `key` */
+    fn first_after<Q>(&self, key: &Q, inclusive: bool) -> Option<usize> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.nodes[self.last_before(key, !inclusive)].next[0]
+    }
+
+    /* the last node in order, which is the head if the map is empty */
+    fn last_node(&self) -> usize {
+        let mut node: usize = HEAD;
+
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.nodes[node].next[lvl] {
+                node = next;
+            }
+        }
+
+        node
+    }
+
+    fn entry(&self, node: Option<usize>) -> Result<(&K, &V), MapError> {
+        match node.and_then(|i| self.nodes[i].entry.as_ref()) {
+            Some((key, value)) => Ok((key, value)),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    /* takes `node` out of the list given its `predecessors` */
+    fn unlink(&mut self, update: &[usize; MAX_LEVEL], node: usize)
+        -> Option<(K, V)> {
+        for (lvl, prev) in update.iter().enumerate().take(self.level) {
+            if self.nodes[*prev].next[lvl] == Some(node) {
+                self.nodes[*prev].next[lvl] = self.nodes[node].next[lvl];
+            }
+        }
+
+        let entry: Option<(K, V)> = self.nodes[node].entry.take();
+
+        self.nodes[node].next.clear();
+        self.free.push(node);
+        self.len -= 1;
+
+        while self.level > 1 && self.nodes[HEAD].next[self.level - 1].is_none()
+        {
+            self.level -= 1;
+        }
+
+        entry
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize> where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let node: usize = self.first_after(key, true)?;
+
+        match self.key(node) {
+            Some(k) if k.borrow() == key => Some(node),
+            _ => None
+        }
+    }
+
+    fn alloc(&mut self, key: K, value: V, height: usize) -> usize {
+        let node: Node<K, V> = Node {
+            entry: Some((key, value)),
+            next: vec![None; height]
+        };
+
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+impl<K, V> Default for SkipListMap<K, V> {
+    fn default() -> Self {
+        SkipListMap::with_seed(0)
+    }
+}
+
+impl<K, V> PartialEq for SkipListMap<K, V> where K: Eq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for SkipListMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for SkipListMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.order()
+            .into_iter()
+            .filter_map(|i| self.nodes[i].entry.take())
+            .collect::<Vec<(K, V)>>()
+            .into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipListMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipListMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: SkipListMap<K, V> = SkipListMap::default();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for SkipListMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K: Ord, V> SkipListMap<K, V> {
+    /* overwrites the value when `key` follows its predecessors, otherwise
+     * links in a node of random height and reports `true` */
+    fn insert(&mut self, key: K, value: V) -> bool {
+        let mut update: [usize; MAX_LEVEL] = self.predecessors(&key);
+
+        if let Some(next) = self.nodes[update[0]].next[0] {
+            if let Some((k, v)) = self.nodes[next].entry.as_mut() {
+                if *k == key {
+                    *v = value;
+                    return false;
+                }
+            }
+        }
+
+        let height: usize = random_level(&mut self.rng, MAX_LEVEL);
+
+        if height > self.level {
+            for slot in update.iter_mut().take(height).skip(self.level) {
+                *slot = HEAD;
+            }
+
+            self.level = height;
+        }
+
+        let node: usize = self.alloc(key, value, height);
+
+        for (lvl, prev) in update.iter().enumerate().take(height) {
+            self.nodes[node].next[lvl] = self.nodes[*prev].next[lvl];
+            self.nodes[*prev].next[lvl] = Some(node);
+        }
+
+        self.len += 1;
+        true
+    }
+}
+
+impl<K, V> Map<K, V> for SkipListMap<K, V> where K: Ord {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        SkipListMap::default()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    /* keeps the generator's state, as `SkipList::clear` does */
+    fn clear(&mut self) -> Result<(), MapError> {
+        let rng: XorShiftRng = self.rng.clone();
+
+        *self = SkipListMap::default();
+        self.rng = rng;
+
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        SkipListMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let order: Vec<usize> = self.order();
+
+        IterMut {
+            entries: self.nodes.iter_mut().map(|node| node.entry.as_mut())
+                .collect(),
+            order: order.into_iter()
+        }
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for SkipListMap<K, V> where
+    K: Ord + Borrow<Q>, Q: Ord + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.find(key)
+            .and_then(|node| self.nodes[node].entry.as_ref())
+            .map(|(_, value)| value)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        let node: usize = self.find(key)?;
+
+        self.nodes[node].entry.as_mut().map(|(_, value)| value)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        let update: [usize; MAX_LEVEL] = self.predecessors(key);
+
+        let node: usize = match self.nodes[update[0]].next[0] {
+            Some(node) if self.key(node).is_some_and(|k| k.borrow() == key) =>
+                node,
+            _ => return None
+        };
+
+        self.unlink(&update, node).map(|(_, value)| value)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> for SkipListMap<K, V> where K: Ord {
+    type Range<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+
+    fn first(&self) -> Result<(&K, &V), MapError> {
+        self.entry(self.nodes[HEAD].next[0])
+    }
+
+    fn last(&self) -> Result<(&K, &V), MapError> {
+        self.entry(Some(self.last_node()))
+    }
+
+    fn floor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(Some(self.last_before(key, true)))
+    }
+
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.first_after(key, true))
+    }
+
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(Some(self.last_before(key, false)))
+    }
+
+    fn successor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.first_after(key, false))
+    }
+
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        SkipListMap::range(self, range)
+    }
+
+    fn pop_first(&mut self) -> Result<(K, V), MapError> {
+        let node: usize = match self.nodes[HEAD].next[0] {
+            Some(node) => node,
+            None => return Err(MapError::KeyNotFound)
+        };
+
+        self.unlink(&[HEAD; MAX_LEVEL], node).ok_or(MapError::KeyNotFound)
+    }
+
+    fn pop_last(&mut self) -> Result<(K, V), MapError> {
+        let node: usize = self.last_node();
+
+        let update: [usize; MAX_LEVEL] = match self.key(node) {
+            Some(key) => self.predecessors(key),
+            None => return Err(MapError::KeyNotFound)
+        };
+
+        self.unlink(&update, node).ok_or(MapError::KeyNotFound)
+    }
+
+    /* pops the moved entries off the back, then links them, smallest
+     * first, into a map whose generator is seeded off this one's */
+    fn split_off<Q>(&mut self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let mut moved: Vec<(K, V)> = Vec::new();
+
+        while self.key(self.last_node()).is_some_and(|k| k.borrow() >= key) {
+            if let Ok(entry) = self.pop_last() {
+                moved.push(entry);
+            }
+        }
+
+        let mut res: SkipListMap<K, V> =
+            SkipListMap::with_seed(self.rng.next_u64());
+
+        for (key, value) in moved.into_iter().rev() {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(map: &SkipListMap<u64, u64>) -> Vec<u64> {
+        map.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn test_set_normal_ordered() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<u64, u64> = SkipListMap::with_seed(1);
+        let mut rng: XorShiftRng = XorShiftRng::new(2);
+        let mut expected_keys: Vec<u64> = Vec::new();
+
+        for _ in 0..1000 {
+            let key: u64 = rng.next_u64() % 5000;
+
+            actual_map.set(key, key * 2)?;
+            expected_keys.push(key);
+        }
+
+        expected_keys.sort();
+        expected_keys.dedup();
+
+        assert_eq!(keys(&actual_map), expected_keys);
+        assert_eq!(actual_map.size()?, expected_keys.len());
+        assert_eq!(actual_map.get(&expected_keys[10])?,
+                   &(expected_keys[10] * 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal_overwrite() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<String, u64> = SkipListMap::new();
+
+        actual_map.set(String::from("a"), 1)?;
+        actual_map.set(String::from("a"), 2)?;
+
+        assert_eq!(actual_map.get("a")?, &2);
+        assert_eq!(actual_map.size()?, 1);
+        assert_eq!(actual_map.get("b"), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<u64, u64> =
+            (0..300).map(|i| (i, i)).collect();
+
+        for i in (0..300).filter(|i| i % 3 != 0) {
+            actual_map.remove(&i)?;
+        }
+
+        assert_eq!(keys(&actual_map), (0..300).step_by(3).collect::<Vec<_>>());
+        assert_eq!(actual_map.remove(&1), Err(MapError::KeyNotFound));
+
+        for i in (0..300).step_by(3) {
+            actual_map.remove(&i)?;
+        }
+
+        assert_eq!(actual_map.size()?, 0);
+        assert_eq!(actual_map.level, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_normal() -> Result<(), MapError> {
+        let actual_map: SkipListMap<u64, u64> =
+            (0..100).step_by(10).map(|i| (i, i)).collect();
+        let collect = |iter: Iter<u64, u64>| -> Vec<u64> {
+            iter.map(|(k, _)| *k).collect()
+        };
+
+        assert_eq!(collect(actual_map.range(15..45)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(20..=40)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(20..40)), vec![20, 30]);
+        assert_eq!(collect(actual_map.range(..15)), vec![0, 10]);
+        assert_eq!(collect(actual_map.range(85..)), vec![90]);
+        assert_eq!(collect(actual_map.range(41..49)), Vec::<u64>::new());
+        assert_eq!(collect(actual_map.range((Bound::Excluded(90),
+                                             Bound::Unbounded))),
+                   Vec::<u64>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_floor_normal_neighbours() -> Result<(), MapError> {
+        let actual_map: SkipListMap<u64, u64> =
+            (10..100).step_by(10).map(|i| (i, i)).collect();
+
+        assert_eq!(actual_map.first()?, (&10, &10));
+        assert_eq!(actual_map.last()?, (&90, &90));
+        assert_eq!(actual_map.floor(&35)?, (&30, &30));
+        assert_eq!(actual_map.floor(&30)?, (&30, &30));
+        assert_eq!(actual_map.ceiling(&35)?, (&40, &40));
+        assert_eq!(actual_map.ceiling(&40)?, (&40, &40));
+        assert_eq!(actual_map.predecessor(&30)?, (&20, &20));
+        assert_eq!(actual_map.successor(&30)?, (&40, &40));
+        assert_eq!(actual_map.floor(&5), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.predecessor(&10), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.ceiling(&91), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.successor(&90), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_first_normal() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+
+        for i in 0..50 {
+            assert_eq!(actual_map.pop_first()?, (i, i));
+            assert_eq!(actual_map.pop_last()?, (99 - i, 99 - i));
+        }
+
+        assert_eq!(actual_map.pop_first(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.pop_last(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.first(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.level, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+
+        let actual_rest: SkipListMap<u64, u64> = actual_map.split_off(&60);
+
+        assert_eq!(keys(&actual_map), (0..60).collect::<Vec<u64>>());
+        assert_eq!(keys(&actual_rest), (60..100).collect::<Vec<u64>>());
+        assert_eq!(actual_rest.size()?, 40);
+        assert_eq!(actual_map.split_off(&0).size()?, 60);
+        assert_eq!(actual_map.size()?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_seed_normal_reproducible() -> Result<(), MapError> {
+        let a: SkipListMap<u64, u64> = {
+            let mut map: SkipListMap<u64, u64> = SkipListMap::with_seed(11);
+            map.extend_from(0..200);
+            map
+        };
+        let b: SkipListMap<u64, u64> = {
+            let mut map: SkipListMap<u64, u64> = SkipListMap::with_seed(11);
+            map.extend_from(0..200);
+            map
+        };
+
+        let heights = |map: &SkipListMap<u64, u64>| -> Vec<usize> {
+            map.order().iter().map(|i| map.nodes[*i].next.len()).collect()
+        };
+
+        assert_eq!(heights(&a), heights(&b));
+        assert_eq!(a, b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), MapError> {
+        let mut actual_map: SkipListMap<u64, u64> =
+            vec![(3, 30), (1, 10), (2, 20)].into_iter().collect();
+
+        actual_map.remove(&1)?;
+        actual_map.set(0, 0)?;
+
+        let actual_keys: Vec<u64> = actual_map.iter_mut()
+            .map(|(k, v)| {
+                *v += 1;
+                *k
+            })
+            .collect();
+
+        assert_eq!(actual_keys, vec![0, 2, 3]);
+        assert!(actual_map.contains_value(&31)?);
+        assert_eq!(actual_map.into_iter().collect::<Vec<_>>(),
+                   vec![(0, 1), (2, 21), (3, 31)]);
+
+        Ok(())
+    }
+
+    impl SkipListMap<u64, u64> {
+        fn extend_from<I: Iterator<Item = u64>>(&mut self, keys: I) {
+            for key in keys {
+                self.insert(key, key);
+            }
+        }
+    }
+}
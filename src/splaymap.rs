@@ -0,0 +1,988 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::fmt;
+use crate::map::{Map, MapError, MapLookup, OrderedMap};
+
+#[derive(Clone, Debug)]
+struct Node<K, V> {
+    entry: Option<(K, V)>,
+    left: Option<usize>,
+    right: Option<usize>
+}
+
+/* a self-adjusting binary search tree: the accesses through `&mut self`
+ * (`get_splay`, `get_mut`, `set`, `remove`, `pop_first`, `pop_last` and
+ * `split_off`) splay the key they looked for to the root, so keys that
+ * were touched recently are found in a few steps. `get`, `contains_key`
+ * and the other `&self` queries walk the tree as the last splay left it
+ * without restructuring it.
+ *
+ * a single splaying operation can cost O(n), but any sequence of m of
+ * them on a map that has held at most n keys costs O((m + n) log n), i.e.
+ * O(log n) amortised each; a run of accesses with a skewed distribution
+ * does better still, roughly in proportion to the entropy of that
+ * distribution. a read-only query costs the depth of its key, which that
+ * bound does not cover, so lookups that should speed up repeated accesses
+ * go through `get_splay` */
+#[derive(Clone, Debug)]
+pub struct SplayMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize
+}
+
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    order: std::vec::IntoIter<usize>
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node: usize = self.order.next()?;
+        self.nodes[node].entry.as_ref().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node: usize = self.order.next_back()?;
+        self.nodes[node].entry.as_ref().map(|(k, v)| (k, v))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct IterMut<'a, K, V> {
+    entries: Vec<Option<&'a mut (K, V)>>,
+    order: std::vec::IntoIter<usize>
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next()?;
+        self.entries[i].take().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let i: usize = self.order.next_back()?;
+        self.entries[i].take().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+impl<K, V> SplayMap<K, V> {
+    pub fn new() -> Self {
+        SplayMap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /* in key order */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            order: self.order().into_iter()
+        }
+    }
+
+    fn key(&self, node: usize) -> &K {
+        match self.nodes[node].entry.as_ref() {
+            Some((key, _)) => key,
+            None => unreachable!("linked node without an entry")
+        }
+    }
+
+    fn left(&self, node: usize) -> Option<usize> {
+        self.nodes[node].left
+    }
+
+    fn right(&self, node: usize) -> Option<usize> {
+        self.nodes[node].right
+    }
+
+    fn set_left(&mut self, node: usize, child: Option<usize>) {
+        self.nodes[node].left = child;
+    }
+
+    fn set_right(&mut self, node: usize, child: Option<usize>) {
+        self.nodes[node].right = child;
+    }
+
+    /* arena indices of the entries in key order */
+    fn order(&self) -> Vec<usize> {
+        let mut res: Vec<usize> = Vec::with_capacity(self.len);
+        let mut stack: Vec<usize> = Vec::new();
+        let mut node: Option<usize> = self.root;
+
+        loop {
+            while let Some(i) = node {
+                stack.push(i);
+                node = self.left(i);
+            }
+
+            match stack.pop() {
+                Some(i) => {
+                    res.push(i);
+                    node = self.right(i);
+                },
+                None => break
+            }
+        }
+
+        res
+    }
+
+    fn alloc(&mut self, key: K, value: V) -> usize {
+        let node: Node<K, V> = Node {
+            entry: Some((key, value)),
+            left: None,
+            right: None
+        };
+
+        match self.free.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> SplayMap<K, V> {
+    /* top-down splay of the subtree rooted at `t`: brings the node that
+     * `dir` steers to, or the last node on the way there, to the top and
+     * returns it. `dir` orders the target against a node's key, so the
+     * target need not be a key borrowed from outside the map */
+    fn splay_from<F>(&mut self, mut t: usize, dir: F) -> usize where
+        F: Fn(&K) -> Ordering {
+        /* roots and innermost nodes of the trees assembled to the left and
+         * right of the search path */
+        let mut left_root: Option<usize> = None;
+        let mut left_last: Option<usize> = None;
+        let mut right_root: Option<usize> = None;
+        let mut right_last: Option<usize> = None;
+
+        loop {
+            match dir(self.key(t)) {
+                Ordering::Less => {
+                    let mut child: usize = match self.left(t) {
+                        Some(child) => child,
+                        None => break
+                    };
+
+                    if dir(self.key(child)) == Ordering::Less { /* zig-zig */
+                        self.set_left(t, self.right(child));
+                        self.set_right(child, Some(t));
+                        t = child;
+
+                        child = match self.left(t) {
+                            Some(child) => child,
+                            None => break
+                        };
+                    }
+
+                    match right_last {
+                        Some(last) => self.set_left(last, Some(t)),
+                        None => right_root = Some(t)
+                    }
+
+                    right_last = Some(t);
+                    t = child;
+                },
+                Ordering::Greater => {
+                    let mut child: usize = match self.right(t) {
+                        Some(child) => child,
+                        None => break
+                    };
+
+                    if dir(self.key(child)) == Ordering::Greater { /* zag-zag */
+                        self.set_right(t, self.left(child));
+                        self.set_left(child, Some(t));
+                        t = child;
+
+                        child = match self.right(t) {
+                            Some(child) => child,
+                            None => break
+                        };
+                    }
+
+                    match left_last {
+                        Some(last) => self.set_right(last, Some(t)),
+                        None => left_root = Some(t)
+                    }
+
+                    left_last = Some(t);
+                    t = child;
+                },
+                Ordering::Equal => break
+            }
+        }
+
+        if let Some(last) = left_last {
+            self.set_right(last, self.left(t));
+            self.set_left(t, left_root);
+        }
+
+        if let Some(last) = right_last {
+            self.set_left(last, self.right(t));
+            self.set_right(t, right_root);
+        }
+
+        t
+    }
+
+    /* splays `key` to the root and returns the root if it holds `key` */
+    fn splay<Q>(&mut self, key: &Q) -> Option<usize> where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let root: usize = self.root?;
+        let root: usize = self.splay_from(root, |k: &K| key.cmp(k.borrow()));
+
+        self.root = Some(root);
+
+        if self.key(root).borrow() == key {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    /* splays the least (or if not `first`, the greatest) node to the root */
+    fn splay_extreme(&mut self, first: bool) -> Option<usize> {
+        let dir: Ordering = if first {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+        let root: usize = self.root?;
+        let root: usize = self.splay_from(root, |_: &K| dir);
+
+        self.root = Some(root);
+        Some(root)
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize> where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let mut node: Option<usize> = self.root;
+
+        while let Some(i) = node {
+            match key.cmp(self.key(i).borrow()) {
+                Ordering::Less => node = self.left(i),
+                Ordering::Greater => node = self.right(i),
+                Ordering::Equal => return Some(i)
+            }
+        }
+
+        None
+    }
+
+    /* the greatest key below `key` (or, if `inclusive`, at most `key`), or
+     * if not `below` the least key above it */
+    fn neighbour<Q>(&self, key: &Q, below: bool, inclusive: bool)
+        -> Option<usize> where K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut res: Option<usize> = None;
+        let mut node: Option<usize> = self.root;
+
+        while let Some(i) = node {
+            let hit: bool = match self.key(i).borrow().cmp(key) {
+                Ordering::Less => below,
+                Ordering::Greater => !below,
+                Ordering::Equal => inclusive
+            };
+
+            if hit {
+                res = Some(i);
+            }
+
+            /* past a hit, anything nearer to `key` is on the side facing
+             * it; short of one, so is anything that could hit at all */
+            node = if hit == below {
+                self.right(i)
+            } else {
+                self.left(i)
+            };
+        }
+
+        res
+    }
+
+    /* the least (or if not `first`, the greatest) node */
+    fn extreme(&self, first: bool) -> Option<usize> {
+        let mut node: usize = self.root?;
+
+        while let Some(next) = if first {
+            self.left(node)
+        } else {
+            self.right(node)
+        } {
+            node = next;
+        }
+
+        Some(node)
+    }
+
+    /* arena indices of the entries within the bounds, in key order;
+     * subtrees wholly below the start are skipped */
+    fn order_in<Q>(&self, start: Bound<&Q>, end: Bound<&Q>) -> Vec<usize>
+        where K: Borrow<Q>, Q: Ord + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+        let mut stack: Vec<usize> = Vec::new();
+        let mut node: Option<usize> = self.root;
+
+        loop {
+            while let Some(i) = node {
+                let k: &Q = self.key(i).borrow();
+
+                let after_start: bool = match start {
+                    Bound::Included(start) => k >= start,
+                    Bound::Excluded(start) => k > start,
+                    Bound::Unbounded => true
+                };
+
+                if after_start {
+                    stack.push(i);
+                    node = self.left(i);
+                } else {
+                    node = self.right(i);
+                }
+            }
+
+            let i: usize = match stack.pop() {
+                Some(i) => i,
+                None => break
+            };
+            let k: &Q = self.key(i).borrow();
+
+            let before_end: bool = match end {
+                Bound::Included(end) => k <= end,
+                Bound::Excluded(end) => k < end,
+                Bound::Unbounded => true
+            };
+
+            if !before_end {
+                break;
+            }
+
+            res.push(i);
+            node = self.right(i);
+        }
+
+        res
+    }
+
+    /* takes the root out, joining its subtrees */
+    fn unlink_root(&mut self, node: usize) -> Option<(K, V)> {
+        /* every key on the left is below the root's, so splaying for that
+         * there lifts the subtree's maximum, which has no right child */
+        let root: Option<usize> = match self.left(node) {
+            Some(left) => {
+                let max: usize =
+                    self.splay_from(left, |_: &K| Ordering::Greater);
+
+                self.set_right(max, self.right(node));
+                Some(max)
+            },
+            None => self.right(node)
+        };
+
+        self.root = root;
+        self.set_left(node, None);
+        self.set_right(node, None);
+        self.free.push(node);
+        self.len -= 1;
+
+        self.nodes[node].entry.take()
+    }
+
+    fn entry(&self, node: Option<usize>) -> Result<(&K, &V), MapError> {
+        match node.and_then(|i| self.nodes[i].entry.as_ref()) {
+            Some((key, value)) => Ok((key, value)),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    /* splays the nearest key to the root and, unless it is `key` itself,
+     * puts a new node above it; reports whether a node was added */
+    fn insert(&mut self, key: K, value: V) -> bool {
+        let root: usize = match self.root {
+            Some(root) => self.splay_from(root, |k: &K| key.cmp(k)),
+            None => {
+                let node: usize = self.alloc(key, value);
+
+                self.root = Some(node);
+                self.len += 1;
+                return true;
+            }
+        };
+
+        if let Some((k, v)) = self.nodes[root].entry.as_mut() {
+            if *k == key {
+                *v = value;
+                self.root = Some(root);
+                return false;
+            }
+        }
+
+        let below: bool = key < *self.key(root);
+        let node: usize = self.alloc(key, value);
+
+        if below {
+            self.set_left(node, self.left(root));
+            self.set_right(node, Some(root));
+            self.set_left(root, None);
+        } else {
+            self.set_right(node, self.right(root));
+            self.set_left(node, Some(root));
+            self.set_right(root, None);
+        }
+
+        self.root = Some(node);
+        self.len += 1;
+        true
+    }
+
+    /* like `get`, but splays `key` to the root so that accesses to it and
+     * its neighbours get cheaper */
+    pub fn get_splay<Q>(&mut self, key: &Q) -> Option<&V> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        let node: usize = self.splay(key)?;
+
+        self.nodes[node].entry.as_ref().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> Default for SplayMap<K, V> {
+    fn default() -> Self {
+        SplayMap::new()
+    }
+}
+
+impl<K, V> PartialEq for SplayMap<K, V> where K: Eq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for SplayMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for SplayMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.order()
+            .into_iter()
+            .filter_map(|i| self.nodes[i].entry.take())
+            .collect::<Vec<(K, V)>>()
+            .into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SplayMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SplayMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: SplayMap<K, V> = SplayMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for SplayMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Map<K, V> for SplayMap<K, V> where K: Ord {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        SplayMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        *self = SplayMap::new();
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        SplayMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let order: Vec<usize> = self.order();
+
+        IterMut {
+            entries: self.nodes.iter_mut().map(|node| node.entry.as_mut())
+                .collect(),
+            order: order.into_iter()
+        }
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for SplayMap<K, V> where
+    K: Ord + Borrow<Q>, Q: Ord + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.find(key)
+            .and_then(|node| self.nodes[node].entry.as_ref())
+            .map(|(_, value)| value)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        let node: usize = self.splay(key)?;
+
+        self.nodes[node].entry.as_mut().map(|(_, value)| value)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        let node: usize = self.splay(key)?;
+
+        self.unlink_root(node).map(|(_, value)| value)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> for SplayMap<K, V> where K: Ord {
+    type Range<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+
+    /* the queries below walk the tree without splaying it, as `get` does;
+     * only the pops restructure it */
+    fn first(&self) -> Result<(&K, &V), MapError> {
+        self.entry(self.extreme(true))
+    }
+
+    fn last(&self) -> Result<(&K, &V), MapError> {
+        self.entry(self.extreme(false))
+    }
+
+    fn floor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.neighbour(key, true, true))
+    }
+
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.neighbour(key, false, true))
+    }
+
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.neighbour(key, true, false))
+    }
+
+    fn successor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        self.entry(self.neighbour(key, false, false))
+    }
+
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        Iter {
+            nodes: &self.nodes,
+            order: self.order_in(range.start_bound(), range.end_bound())
+                .into_iter()
+        }
+    }
+
+    fn pop_first(&mut self) -> Result<(K, V), MapError> {
+        match self.splay_extreme(true) {
+            Some(node) => self.unlink_root(node).ok_or(MapError::KeyNotFound),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    fn pop_last(&mut self) -> Result<(K, V), MapError> {
+        match self.splay_extreme(false) {
+            Some(node) => self.unlink_root(node).ok_or(MapError::KeyNotFound),
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    /* the moved subtree is detached in one piece, but its entries still
+     * have to be carried over to the new map's arena: O(m) for m moved */
+    fn split_off<Q>(&mut self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        let mut res: SplayMap<K, V> = SplayMap::new();
+
+        let root: usize = match self.root {
+            Some(root) => self.splay_from(root, |k: &K| key.cmp(k.borrow())),
+            None => return res
+        };
+
+        let moved: Option<usize> = if self.key(root).borrow() >= key {
+            self.root = self.left(root);
+            self.set_left(root, None);
+            Some(root)
+        } else {
+            let right: Option<usize> = self.right(root);
+
+            self.root = Some(root);
+            self.set_right(root, None);
+            right
+        };
+
+        let moved: Vec<usize> = {
+            let rest: Option<usize> = std::mem::replace(&mut self.root, moved);
+            let order: Vec<usize> = self.order();
+
+            self.root = rest;
+            order
+        };
+
+        /* ascending inserts each land at the root, in O(1) */
+        for i in moved {
+            if let Some((key, value)) = self.nodes[i].entry.take() {
+                res.insert(key, value);
+            }
+
+            self.set_left(i, None);
+            self.set_right(i, None);
+            self.free.push(i);
+            self.len -= 1;
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShiftRng;
+
+    fn keys(map: &SplayMap<u64, u64>) -> Vec<u64> {
+        map.iter().map(|(k, _)| *k).collect()
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn depth(map: &SplayMap<u64, u64>, key: u64) -> usize {
+        let mut res: usize = 0;
+        let mut node: Option<usize> = map.root;
+
+        while let Some(i) = node {
+            match key.cmp(map.key(i)) {
+                Ordering::Less => node = map.left(i),
+                Ordering::Greater => node = map.right(i),
+                Ordering::Equal => return res
+            }
+
+            res += 1;
+        }
+
+        res
+    }
+
+    #[test]
+    fn test_set_normal_ordered() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> = SplayMap::new();
+        let mut rng: XorShiftRng = XorShiftRng::new(3);
+        let mut expected_keys: Vec<u64> = Vec::new();
+
+        for _ in 0..1000 {
+            let key: u64 = rng.next_u64() % 5000;
+
+            actual_map.set(key, key * 2)?;
+            expected_keys.push(key);
+        }
+
+        expected_keys.sort();
+        expected_keys.dedup();
+
+        assert_eq!(keys(&actual_map), expected_keys);
+        assert_eq!(actual_map.size()?, expected_keys.len());
+
+        for key in expected_keys.iter() {
+            assert_eq!(actual_map.get(key)?, &(key * 2));
+        }
+
+        assert_eq!(actual_map.get(&5000), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_splay_normal() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> =
+            (0..1000).map(|i| (i, i)).collect();
+
+        /* ascending inserts leave a path; one access roughly halves it */
+        assert_eq!(depth(&actual_map, 0), 999);
+        assert_eq!(actual_map.get_splay(&0), Some(&0));
+        assert_eq!(depth(&actual_map, 0), 0);
+        assert!(depth(&actual_map, 998) < 600);
+
+        for _ in 0..100 {
+            actual_map.get_splay(&500);
+            actual_map.get_splay(&501);
+        }
+
+        assert!(depth(&actual_map, 500) <= 1);
+        assert_eq!(actual_map.get_splay(&1000), None);
+        assert_eq!(keys(&actual_map), (0..1000).collect::<Vec<u64>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> =
+            (0..300).map(|i| (i * 7 % 300, i)).collect();
+
+        for i in (0..300).filter(|i| i % 3 != 0) {
+            actual_map.remove(&i)?;
+        }
+
+        assert_eq!(keys(&actual_map), (0..300).step_by(3).collect::<Vec<_>>());
+        assert_eq!(actual_map.remove(&1), Err(MapError::KeyNotFound));
+
+        actual_map.set(1, 1)?;
+
+        assert_eq!(actual_map.nodes.len(), 300);
+
+        for i in (0..300).step_by(3) {
+            actual_map.remove(&i)?;
+        }
+
+        assert_eq!(keys(&actual_map), vec![1]);
+        assert_eq!(actual_map.remove(&1), Ok(()));
+        assert!(actual_map.is_empty());
+        assert_eq!(actual_map.remove(&1), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal_without_splaying() -> Result<(), MapError> {
+        let actual_map: SplayMap<u64, u64> =
+            (0..10).map(|i| (i, i)).collect();
+        let mut actual_keys: Vec<u64> = Vec::new();
+
+        for (key, _) in actual_map.iter() {
+            assert_eq!(actual_map.get(&(9 - key))?, &(9 - key));
+            assert!(actual_map.contains_key(key)?);
+            actual_keys.push(*key);
+        }
+
+        assert_eq!(actual_keys, (0..10).collect::<Vec<u64>>());
+        assert_eq!(actual_map.floor(&4)?, (&4, &4));
+        assert_eq!(depth(&actual_map, 0), 9);
+        assert_eq!(depth(&actual_map, 9), 0);
+        assert_send_sync::<SplayMap<u64, u64>>();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_floor_normal_neighbours() -> Result<(), MapError> {
+        let actual_map: SplayMap<u64, u64> =
+            (10..100).step_by(10).map(|i| (i, i)).collect();
+
+        assert_eq!(actual_map.first()?, (&10, &10));
+        assert_eq!(actual_map.last()?, (&90, &90));
+        assert_eq!(actual_map.floor(&35)?, (&30, &30));
+        assert_eq!(actual_map.floor(&30)?, (&30, &30));
+        assert_eq!(actual_map.ceiling(&35)?, (&40, &40));
+        assert_eq!(actual_map.ceiling(&40)?, (&40, &40));
+        assert_eq!(actual_map.predecessor(&30)?, (&20, &20));
+        assert_eq!(actual_map.successor(&30)?, (&40, &40));
+        assert_eq!(actual_map.floor(&5), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.predecessor(&10), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.ceiling(&91), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.successor(&90), Err(MapError::KeyNotFound));
+        assert_eq!(keys(&actual_map),
+                   (10..100).step_by(10).collect::<Vec<u64>>());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_normal() -> Result<(), MapError> {
+        let actual_map: SplayMap<u64, u64> =
+            (0..100).step_by(10).map(|i| (i * 7 % 100, i)).collect();
+        let collect = |iter: Iter<u64, u64>| -> Vec<u64> {
+            iter.map(|(k, _)| *k).collect()
+        };
+
+        assert_eq!(collect(actual_map.range(15..45)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(20..=40)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(..15)), vec![0, 10]);
+        assert_eq!(collect(actual_map.range(85..)), vec![90]);
+        assert_eq!(collect(actual_map.range(41..49)), Vec::<u64>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_first_normal() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> =
+            (0..100).map(|i| (i * 37 % 100, i)).collect();
+
+        for i in 0..50 {
+            assert_eq!(actual_map.pop_first()?.0, i);
+            assert_eq!(actual_map.pop_last()?.0, 99 - i);
+        }
+
+        assert_eq!(actual_map.pop_first(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.pop_last(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.last(), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> =
+            (0..100).map(|i| (i * 37 % 100, i)).collect();
+
+        let actual_rest: SplayMap<u64, u64> = actual_map.split_off(&60);
+
+        assert_eq!(keys(&actual_map), (0..60).collect::<Vec<u64>>());
+        assert_eq!(keys(&actual_rest), (60..100).collect::<Vec<u64>>());
+        assert_eq!(actual_map.size()?, 60);
+        assert_eq!(actual_map.split_off(&100).size()?, 0);
+        assert_eq!(actual_map.split_off(&0).size()?, 60);
+        assert!(actual_map.is_empty());
+
+        /* the freed slots are taken by later inserts */
+        actual_map.set(1, 1)?;
+
+        assert_eq!(actual_map.nodes.len(), 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal_absent_key() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<u64, u64> =
+            vec![(10, 1), (20, 2), (30, 3)].into_iter().collect();
+
+        /* splays 10 or 20 to the root, which stays behind */
+        actual_map.get(&10)?;
+
+        let actual_rest: SplayMap<u64, u64> = actual_map.split_off(&15);
+
+        assert_eq!(keys(&actual_map), vec![10]);
+        assert_eq!(keys(&actual_rest), vec![20, 30]);
+        assert_eq!(actual_map.size()?, 1);
+        assert_eq!(actual_rest.size()?, 2);
+
+        /* splays 30 or 20 to the root, which moves */
+        let mut actual_map: SplayMap<u64, u64> =
+            vec![(10, 1), (20, 2), (30, 3)].into_iter().collect();
+
+        actual_map.get(&30)?;
+
+        let actual_rest: SplayMap<u64, u64> = actual_map.split_off(&25);
+
+        assert_eq!(keys(&actual_map), vec![10, 20]);
+        assert_eq!(keys(&actual_rest), vec![30]);
+        assert_eq!(actual_map.size()?, 2);
+        assert_eq!(actual_rest.size()?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), MapError> {
+        let mut actual_map: SplayMap<String, u64> = SplayMap::new();
+
+        actual_map.set(String::from("b"), 2)?;
+        actual_map.set(String::from("a"), 1)?;
+
+        for (_, value) in actual_map.iter_mut() {
+            *value *= 10;
+        }
+
+        *actual_map.get_mut("a")? += 1;
+
+        assert_eq!(actual_map.to_string(), "{a: 11, b: 20, }");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), MapError> {
+        let mut actual_map: Box<dyn crate::map::DynMap<u64, u64>> =
+            Box::new(SplayMap::new());
+
+        actual_map.set(2, 20)?;
+        actual_map.set(1, 10)?;
+
+        assert_eq!(actual_map.get(&1)?, &10);
+        assert!(actual_map.contains_value(&20)?);
+
+        actual_map.clear()?;
+
+        assert_eq!(actual_map.size()?, 0);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use std::fmt::{Display, Debug};
+use std::error::Error;
+use std::fmt;
 
 #[derive(Copy, Clone, PartialEq, Hash, Debug)]
 #[allow(dead_code)]
@@ -7,13 +8,63 @@ pub enum StackError {
     Impossible
 }
 
-pub trait Stack<T: Sized + Clone+ Eq + Display + Debug>: Clone + Eq + Debug +
-    Display + IntoIterator {
+impl fmt::Display for StackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StackError::OutOfBounds => write!(f, "stack is empty"),
+            StackError::Impossible => write!(f, "impossible state reached")
+        }
+    }
+}
+
+impl Error for StackError {}
+
+pub trait Stack<T>: IntoIterator {
+    type Iter<'a>: Iterator<Item = &'a T> where Self: 'a, T: 'a;
+    type IterMut<'a>: Iterator<Item = &'a mut T> where Self: 'a, T: 'a;
+
     fn new() -> Self;
     fn push(&mut self, elem: T) -> Result<(), StackError>;
     fn pop(&mut self) -> Result<T, StackError>;
     fn peek(&self) -> Result<&T, StackError>;
     fn depth(&self) -> Result<usize, StackError>;
     fn clear(&mut self) -> Result<(), StackError>;
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
 }
 
+/* object-safe subset of `Stack` for use as `Box<dyn DynStack<T>>` */
+pub trait DynStack<T> {
+    fn push(&mut self, elem: T) -> Result<(), StackError>;
+    fn pop(&mut self) -> Result<T, StackError>;
+    fn peek(&self) -> Result<&T, StackError>;
+    fn depth(&self) -> Result<usize, StackError>;
+    fn clear(&mut self) -> Result<(), StackError>;
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+}
+
+impl<T, S> DynStack<T> for S where S: Stack<T> {
+    fn push(&mut self, elem: T) -> Result<(), StackError> {
+        Stack::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Result<T, StackError> {
+        Stack::pop(self)
+    }
+
+    fn peek(&self) -> Result<&T, StackError> {
+        Stack::peek(self)
+    }
+
+    fn depth(&self) -> Result<usize, StackError> {
+        Stack::depth(self)
+    }
+
+    fn clear(&mut self) -> Result<(), StackError> {
+        Stack::clear(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(Stack::iter(self))
+    }
+}
@@ -0,0 +1,629 @@
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+use std::iter::{Flatten, FromIterator};
+use std::fmt;
+use std::fmt::Display;
+use std::slice;
+use crate::list::{List, ListError};
+
+const MIN_BLOCK_SIZE: usize = 8;
+
+/* elements are split across ring-buffer blocks of `block_size` elements,
+ * every block but the last being full; an edit shifts within one block and
+ * then carries a single element through each of the following blocks, so
+ * keeping `block_size` near √n bounds both costs by O(√n) */
+#[derive(Clone, Debug)]
+pub struct TieredVec<T> {
+    blocks: Vec<VecDeque<T>>,
+    block_size: usize,
+    len: usize
+}
+
+pub struct Iter<'a, T> {
+    elems: Flatten<slice::Iter<'a, VecDeque<T>>>,
+    remaining: usize
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a T> = self.elems.next();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a T> = self.elems.next_back();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    elems: Flatten<slice::IterMut<'a, VecDeque<T>>>,
+    remaining: usize
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a mut T> = self.elems.next();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a mut T> = self.elems.next_back();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<T> TieredVec<T> {
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /* smallest power of two no less than √len */
+    fn block_size_for(len: usize) -> usize {
+        let mut block_size: usize = MIN_BLOCK_SIZE;
+
+        while block_size.saturating_mul(block_size) < len {
+            block_size *= 2;
+        }
+
+        block_size
+    }
+
+    fn locate(&self, pos: usize) -> (usize, usize) {
+        (pos / self.block_size, pos % self.block_size)
+    }
+
+    fn new_block(&self) -> VecDeque<T> {
+        VecDeque::with_capacity(self.block_size)
+    }
+
+    /* redistributes the elements once the block size has drifted more than a
+     * factor of two away from √len; amortised against the Θ(len) edits it
+     * takes to drift that far */
+    fn rebalance(&mut self) {
+        let too_small: bool = self.len > 4 * self.block_size * self.block_size;
+        let too_large: bool = self.block_size > MIN_BLOCK_SIZE &&
+            self.len < self.block_size * self.block_size / 4;
+
+        if !too_small && !too_large {
+            return;
+        }
+
+        let block_size: usize = TieredVec::<T>::block_size_for(self.len);
+        let old_blocks: Vec<VecDeque<T>> = std::mem::take(&mut self.blocks);
+
+        self.block_size = block_size;
+
+        for elem in old_blocks.into_iter().flatten() {
+            self.push_back(elem);
+        }
+    }
+
+    fn push_back(&mut self, elem: T) {
+        let full: bool = match self.blocks.last() {
+            Some(block) => block.len() == self.block_size,
+            None => true
+        };
+
+        if full {
+            let block: VecDeque<T> = self.new_block();
+            self.blocks.push(block);
+        }
+
+        if let Some(block) = self.blocks.last_mut() {
+            block.push_back(elem);
+        }
+    }
+}
+
+impl<T> Default for TieredVec<T> {
+    fn default() -> Self {
+        TieredVec {
+            blocks: Vec::new(),
+            block_size: MIN_BLOCK_SIZE,
+            len: 0
+        }
+    }
+}
+
+impl<T> PartialEq for TieredVec<T> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        List::iter(self).zip(List::iter(other)).all(|(a, b)| a == b)
+    }
+}
+
+impl<T> Eq for TieredVec<T> where T: Eq {}
+
+impl<T> IntoIterator for TieredVec<T> {
+    type Item = T;
+    type IntoIter = Flatten<std::vec::IntoIter<VecDeque<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.blocks.into_iter().flatten()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TieredVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut TieredVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter_mut(self)
+    }
+}
+
+impl<T> FromIterator<T> for TieredVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elems: Vec<T> = iter.into_iter().collect();
+        let mut res: TieredVec<T> = TieredVec {
+            blocks: Vec::new(),
+            block_size: TieredVec::<T>::block_size_for(elems.len()),
+            len: elems.len()
+        };
+
+        for elem in elems {
+            res.push_back(elem);
+        }
+
+        res
+    }
+}
+
+impl<T> Extend<T> for TieredVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for TieredVec<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T> Index<usize> for TieredVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> IndexMut<usize> for TieredVec<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl<T> Display for TieredVec<T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in List::iter(self) {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+impl<T> List<T> for TieredVec<T> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        TieredVec::default()
+    }
+
+    /* only the block table is reserved, since the block size tracks the
+     * current length rather than the expected one */
+    fn with_capacity(capacity: usize) -> Self {
+        TieredVec {
+            blocks: Vec::with_capacity(capacity.div_ceil(MIN_BLOCK_SIZE)),
+            block_size: MIN_BLOCK_SIZE,
+            len: 0
+        }
+    }
+
+    fn get(&self, pos: usize) -> Result<&T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (block, offset) = self.locate(pos);
+
+        match self.blocks[block].get(offset) {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (block, offset) = self.locate(pos);
+
+        match self.blocks[block].get_mut(offset) {
+            Some(elem) => Ok(elem),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        *self.get_mut(pos)? = elem;
+        Ok(())
+    }
+
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        if pos > self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        if pos == self.len {
+            self.push_back(elem);
+        } else {
+            let (block, offset) = self.locate(pos);
+            self.blocks[block].insert(offset, elem);
+
+            /* carry the overflow through the remaining blocks */
+            for i in block..self.blocks.len() {
+                if self.blocks[i].len() <= self.block_size {
+                    break;
+                }
+
+                let carry: T = match self.blocks[i].pop_back() {
+                    Some(carry) => carry,
+                    None => return Err(ListError::Impossible)
+                };
+
+                if i + 1 == self.blocks.len() {
+                    let new_block: VecDeque<T> = self.new_block();
+                    self.blocks.push(new_block);
+                }
+
+                self.blocks[i + 1].push_front(carry);
+            }
+        }
+
+        self.len += 1;
+        self.rebalance();
+
+        Ok(())
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (block, offset) = self.locate(pos);
+
+        let elem: T = match self.blocks[block].remove(offset) {
+            Some(elem) => elem,
+            None => return Err(ListError::Impossible)
+        };
+
+        /* refill the hole from each of the following blocks */
+        for i in block + 1..self.blocks.len() {
+            if let Some(carry) = self.blocks[i].pop_front() {
+                self.blocks[i - 1].push_back(carry);
+            }
+        }
+
+        if self.blocks.last().is_some_and(|last| last.is_empty()) {
+            self.blocks.pop();
+        }
+
+        self.len -= 1;
+        self.rebalance();
+
+        Ok(elem)
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        Ok(self.len)
+    }
+
+    fn append(&mut self, elem: T) -> Result<(), ListError> {
+        let len: usize = self.len;
+        self.insert(len, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        if a >= self.len || b >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (block_a, offset_a) = self.locate(a);
+        let (block_b, offset_b) = self.locate(b);
+
+        if block_a == block_b {
+            self.blocks[block_a].swap(offset_a, offset_b);
+            return Ok(());
+        }
+
+        let (low, high) = (block_a.min(block_b), block_a.max(block_b));
+        let (head, tail) = self.blocks.split_at_mut(high);
+        let (low_offset, high_offset) = if block_a < block_b {
+            (offset_a, offset_b)
+        } else {
+            (offset_b, offset_a)
+        };
+
+        std::mem::swap(&mut head[low][low_offset], &mut tail[0][high_offset]);
+        Ok(())
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).any(|curr| curr.borrow() == elem))
+    }
+
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+
+        for (i, curr_elem) in List::iter(self).enumerate() {
+            if curr_elem.borrow() == elem {
+                res.push(i);
+            }
+        }
+
+        Ok(Some(res))
+    }
+
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).position(|curr| curr.borrow() == elem))
+    }
+
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).filter(|curr| (*curr).borrow() == elem).count())
+    }
+
+    fn clear(&mut self) -> Result<(), ListError> {
+        self.blocks.clear();
+        self.block_size = MIN_BLOCK_SIZE;
+        self.len = 0;
+
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            elems: self.blocks.iter().flatten(),
+            remaining: self.len
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            elems: self.blocks.iter_mut().flatten(),
+            remaining: self.len
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(list: &TieredVec<u64>) -> Vec<u64> {
+        List::iter(list).cloned().collect()
+    }
+
+    /* every block but the last is full and the length is consistent */
+    fn assert_invariants(list: &TieredVec<u64>) {
+        let (last, full) = match list.blocks.split_last() {
+            Some(split) => split,
+            None => {
+                assert_eq!(list.len, 0);
+                return;
+            }
+        };
+
+        assert!(full.iter().all(|block| block.len() == list.block_size));
+        assert!(!last.is_empty() && last.len() <= list.block_size);
+        assert_eq!(list.blocks.iter().map(|b| b.len()).sum::<usize>(),
+                   list.len);
+    }
+
+    #[test]
+    fn test_new_normal() -> Result<(), ListError> {
+        let actual_list: TieredVec<u64> = TieredVec::new();
+
+        assert_eq!(actual_list.length()?, 0);
+        assert_eq!(actual_list.block_size(), MIN_BLOCK_SIZE);
+        assert_invariants(&actual_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_middle() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = TieredVec::new();
+        let mut expected_elems: Vec<u64> = Vec::new();
+
+        for i in 0..500 {
+            let pos: usize = (i as usize * 7) % (expected_elems.len() + 1);
+
+            actual_list.insert(pos, i)?;
+            expected_elems.insert(pos, i);
+        }
+
+        assert_eq!(contents(&actual_list), expected_elems);
+        assert_invariants(&actual_list);
+        assert!(actual_list.block_size() >= 16);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = TieredVec::from(vec![1, 2]);
+
+        let actual_res: Result<(), ListError> = actual_list.insert(3, 4);
+        let expected_res: Result<(), ListError> = Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        assert_eq!(contents(&actual_list), vec![1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = (0..1000).collect();
+        let mut expected_elems: Vec<u64> = (0..1000).collect();
+
+        while expected_elems.len() > 3 {
+            let pos: usize = expected_elems.len() / 3;
+
+            assert_eq!(actual_list.remove(pos)?, expected_elems.remove(pos));
+        }
+
+        assert_eq!(contents(&actual_list), expected_elems);
+        assert_eq!(actual_list.block_size(), MIN_BLOCK_SIZE);
+        assert_invariants(&actual_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = TieredVec::new();
+
+        assert_eq!(actual_list.remove(0), Err(ListError::OutOfBounds));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = (0..100).collect();
+
+        *actual_list.get_mut(42)? = 0;
+
+        assert_eq!(actual_list.get(41)?, &41);
+        assert_eq!(actual_list[42], 0);
+        assert_eq!(actual_list.get(100), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_normal_across_blocks() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = (0..100).collect();
+
+        actual_list.swap(3, 97)?;
+        actual_list.swap(5, 4)?;
+
+        assert_eq!(actual_list[3], 97);
+        assert_eq!(actual_list[97], 3);
+        assert_eq!(actual_list[4], 5);
+        assert_eq!(actual_list[5], 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_normal() -> Result<(), ListError> {
+        let actual_list: TieredVec<u64> = TieredVec::from(vec![4, 1, 4, 2]);
+
+        assert_eq!(actual_list.find(&4)?, Some(0));
+        assert_eq!(actual_list.find_all(&4)?, Some(vec![0, 2]));
+        assert_eq!(actual_list.count(&4)?, 2);
+        assert!(!actual_list.contains(&3)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = (0..50).collect();
+
+        for elem in actual_list.iter_mut() {
+            *elem *= 2;
+        }
+
+        let actual_iter: Iter<u64> = List::iter(&actual_list);
+
+        assert_eq!(actual_iter.len(), 50);
+        assert_eq!(List::iter(&actual_list).next_back(), Some(&98));
+        assert_eq!(actual_list.into_iter().sum::<u64>(), 2450);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_normal() -> Result<(), ListError> {
+        let mut actual_list: TieredVec<u64> = (0..500).collect();
+
+        actual_list.clear()?;
+
+        assert_eq!(actual_list, TieredVec::new());
+        assert_invariants(&actual_list);
+
+        Ok(())
+    }
+}
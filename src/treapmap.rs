@@ -0,0 +1,714 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+use std::fmt;
+use crate::bst::{self, above, below, entry, find, find_mut, leftmost,
+                 rightmost};
+use crate::map::{Map, MapError, MapLookup, OrderedMap};
+use crate::rng::XorShiftRng;
+
+/* each node's `meta` is its heap priority */
+type Node<K, V> = bst::Node<K, V, u64>;
+type Link<K, V> = bst::Link<K, V, u64>;
+
+pub type Iter<'a, K, V> = bst::Iter<'a, K, V, u64>;
+pub type IterMut<'a, K, V> = bst::IterMut<'a, K, V, u64>;
+pub type IntoIter<K, V> = bst::IntoIter<K, V, u64>;
+
+/* a binary search tree on the keys that is also a max-heap on random
+ * priorities, so its shape is that of a tree built from a random insertion
+ * order whatever order the keys really arrive in: O(log n) expected depth,
+ * with `split` and `merge` in O(log n) expected as well */
+#[derive(Clone, Debug)]
+pub struct TreapMap<K, V> {
+    root: Link<K, V>,
+    len: usize,
+    rng: XorShiftRng
+}
+
+pub struct Range<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    end: Option<&'a Node<K, V>>
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node: &'a Node<K, V> = self.stack.pop()?;
+
+        if self.end.is_some_and(|end| std::ptr::eq(end, node)) {
+            self.stack.clear();
+            return None;
+        }
+
+        let mut link: &'a Link<K, V> = &node.right;
+
+        while let Some(next) = link {
+            self.stack.push(next);
+            link = &next.left;
+        }
+
+        Some((&node.key, &node.value))
+    }
+}
+
+/* splits `link` into the keys below `key` and the rest */
+fn split<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Link<K, V>)
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+    match link {
+        None => (None, None),
+        Some(mut node) => {
+            if node.key.borrow() < key {
+                let (below, rest) = split(node.right.take(), key);
+
+                node.right = below;
+                (Some(node), rest)
+            } else {
+                let (below, rest) = split(node.left.take(), key);
+
+                node.left = rest;
+                (below, Some(node))
+            }
+        }
+    }
+}
+
+/* joins two treaps where every key of `a` is below every key of `b` */
+fn join<K, V>(a: Link<K, V>, b: Link<K, V>) -> Link<K, V> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.meta >= b.meta {
+                a.right = join(a.right.take(), Some(b));
+                Some(a)
+            } else {
+                b.left = join(Some(a), b.left.take());
+                Some(b)
+            }
+        }
+    }
+}
+
+/* `node` must hold a key not yet in `link` */
+fn insert<K: Ord, V>(link: &mut Link<K, V>, mut node: Box<Node<K, V>>) {
+    match link {
+        Some(cur) if cur.meta >= node.meta => {
+            if node.key < cur.key {
+                insert(&mut cur.left, node);
+            } else {
+                insert(&mut cur.right, node);
+            }
+        },
+        _ => {
+            let (below, rest) = split(link.take(), &node.key);
+
+            node.left = below;
+            node.right = rest;
+            *link = Some(node);
+        }
+    }
+}
+
+fn remove<K, V, Q>(link: &mut Link<K, V>, key: &Q) -> Link<K, V> where
+    K: Borrow<Q>, Q: Ord + ?Sized {
+    let ord: Ordering = match link.as_ref() {
+        Some(node) => key.cmp(node.key.borrow()),
+        None => return None
+    };
+
+    match ord {
+        Ordering::Less => remove(&mut link.as_mut()?.left, key),
+        Ordering::Greater => remove(&mut link.as_mut()?.right, key),
+        Ordering::Equal => {
+            let mut node: Box<Node<K, V>> = link.take()?;
+
+            *link = join(node.left.take(), node.right.take());
+            Some(node)
+        }
+    }
+}
+
+/* a leftmost node has no left child, so its right subtree takes its place
+ * without disturbing the heap order */
+fn pop_first<K, V>(mut link: &mut Link<K, V>) -> Link<K, V> {
+    while link.as_ref().is_some_and(|node| node.left.is_some()) {
+        link = &mut link.as_mut()?.left;
+    }
+
+    let mut node: Box<Node<K, V>> = link.take()?;
+
+    *link = node.right.take();
+    Some(node)
+}
+
+fn pop_last<K, V>(mut link: &mut Link<K, V>) -> Link<K, V> {
+    while link.as_ref().is_some_and(|node| node.right.is_some()) {
+        link = &mut link.as_mut()?.right;
+    }
+
+    let mut node: Box<Node<K, V>> = link.take()?;
+
+    *link = node.left.take();
+    Some(node)
+}
+
+fn count<K, V>(link: &Link<K, V>) -> usize {
+    match link {
+        Some(node) => 1 + count(&node.left) + count(&node.right),
+        None => 0
+    }
+}
+
+impl<K, V> TreapMap<K, V> {
+    pub fn new() -> Self {
+        TreapMap::with_seed(0)
+    }
+
+    /* the seed fixes the priorities, and hence the shape of the tree */
+    pub fn with_seed(seed: u64) -> Self {
+        TreapMap {
+            root: None,
+            len: 0,
+            rng: XorShiftRng::new(seed)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.root, self.len)
+    }
+
+}
+
+impl<K: Ord, V> TreapMap<K, V> {
+    /* moves the entries with keys at or above `key` into a new map, which
+     * draws its priorities from a generator seeded off this one's; cutting
+     * the tree is O(log n) expected, but counting what was moved is O(m) */
+    pub fn split<Q>(&mut self, key: &Q) -> TreapMap<K, V> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        let (below, rest) = split(self.root.take(), key);
+        let moved: usize = count(&rest);
+
+        self.root = below;
+        self.len -= moved;
+
+        TreapMap {
+            root: rest,
+            len: moved,
+            rng: XorShiftRng::new(self.rng.next_u64())
+        }
+    }
+
+    /* moves every entry of `other` into this map: O(log n) expected when
+     * all of `other`'s keys lie above or all lie below this map's (as after
+     * a `split`), otherwise one insert per entry, with `other`'s values
+     * winning for keys in both */
+    pub fn merge(&mut self, mut other: TreapMap<K, V>) {
+        let len: usize = self.len + other.len;
+
+        let (first, last) = (leftmost(&self.root), rightmost(&self.root));
+
+        if leftmost(&other.root)
+            .is_some_and(|a| last.is_none_or(|b| b.key < a.key)) {
+            self.root = join(self.root.take(), other.root.take());
+            self.len = len;
+        } else if rightmost(&other.root)
+            .is_some_and(|a| first.is_none_or(|b| a.key < b.key)) {
+            self.root = join(other.root.take(), self.root.take());
+            self.len = len;
+        } else {
+            for (key, value) in other {
+                self.insert(key, value);
+            }
+        }
+    }
+
+    /* looks the key up before drawing a priority, so replacing a value
+     * leaves the generator, and the shape of later inserts, untouched */
+    fn insert(&mut self, key: K, value: V) -> bool {
+        if let Some(node) = find_mut(&mut self.root, &key) {
+            node.value = value;
+            return false;
+        }
+
+        let node: Box<Node<K, V>> = Box::new(Node {
+            key,
+            value,
+            meta: self.rng.next_u64(),
+            left: None,
+            right: None
+        });
+
+        insert(&mut self.root, node);
+        self.len += 1;
+        true
+    }
+}
+
+impl<K, V> Default for TreapMap<K, V> {
+    fn default() -> Self {
+        TreapMap::new()
+    }
+}
+
+impl<K, V> PartialEq for TreapMap<K, V> where K: Eq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for TreapMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for TreapMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        IntoIter::new(self.root.take(), self.len)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TreapMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for TreapMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: TreapMap<K, V> = TreapMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+impl<K, V> fmt::Display for TreapMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Map<K, V> for TreapMap<K, V> where K: Ord {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        TreapMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        self.root = None;
+        self.len = 0;
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        TreapMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut::new(&mut self.root, self.len)
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for TreapMap<K, V> where
+    K: Ord + Borrow<Q>, Q: Ord + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        find(&self.root, key).map(|node| &node.value)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        find_mut(&mut self.root, key).map(|node| &mut node.value)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        let node: Box<Node<K, V>> = remove(&mut self.root, key)?;
+
+        self.len -= 1;
+        Some(node.value)
+    }
+}
+
+impl<K, V> OrderedMap<K, V> for TreapMap<K, V> where K: Ord {
+    type Range<'a> = Range<'a, K, V> where K: 'a, V: 'a;
+
+    fn first(&self) -> Result<(&K, &V), MapError> {
+        entry(leftmost(&self.root))
+    }
+
+    fn last(&self) -> Result<(&K, &V), MapError> {
+        entry(rightmost(&self.root))
+    }
+
+    fn floor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(below(&self.root, key, true))
+    }
+
+    fn ceiling<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(above(&self.root, key, true))
+    }
+
+    fn predecessor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(below(&self.root, key, false))
+    }
+
+    fn successor<Q>(&self, key: &Q) -> Result<(&K, &V), MapError> where
+        K: Borrow<Q>, Q: Ord + ?Sized {
+        entry(above(&self.root, key, false))
+    }
+
+    /* starts from the path down to the first key in range and stops at
+     * the first key past it, so only the entries yielded are visited */
+    fn range<Q, R>(&self, range: R) -> Self::Range<'_> where K: Borrow<Q>,
+        Q: Ord + ?Sized, R: RangeBounds<Q> {
+        let mut stack: Vec<&Node<K, V>> = Vec::new();
+        let mut link: &Link<K, V> = &self.root;
+
+        while let Some(node) = link {
+            let after_start: bool = match range.start_bound() {
+                Bound::Included(start) => node.key.borrow() >= start,
+                Bound::Excluded(start) => node.key.borrow() > start,
+                Bound::Unbounded => true
+            };
+
+            if after_start {
+                stack.push(node);
+                link = &node.left;
+            } else {
+                link = &node.right;
+            }
+        }
+
+        let end: Option<&Node<K, V>> = match range.end_bound() {
+            Bound::Included(end) => above(&self.root, end, false),
+            Bound::Excluded(end) => above(&self.root, end, true),
+            Bound::Unbounded => None
+        };
+
+        /* an empty or inverted range */
+        if let (Some(first), Some(end)) = (stack.last(), end) {
+            if first.key > end.key {
+                stack.clear();
+            }
+        }
+
+        Range {
+            stack,
+            end
+        }
+    }
+
+    fn pop_first(&mut self) -> Result<(K, V), MapError> {
+        match pop_first(&mut self.root) {
+            Some(node) => {
+                self.len -= 1;
+                Ok((node.key, node.value))
+            },
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    fn pop_last(&mut self) -> Result<(K, V), MapError> {
+        match pop_last(&mut self.root) {
+            Some(node) => {
+                self.len -= 1;
+                Ok((node.key, node.value))
+            },
+            None => Err(MapError::KeyNotFound)
+        }
+    }
+
+    fn split_off<Q>(&mut self, key: &Q) -> Self where K: Borrow<Q>,
+        Q: Ord + ?Sized {
+        self.split(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(map: &TreapMap<u64, u64>) -> Vec<u64> {
+        map.iter().map(|(k, _)| *k).collect()
+    }
+
+    /* the heap order on priorities, and the height of the tree */
+    fn check(link: &Link<u64, u64>) -> usize {
+        match link {
+            Some(node) => {
+                for child in node.left.iter().chain(node.right.iter()) {
+                    assert!(child.meta <= node.meta);
+                }
+
+                1 + check(&node.left).max(check(&node.right))
+            },
+            None => 0
+        }
+    }
+
+    #[test]
+    fn test_set_normal_sorted_input() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> = TreapMap::with_seed(5);
+
+        for i in 0..4096 {
+            actual_map.set(i, i * 2)?;
+        }
+
+        assert_eq!(keys(&actual_map), (0..4096).collect::<Vec<u64>>());
+        assert_eq!(actual_map.get(&100)?, &200);
+        assert!(check(&actual_map.root) < 48);
+
+        actual_map.set(100, 0)?;
+
+        assert_eq!(actual_map.get(&100)?, &0);
+        assert_eq!(actual_map.size()?, 4096);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..300).map(|i| (i * 7 % 300, i)).collect();
+
+        for i in (0..300).filter(|i| i % 3 != 0) {
+            actual_map.remove(&i)?;
+        }
+
+        check(&actual_map.root);
+
+        assert_eq!(keys(&actual_map), (0..300).step_by(3).collect::<Vec<_>>());
+        assert_eq!(actual_map.remove(&1), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.size()?, 100);
+
+        Ok(())
+    }
+
+    /* keys that can be ordered but not hashed */
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct Version(u32, u32);
+
+    #[test]
+    fn test_get_normal_unhashable_key() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<Version, &str> = TreapMap::new();
+
+        actual_map.set(Version(1, 2), "b")?;
+        actual_map.set(Version(1, 0), "a")?;
+
+        assert_eq!(actual_map.get(&Version(1, 2))?, &"b");
+        assert!(actual_map.contains_key(&Version(1, 0))?);
+
+        actual_map.remove(&Version(1, 0))?;
+
+        assert_eq!(actual_map.get(&Version(1, 0)), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_normal() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+
+        let actual_rest: TreapMap<u64, u64> = actual_map.split(&40);
+
+        check(&actual_map.root);
+        check(&actual_rest.root);
+
+        assert_eq!(keys(&actual_map), (0..40).collect::<Vec<u64>>());
+        assert_eq!(keys(&actual_rest), (40..100).collect::<Vec<u64>>());
+        assert_eq!(actual_map.size()?, 40);
+        assert_eq!(actual_rest.size()?, 60);
+        assert!(actual_map.split(&1000).is_empty());
+        assert_eq!(actual_map.split(&0).len(), 40);
+        assert!(actual_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_normal() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..100).map(|i| (i, i)).collect();
+        let expected_map: TreapMap<u64, u64> = actual_map.clone();
+
+        let rest: TreapMap<u64, u64> = actual_map.split(&50);
+
+        /* the disjoint joins, in either order */
+        actual_map.merge(rest);
+
+        check(&actual_map.root);
+        assert_eq!(actual_map, expected_map);
+
+        let mut actual_high: TreapMap<u64, u64> = actual_map.split(&50);
+
+        actual_high.merge(std::mem::take(&mut actual_map));
+
+        check(&actual_high.root);
+        assert_eq!(actual_high, expected_map);
+
+        actual_map.merge(actual_high);
+
+        assert_eq!(actual_map, expected_map);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_normal_overlapping() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..10).step_by(2).map(|i| (i, 0)).collect();
+        let other: TreapMap<u64, u64> = (0..10).step_by(3).map(|i| (i, 1))
+            .collect();
+
+        actual_map.merge(other);
+
+        check(&actual_map.root);
+        assert_eq!(actual_map.to_string(),
+                   "{0: 1, 2: 0, 3: 1, 4: 0, 6: 1, 8: 0, 9: 1, }");
+        assert_eq!(actual_map.size()?, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_floor_normal_neighbours() -> Result<(), MapError> {
+        let actual_map: TreapMap<u64, u64> =
+            (10..100).step_by(10).map(|i| (i, i)).collect();
+
+        assert_eq!(actual_map.first()?, (&10, &10));
+        assert_eq!(actual_map.last()?, (&90, &90));
+        assert_eq!(actual_map.floor(&35)?, (&30, &30));
+        assert_eq!(actual_map.floor(&30)?, (&30, &30));
+        assert_eq!(actual_map.ceiling(&35)?, (&40, &40));
+        assert_eq!(actual_map.ceiling(&40)?, (&40, &40));
+        assert_eq!(actual_map.predecessor(&30)?, (&20, &20));
+        assert_eq!(actual_map.successor(&30)?, (&40, &40));
+        assert_eq!(actual_map.floor(&5), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.predecessor(&10), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.ceiling(&91), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.successor(&90), Err(MapError::KeyNotFound));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_normal() -> Result<(), MapError> {
+        let actual_map: TreapMap<u64, u64> =
+            (0..100).step_by(10).map(|i| (i, i)).collect();
+        let collect = |iter: Range<u64, u64>| -> Vec<u64> {
+            iter.map(|(k, _)| *k).collect()
+        };
+
+        assert_eq!(collect(actual_map.range(15..45)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(20..=40)), vec![20, 30, 40]);
+        assert_eq!(collect(actual_map.range(20..40)), vec![20, 30]);
+        assert_eq!(collect(actual_map.range(..15)), vec![0, 10]);
+        assert_eq!(collect(actual_map.range(85..)), vec![90]);
+        assert_eq!(collect(actual_map.range(41..49)), Vec::<u64>::new());
+        assert_eq!(collect(actual_map.range((Bound::Excluded(50),
+                                             Bound::Excluded(20)))),
+                   Vec::<u64>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_first_normal() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..100).map(|i| (i * 37 % 100, i)).collect();
+
+        for i in 0..50 {
+            assert_eq!(actual_map.pop_first()?.0, i);
+            assert_eq!(actual_map.pop_last()?.0, 99 - i);
+            check(&actual_map.root);
+        }
+
+        assert_eq!(actual_map.pop_first(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.pop_last(), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.size()?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_seed_normal_reproducible() -> Result<(), MapError> {
+        let a: TreapMap<u64, u64> = {
+            let mut map: TreapMap<u64, u64> = TreapMap::with_seed(9);
+            (0..64).for_each(|i| { map.insert(i, i); });
+            map
+        };
+        let b: TreapMap<u64, u64> = {
+            let mut map: TreapMap<u64, u64> = TreapMap::with_seed(9);
+            (0..64).for_each(|i| { map.insert(i, i); });
+            map
+        };
+
+        assert_eq!(a.root.as_ref().map(|n| n.key),
+                   b.root.as_ref().map(|n| n.key));
+        assert_eq!(check(&a.root), check(&b.root));
+        assert_eq!(a, b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), MapError> {
+        let mut actual_map: TreapMap<u64, u64> =
+            (0..50).rev().map(|i| (i, i)).collect();
+
+        for (key, value) in actual_map.iter_mut() {
+            *value = key * 10;
+        }
+
+        assert_eq!(actual_map.iter_mut().len(), 50);
+        assert_eq!(actual_map.into_iter().collect::<Vec<_>>(),
+                   (0..50).map(|i| (i, i * 10)).collect::<Vec<_>>());
+
+        Ok(())
+    }
+}
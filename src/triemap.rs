@@ -0,0 +1,670 @@
+use std::iter::FromIterator;
+use std::fmt;
+use std::mem;
+use crate::map::{Map, MapError, MapLookup};
+
+/* sorted by byte */
+type Children<K, V> = Vec<(u8, Box<Node<K, V>>)>;
+
+struct Node<K, V> {
+    entry: Option<(K, V)>,
+    children: Children<K, V>
+}
+
+/* a map over byte-string keys (`String`, `Vec<u8>`, `&str`, ...) with one
+ * node per distinct prefix, so everything below a prefix is one subtree:
+ * prefix queries cost O(prefix length) to find, and iteration runs in the
+ * lexicographic order of the bytes. lookups go by the bytes alone, so a
+ * `TrieMap<String, _>` answers to a `&str` or a `&[u8]` as well */
+#[derive(Clone)]
+pub struct TrieMap<K, V> {
+    root: Node<K, V>,
+    len: usize
+}
+
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    /* pre-order: a key sorts before every key it is a prefix of */
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node: &'a Node<K, V> = self.stack.pop()?;
+
+            self.stack.extend(node.children.iter().rev()
+                .map(|(_, child)| &**child));
+
+            if let Some((key, value)) = node.entry.as_ref() {
+                return Some((key, value));
+            }
+        }
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    stack: Vec<&'a mut Node<K, V>>
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Node { entry, children } = self.stack.pop()?;
+
+            self.stack.extend(children.iter_mut().rev()
+                .map(|(_, child)| &mut **child));
+
+            if let Some((key, value)) = entry.as_mut() {
+                return Some((&*key, value));
+            }
+        }
+    }
+}
+
+pub struct IntoIter<K, V> {
+    stack: Vec<Box<Node<K, V>>>
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut node: Box<Node<K, V>> = self.stack.pop()?;
+
+            self.stack.extend(mem::take(&mut node.children).into_iter().rev()
+                .map(|(_, child)| child));
+
+            if let Some(entry) = node.entry.take() {
+                return Some(entry);
+            }
+        }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Node {
+            entry: None,
+            children: Vec::new()
+        }
+    }
+
+    fn child(&self, byte: u8) -> Option<&Node<K, V>> {
+        match self.children.binary_search_by_key(&byte, |(b, _)| *b) {
+            Ok(i) => Some(&self.children[i].1),
+            Err(_) => None
+        }
+    }
+
+    fn child_mut(&mut self, byte: u8) -> Option<&mut Node<K, V>> {
+        match self.children.binary_search_by_key(&byte, |(b, _)| *b) {
+            Ok(i) => Some(&mut self.children[i].1),
+            Err(_) => None
+        }
+    }
+
+    fn count(&self) -> usize {
+        let mut res: usize = 0;
+        let mut stack: Vec<&Node<K, V>> = vec![self];
+
+        while let Some(node) = stack.pop() {
+            res += node.entry.is_some() as usize;
+            stack.extend(node.children.iter().map(|(_, child)| &**child));
+        }
+
+        res
+    }
+}
+
+/* long keys make deep chains, so they are taken apart a level at a time
+ * rather than by the recursive drop glue, and copied the same way below */
+impl<K, V> Drop for Node<K, V> {
+    fn drop(&mut self) {
+        let mut stack: Children<K, V> = mem::take(&mut self.children);
+
+        while let Some((_, mut node)) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+/* a node being copied, and the copies of its children made so far */
+type Frame<'a, K, V> = (&'a Node<K, V>, Children<K, V>);
+
+/* `done` carries each finished copy up to its parent's frame, which takes
+ * it as the child at the next index */
+impl<K: Clone, V: Clone> Clone for Node<K, V> {
+    fn clone(&self) -> Self {
+        let mut stack: Vec<Frame<'_, K, V>> = vec![(self, Vec::new())];
+        let mut done: Option<Node<K, V>> = None;
+
+        while let Some((node, mut children)) = stack.pop() {
+            if let Some(child) = done.take() {
+                children.push((node.children[children.len()].0,
+                               Box::new(child)));
+            }
+
+            match node.children.get(children.len()) {
+                Some((_, child)) => {
+                    stack.push((node, children));
+                    stack.push((child, Vec::new()));
+                },
+                None => done = Some(Node {
+                    entry: node.entry.clone(),
+                    children
+                })
+            }
+        }
+
+        done.unwrap_or_else(Node::new)
+    }
+}
+
+impl<K, V> TrieMap<K, V> {
+    pub fn new() -> Self {
+        TrieMap {
+            root: Node::new(),
+            len: 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /* in lexicographic order of the keys' bytes */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            stack: vec![&self.root]
+        }
+    }
+
+    /* the entries whose keys start with `prefix`, in order */
+    pub fn iter_prefix<P>(&self, prefix: &P) -> Iter<'_, K, V> where
+        P: AsRef<[u8]> + ?Sized {
+        Iter {
+            stack: self.find(prefix.as_ref()).into_iter().collect()
+        }
+    }
+
+    /* the entry with the longest key that is a prefix of `key` */
+    pub fn longest_prefix_match<P>(&self, key: &P) -> Option<(&K, &V)> where
+        P: AsRef<[u8]> + ?Sized {
+        let mut node: &Node<K, V> = &self.root;
+        let mut res: Option<&(K, V)> = node.entry.as_ref();
+
+        for byte in key.as_ref() {
+            node = match node.child(*byte) {
+                Some(child) => child,
+                None => break
+            };
+
+            if node.entry.is_some() {
+                res = node.entry.as_ref();
+            }
+        }
+
+        res.map(|(k, v)| (k, v))
+    }
+
+    /* removes every entry whose key starts with `prefix` and returns how
+     * many there were */
+    pub fn remove_prefix<P>(&mut self, prefix: &P) -> usize where
+        P: AsRef<[u8]> + ?Sized {
+        let bytes: &[u8] = prefix.as_ref();
+
+        if bytes.is_empty() {
+            let res: usize = self.len;
+
+            self.root = Node::new();
+            self.len = 0;
+            return res;
+        }
+
+        let res: usize = match self.detach(bytes) {
+            Some(node) => node.count(),
+            None => 0
+        };
+
+        self.len -= res;
+        res
+    }
+
+    fn find(&self, bytes: &[u8]) -> Option<&Node<K, V>> {
+        let mut node: &Node<K, V> = &self.root;
+
+        for byte in bytes {
+            node = node.child(*byte)?;
+        }
+
+        Some(node)
+    }
+
+    fn find_mut(&mut self, bytes: &[u8]) -> Option<&mut Node<K, V>> {
+        let mut node: &mut Node<K, V> = &mut self.root;
+
+        for byte in bytes {
+            node = node.child_mut(*byte)?;
+        }
+
+        Some(node)
+    }
+
+    /* cuts out the subtree at `bytes` (which must be non-empty) together
+     * with the run of ancestors that would be left with nothing else below
+     * them, and returns the top of what was cut */
+    fn detach(&mut self, bytes: &[u8]) -> Option<Box<Node<K, V>>> {
+        /* the deepest ancestor that keeps an entry or another branch */
+        let mut cut: usize = 0;
+        let mut node: &Node<K, V> = &self.root;
+
+        for (depth, byte) in bytes.iter().enumerate() {
+            if node.entry.is_some() || node.children.len() > 1 {
+                cut = depth;
+            }
+
+            node = node.child(*byte)?;
+        }
+
+        let parent: &mut Node<K, V> = self.find_mut(&bytes[..cut])?;
+
+        match parent.children.binary_search_by_key(&bytes[cut], |(b, _)| *b) {
+            Ok(i) => Some(parent.children.remove(i).1),
+            Err(_) => None
+        }
+    }
+
+    fn take(&mut self, bytes: &[u8]) -> Option<(K, V)> {
+        let target: &mut Node<K, V> = self.find_mut(bytes)?;
+
+        target.entry.as_ref()?;
+
+        /* a key that others extend only loses its entry */
+        let res: Option<(K, V)> = if bytes.is_empty() ||
+            !target.children.is_empty() {
+            target.entry.take()
+        } else {
+            let mut node: Box<Node<K, V>> = self.detach(bytes)?;
+            let mut node: &mut Node<K, V> = &mut node;
+
+            while node.entry.is_none() {
+                node = &mut node.children.first_mut()?.1;
+            }
+
+            node.entry.take()
+        };
+
+        self.len -= 1;
+        res
+    }
+}
+
+impl<K: AsRef<[u8]>, V> TrieMap<K, V> {
+    /* grows the byte path as needed; `true` if its end held no entry yet */
+    fn insert(&mut self, key: K, value: V) -> bool {
+        let mut node: &mut Node<K, V> = &mut self.root;
+
+        for byte in key.as_ref() {
+            let i: usize = match node.children
+                .binary_search_by_key(byte, |(b, _)| *b) {
+                Ok(i) => i,
+                Err(i) => {
+                    node.children.insert(i, (*byte, Box::new(Node::new())));
+                    i
+                }
+            };
+
+            node = &mut node.children[i].1;
+        }
+
+        let new: bool = node.entry.is_none();
+
+        node.entry = Some((key, value));
+
+        if new {
+            self.len += 1;
+        }
+
+        new
+    }
+}
+
+impl<K, V> Default for TrieMap<K, V> {
+    fn default() -> Self {
+        TrieMap::new()
+    }
+}
+
+impl<K, V> PartialEq for TrieMap<K, V> where K: Eq, V: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len &&
+            self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<K, V> Eq for TrieMap<K, V> where K: Eq, V: Eq {}
+
+impl<K, V> IntoIterator for TrieMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            stack: vec![Box::new(self.root)]
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TrieMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: AsRef<[u8]>, V> FromIterator<(K, V)> for TrieMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut res: TrieMap<K, V> = TrieMap::new();
+
+        for (key, value) in iter {
+            res.insert(key, value);
+        }
+
+        res
+    }
+}
+
+/* the entries, as the tree itself is too deep to print by recursion */
+impl<K, V> fmt::Debug for TrieMap<K, V> where K: fmt::Debug,
+    V: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> fmt::Display for TrieMap<K, V> where K: fmt::Display,
+    V: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+
+        for (key, value) in self.iter() {
+            write!(f, "{}: {}, ", key, value)?;
+        }
+
+        write!(f, "}}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, V> Map<K, V> for TrieMap<K, V> where K: Eq + AsRef<[u8]> {
+    type Iter<'a> = Iter<'a, K, V> where K: 'a, V: 'a;
+    type IterMut<'a> = IterMut<'a, K, V> where K: 'a, V: 'a;
+
+    fn new() -> Self {
+        TrieMap::new()
+    }
+
+    fn set(&mut self, key: K, value: V) -> Result<(), MapError> {
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn size(&self) -> Result<usize, MapError> {
+        Ok(self.len)
+    }
+
+    fn contains_value(&self, value: &V) -> Result<bool, MapError> where
+        V: Eq {
+        Ok(self.iter().any(|(_, v)| v == value))
+    }
+
+    fn clear(&mut self) -> Result<(), MapError> {
+        self.root = Node::new();
+        self.len = 0;
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        TrieMap::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            stack: vec![&mut self.root]
+        }
+    }
+}
+
+impl<K, V, Q> MapLookup<K, V, Q> for TrieMap<K, V> where
+    K: Eq + AsRef<[u8]>, Q: AsRef<[u8]> + ?Sized {
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.find(key.as_ref())
+            .and_then(|node| node.entry.as_ref())
+            .map(|(_, value)| value)
+    }
+
+    fn lookup_mut(&mut self, key: &Q) -> Option<&mut V> {
+        self.find_mut(key.as_ref())
+            .and_then(|node| node.entry.as_mut())
+            .map(|(_, value)| value)
+    }
+
+    fn lookup_remove(&mut self, key: &Q) -> Option<V> {
+        self.take(key.as_ref()).map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes<K, V>(map: &TrieMap<K, V>) -> usize {
+        let mut res: usize = 0;
+        let mut stack: Vec<&Node<K, V>> = vec![&map.root];
+
+        while let Some(node) = stack.pop() {
+            res += 1;
+            stack.extend(node.children.iter().map(|(_, child)| &**child));
+        }
+
+        res
+    }
+
+    #[test]
+    fn test_set_normal_lexicographic() -> Result<(), MapError> {
+        let mut actual_map: TrieMap<String, u64> = TrieMap::new();
+
+        for (i, word) in ["tea", "ten", "to", "", "inn", "in", "a"].iter()
+            .enumerate() {
+            actual_map.set(word.to_string(), i as u64)?;
+        }
+
+        actual_map.set(String::from("to"), 20)?;
+
+        let actual_keys: Vec<&str> = actual_map.iter()
+            .map(|(k, _)| k.as_str())
+            .collect();
+
+        assert_eq!(actual_keys, vec!["", "a", "in", "inn", "tea", "ten", "to"]);
+        assert_eq!(actual_map.get("to")?, &20);
+        assert_eq!(actual_map.get("")?, &3);
+        assert_eq!(actual_map.get("te"), Err(MapError::KeyNotFound));
+        assert!(!actual_map.contains_key("t")?);
+        assert_eq!(actual_map.size()?, 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_normal_bytes() -> Result<(), MapError> {
+        let mut actual_map: TrieMap<Vec<u8>, u64> = TrieMap::new();
+
+        actual_map.set(vec![0xff, 0x00], 1)?;
+        actual_map.set(vec![0x00, 0xff], 2)?;
+        actual_map.set(vec![0x00], 3)?;
+
+        assert_eq!(actual_map.get(&[0x00, 0xff][..])?, &2);
+        assert_eq!(actual_map.iter().map(|(_, v)| *v).collect::<Vec<u64>>(),
+                   vec![3, 2, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_prefix_match_normal() -> Result<(), MapError> {
+        let actual_routes: TrieMap<&str, u64> =
+            vec![("/", 0), ("/api", 1), ("/api/users", 2), ("/static", 3)]
+                .into_iter()
+                .collect();
+
+        assert_eq!(actual_routes.longest_prefix_match("/api/users/7"),
+                   Some((&"/api/users", &2)));
+        assert_eq!(actual_routes.longest_prefix_match("/api/user"),
+                   Some((&"/api", &1)));
+        assert_eq!(actual_routes.longest_prefix_match("/index.html"),
+                   Some((&"/", &0)));
+        assert_eq!(actual_routes.longest_prefix_match("api"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_prefix_normal() -> Result<(), MapError> {
+        let actual_map: TrieMap<&str, ()> =
+            ["car", "cart", "carbon", "cat", "dog"].iter()
+                .map(|w| (*w, ()))
+                .collect();
+
+        let actual_words: Vec<&str> = actual_map.iter_prefix("car")
+            .map(|(k, _)| *k)
+            .collect();
+
+        assert_eq!(actual_words, vec!["car", "carbon", "cart"]);
+        assert_eq!(actual_map.iter_prefix("ca").count(), 4);
+        assert_eq!(actual_map.iter_prefix("cow").count(), 0);
+        assert_eq!(actual_map.iter_prefix("").count(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal_prunes() -> Result<(), MapError> {
+        let mut actual_map: TrieMap<&str, u64> =
+            vec![("tea", 0), ("team", 1), ("ten", 2)].into_iter().collect();
+
+        assert_eq!(nodes(&actual_map), 6);
+
+        actual_map.remove("tea")?;
+
+        assert_eq!(nodes(&actual_map), 6);
+        assert_eq!(actual_map.get("team")?, &1);
+
+        actual_map.remove("team")?;
+
+        assert_eq!(nodes(&actual_map), 4);
+        assert_eq!(actual_map.remove("team"), Err(MapError::KeyNotFound));
+        assert_eq!(actual_map.remove("te"), Err(MapError::KeyNotFound));
+
+        actual_map.remove("ten")?;
+
+        assert_eq!(nodes(&actual_map), 1);
+        assert!(actual_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_prefix_normal() -> Result<(), MapError> {
+        let mut actual_map: TrieMap<&str, ()> =
+            ["car", "cart", "carbon", "cat", "dog"].iter()
+                .map(|w| (*w, ()))
+                .collect();
+
+        assert_eq!(actual_map.remove_prefix("cart"), 1);
+        assert_eq!(actual_map.remove_prefix("car"), 2);
+        assert_eq!(actual_map.remove_prefix("x"), 0);
+        assert_eq!(actual_map.size()?, 2);
+        assert_eq!(actual_map.iter().map(|(k, _)| *k).collect::<Vec<&str>>(),
+                   vec!["cat", "dog"]);
+        assert_eq!(nodes(&actual_map), 7);
+        assert_eq!(actual_map.remove_prefix(""), 2);
+        assert!(actual_map.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal_long_key() -> Result<(), MapError> {
+        let key: Vec<u8> = vec![b'a'; 200_000];
+        let mut actual_map: TrieMap<Vec<u8>, u64> = TrieMap::new();
+
+        actual_map.set(key.clone(), 1)?;
+        actual_map.set(key[..10].to_vec(), 2)?;
+        actual_map.remove(&key)?;
+
+        assert_eq!(nodes(&actual_map), 11);
+
+        actual_map.set(key, 3)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_normal_long_key() -> Result<(), MapError> {
+        let key: Vec<u8> = vec![b'a'; 200_000];
+        let mut actual_map: TrieMap<Vec<u8>, u64> = TrieMap::new();
+
+        actual_map.set(key.clone(), 1)?;
+        actual_map.set(b"ab".to_vec(), 2)?;
+
+        let actual_copy: TrieMap<Vec<u8>, u64> = actual_map.clone();
+
+        assert_eq!(actual_copy, actual_map);
+        assert_eq!(nodes(&actual_copy), nodes(&actual_map));
+        assert_eq!(actual_copy.get(&key)?, &1);
+        assert!(format!("{:?}", actual_copy)
+                    .ends_with("97, 97]: 1, [97, 98]: 2}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt_normal_debug() -> Result<(), MapError> {
+        let actual_map: TrieMap<&str, u64> =
+            vec![("b", 2), ("a", 1)].into_iter().collect();
+
+        assert_eq!(format!("{:?}", actual_map), "{\"a\": 1, \"b\": 2}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_mut_normal() -> Result<(), MapError> {
+        let mut actual_map: Box<dyn crate::map::DynMap<String, u64>> =
+            Box::new(TrieMap::new());
+
+        actual_map.set(String::from("b"), 2)?;
+        actual_map.set(String::from("a"), 1)?;
+
+        for (_, value) in actual_map.iter_mut() {
+            *value *= 10;
+        }
+
+        assert_eq!(actual_map.get(&String::from("a"))?, &10);
+        assert!(actual_map.contains_value(&20)?);
+
+        Ok(())
+    }
+}
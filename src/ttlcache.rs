@@ -0,0 +1,341 @@
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use crate::cache::{Cache, CacheError, CacheStats};
+use crate::lrucache::LruCache;
+
+/* a source of the current time, as an offset from some fixed start; it
+ * must never run backwards */
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+/* wall time since the clock was made */
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    start: Instant
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now()
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/* a clock that only moves when told to; clones share the same time, so
+ * one can be handed to a cache and the other kept to drive it */
+#[derive(Clone, Default, Debug)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/* a bounded map whose entries expire a fixed `ttl` after they were last
+ * put; entries are kept in the order they were put, which is also the
+ * order they expire in, so expired entries are purged from the old end as
+ * the cache is used, and a full cache evicts its oldest entry; `len`
+ * counts entries that have expired but not yet been purged */
+#[derive(Debug)]
+pub struct TtlCache<K, V, C = SystemClock> {
+    entries: LruCache<K, (Duration, V)>,
+    ttl: Duration,
+    clock: C,
+    stats: CacheStats
+}
+
+impl<K: Hash + Eq, V> TtlCache<K, V, SystemClock> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        TtlCache::with_clock(capacity, ttl, SystemClock::new())
+    }
+}
+
+impl<K: Hash + Eq, V, C: Clock> TtlCache<K, V, C> {
+    pub fn with_clock(capacity: usize, ttl: Duration, clock: C) -> Self {
+        TtlCache {
+            entries: LruCache::new(capacity),
+            ttl,
+            clock,
+            stats: CacheStats::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /* looks `key` up, counting it as a hit only if it has not expired */
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        self.purge_expired();
+
+        let res: Option<&V> = self.entries.peek(key).map(|(_, v)| v);
+
+        self.stats.record(res.is_some());
+        res
+    }
+
+    /* looks `key` up without counting it as a use */
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        let now: Duration = self.clock.now();
+
+        match self.entries.peek(key) {
+            Some((expiry, value)) if *expiry > now => Some(value),
+            _ => None
+        }
+    }
+
+    /* how long `key` has left to live */
+    pub fn time_to_live<Q>(&self, key: &Q) -> Option<Duration> where
+        K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let now: Duration = self.clock.now();
+
+        match self.entries.peek(key) {
+            Some((expiry, _)) if *expiry > now => Some(*expiry - now),
+            _ => None
+        }
+    }
+
+    /* inserts or replaces `key` with a fresh lifetime, returning the entry
+     * evicted to make room for it, if any; an entry that merely expired is
+     * not an eviction. an expiry past what a `Duration` holds is pinned to
+     * `Duration::MAX`, which the clock never reaches */
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        self.purge_expired();
+
+        let expiry: Duration = self.clock.now().saturating_add(self.ttl);
+
+        self.entries.put(key, (expiry, value)).map(|(k, (_, v))| (k, v))
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<(K, V)> where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized {
+        let now: Duration = self.clock.now();
+
+        match self.entries.pop(key) {
+            Some((key, (expiry, value))) if expiry > now => Some((key, value)),
+            _ => None
+        }
+    }
+
+    /* drops every expired entry, returning how many there were */
+    pub fn purge_expired(&mut self) -> usize {
+        let now: Duration = self.clock.now();
+        let mut res: usize = 0;
+
+        while let Some((_, (expiry, _))) = self.entries.peek_lru() {
+            if *expiry > now {
+                break;
+            }
+
+            self.entries.pop_lru();
+            res += 1;
+        }
+
+        res
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<K, V, C> Cache<K, V> for TtlCache<K, V, C> where K: Hash + Eq,
+    C: Clock {
+    fn get<Q>(&mut self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        TtlCache::get(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn peek<Q>(&self, key: &Q) -> Result<&V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        TtlCache::peek(self, key).ok_or(CacheError::KeyNotFound)
+    }
+
+    fn put(&mut self, key: K, value: V)
+        -> Result<Option<(K, V)>, CacheError> {
+        Ok(TtlCache::put(self, key, value))
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        match self.pop(key) {
+            Some((_, value)) => Ok(value),
+            None => Err(CacheError::KeyNotFound)
+        }
+    }
+
+    fn contains<Q>(&self, key: &Q) -> Result<bool, CacheError> where
+        K: Borrow<Q>, Q: Eq + Hash + ?Sized {
+        Ok(self.peek(key).is_some())
+    }
+
+    fn size(&self) -> Result<usize, CacheError> {
+        Ok(self.len())
+    }
+
+    fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    fn clear(&mut self) -> Result<(), CacheError> {
+        TtlCache::clear(self);
+        Ok(())
+    }
+
+    fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_get_normal_expires() -> Result<(), CacheError> {
+        let clock: ManualClock = ManualClock::new();
+        let mut actual_cache: TtlCache<&str, u64, ManualClock> =
+            TtlCache::with_clock(8, 10 * SECOND, clock.clone());
+
+        actual_cache.put("a", 1);
+        clock.advance(4 * SECOND);
+        actual_cache.put("b", 2);
+        clock.advance(5 * SECOND);
+
+        assert_eq!(actual_cache.get("a"), Some(&1));
+        assert_eq!(actual_cache.time_to_live("b"), Some(5 * SECOND));
+
+        clock.advance(SECOND);
+
+        assert_eq!(actual_cache.peek("a"), None);
+        assert_eq!(actual_cache.len(), 2);
+        assert_eq!(actual_cache.get("a"), None);
+        assert_eq!(actual_cache.len(), 1);
+        assert_eq!(actual_cache.get("b"), Some(&2));
+        assert_eq!(Cache::stats(&actual_cache),
+                   CacheStats { hits: 2, misses: 1 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_normal_unbounded_ttl() -> Result<(), CacheError> {
+        let clock: ManualClock = ManualClock::new();
+        let mut actual_cache: TtlCache<&str, u64, ManualClock> =
+            TtlCache::with_clock(8, Duration::MAX, clock.clone());
+
+        clock.advance(SECOND);
+        actual_cache.put("a", 1);
+        clock.advance(1000 * SECOND);
+
+        assert_eq!(actual_cache.get("a"), Some(&1));
+        assert_eq!(actual_cache.time_to_live("a"),
+                   Some(Duration::MAX - 1001 * SECOND));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_normal_refreshes() -> Result<(), CacheError> {
+        let clock: ManualClock = ManualClock::new();
+        let mut actual_cache: TtlCache<u64, u64, ManualClock> =
+            TtlCache::with_clock(2, 10 * SECOND, clock.clone());
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        clock.advance(6 * SECOND);
+
+        /* 1 is put again, so 2 is now the oldest */
+        assert_eq!(actual_cache.put(1, 11), None);
+        assert_eq!(actual_cache.put(3, 30), Some((2, 20)));
+
+        clock.advance(6 * SECOND);
+
+        assert_eq!(actual_cache.peek(&1), Some(&11));
+        assert_eq!(actual_cache.purge_expired(), 0);
+
+        clock.advance(4 * SECOND);
+
+        assert_eq!(actual_cache.purge_expired(), 2);
+        assert!(actual_cache.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_normal_expired_makes_room() -> Result<(), CacheError> {
+        let clock: ManualClock = ManualClock::new();
+        let mut actual_cache: TtlCache<u64, u64, ManualClock> =
+            TtlCache::with_clock(2, SECOND, clock.clone());
+
+        actual_cache.put(1, 10);
+        actual_cache.put(2, 20);
+        clock.advance(SECOND);
+
+        assert_eq!(actual_cache.put(3, 30), None);
+        assert_eq!(Cache::remove(&mut actual_cache, &1),
+                   Err(CacheError::KeyNotFound));
+        assert_eq!(Cache::remove(&mut actual_cache, &3)?, 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_normal() -> Result<(), CacheError> {
+        let mut actual_cache: Box<dyn crate::cache::DynCache<u64, u64>> =
+            Box::new(TtlCache::new(4, Duration::from_secs(3600)));
+
+        actual_cache.put(1, 10)?;
+
+        assert_eq!(actual_cache.get(&1)?, &10);
+        assert!(actual_cache.contains(&1)?);
+        assert_eq!(actual_cache.capacity(), 4);
+
+        actual_cache.clear()?;
+
+        assert_eq!(actual_cache.get(&1), Err(CacheError::KeyNotFound));
+        Ok(())
+    }
+}
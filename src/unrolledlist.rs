@@ -0,0 +1,965 @@
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut};
+use std::iter::{Flatten, FromIterator};
+use std::fmt;
+use std::fmt::Display;
+use crate::list::{List, ListError};
+
+struct Node<T> {
+    elems: Vec<T>,
+    next: Option<Box<Node<T>>>
+}
+
+/* evens out a pair of neighbouring nodes once either has dropped below half
+ * of `capacity`, emptying `b` entirely if both fit in `a`; returns whether
+ * `b` was emptied */
+fn rebalance<T>(a: &mut Vec<T>, b: &mut Vec<T>, capacity: usize) -> bool {
+    let half: usize = capacity / 2;
+
+    if a.len() >= half && b.len() >= half {
+        return false;
+    }
+
+    let total: usize = a.len() + b.len();
+
+    if total <= capacity {
+        a.append(b);
+        return true;
+    }
+
+    let target: usize = total / 2;
+
+    if a.len() < target {
+        a.extend(b.drain(..target - a.len()));
+    } else {
+        let mut moved: Vec<T> = a.split_off(target);
+        moved.append(b);
+        *b = moved;
+    }
+
+    false
+}
+
+impl<T> Node<T> {
+    fn with_elems(elems: Vec<T>, next: Option<Box<Node<T>>>) -> Box<Self> {
+        Box::new(Node {
+            elems,
+            next
+        })
+    }
+
+    /* restores the fill invariant between this node and its successor,
+     * unlinking the successor if it was merged into this one */
+    fn absorb_next(&mut self, capacity: usize) {
+        let merged: bool = match self.next.as_mut() {
+            Some(next) => rebalance(&mut self.elems, &mut next.elems, capacity),
+            None => false
+        };
+
+        if merged {
+            if let Some(mut next) = self.next.take() {
+                self.next = next.next.take();
+            }
+        }
+    }
+}
+
+/* a singly-linked list of nodes holding up to `N` elements each; every node
+ * is at least half full unless it is the only one, so indexing walks
+ * O(n / N) nodes while scans stay within contiguous memory */
+pub struct UnrolledList<T, const N: usize> {
+    head: Option<Box<Node<T>>>,
+    len: usize
+}
+
+pub struct Iter<'a, T> {
+    elems: Flatten<std::vec::IntoIter<&'a [T]>>,
+    remaining: usize
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a T> = self.elems.next();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a T> = self.elems.next_back();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    elems: Flatten<std::vec::IntoIter<&'a mut [T]>>,
+    remaining: usize
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a mut T> = self.elems.next();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let elem: Option<&'a mut T> = self.elems.next_back();
+
+        if elem.is_some() {
+            self.remaining -= 1;
+        }
+
+        elem
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    curr: std::vec::IntoIter<T>,
+    next: Option<Box<Node<T>>>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(elem) = self.curr.next() {
+                return Some(elem);
+            }
+
+            match self.next.take() {
+                Some(mut node) => {
+                    self.next = node.next.take();
+                    self.curr = std::mem::take(&mut node.elems).into_iter();
+                },
+                None => return None
+            }
+        }
+    }
+}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        let mut curr: Option<Box<Node<T>>> = self.next.take();
+
+        while let Some(mut node) = curr {
+            curr = node.next.take();
+        }
+    }
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    const CAPACITY_CHECK: () = assert!(N >= 2,
+                                       "node capacity must be at least 2");
+
+    /* leaves `[0, pos)` in `self` and returns the rest; only the node
+     * straddling `pos` has elements moved, the others are relinked */
+    pub fn split_off(&mut self, pos: usize) -> Result<Self, ListError> {
+        if pos > self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        if pos == self.len {
+            return Ok(UnrolledList::default());
+        }
+
+        let (k, offset) = match self.locate(pos) {
+            Some(loc) => loc,
+            None => return Err(ListError::Impossible)
+        };
+
+        let head: Option<Box<Node<T>>> = if offset == 0 {
+            if k == 0 {
+                self.head.take()
+            } else {
+                match self.node_mut(k - 1) {
+                    Some(prev) => prev.next.take(),
+                    None => return Err(ListError::Impossible)
+                }
+            }
+        } else {
+            match self.node_mut(k) {
+                Some(node) => {
+                    let mut elems: Vec<T> = Vec::with_capacity(N);
+                    elems.extend(node.elems.drain(offset..));
+
+                    Some(Node::with_elems(elems, node.next.take()))
+                },
+                None => return Err(ListError::Impossible)
+            }
+        };
+
+        let mut res: UnrolledList<T, N> = UnrolledList {
+            head,
+            len: self.len - pos
+        };
+
+        self.len = pos;
+        self.repair_tail();
+
+        if let Some(node) = res.head.as_mut() {
+            node.absorb_next(N);
+        }
+
+        Ok(res)
+    }
+
+    /* moves all of `other` onto the end of `self` by linking its nodes in
+     * after the current tail */
+    pub fn append_list(&mut self, mut other: Self) {
+        let len: usize = other.len;
+        let head: Option<Box<Node<T>>> = other.head.take();
+
+        other.len = 0;
+
+        match self.tail_mut() {
+            Some(tail) => {
+                tail.next = head;
+                tail.absorb_next(N);
+            },
+            None => self.head = head
+        }
+
+        self.len += len;
+    }
+
+    /* releases the spare slots of every node; nodes are never merged here,
+     * so positions and node boundaries are left as they are */
+    pub fn shrink_to_fit(&mut self) {
+        let mut curr: Option<&mut Node<T>> = self.head.as_deref_mut();
+
+        while let Some(node) = curr {
+            node.elems.shrink_to_fit();
+            curr = node.next.as_deref_mut();
+        }
+    }
+
+    /* node index and offset within it of the element at `pos` */
+    fn locate(&self, pos: usize) -> Option<(usize, usize)> {
+        let mut curr: Option<&Node<T>> = self.head.as_deref();
+        let mut start: usize = 0;
+        let mut k: usize = 0;
+
+        while let Some(node) = curr {
+            if pos < start + node.elems.len() {
+                return Some((k, pos - start));
+            }
+
+            start += node.elems.len();
+            k += 1;
+            curr = node.next.as_deref();
+        }
+
+        None
+    }
+
+    /* like `locate`, but positions one past the end of a node resolve to
+     * that node so that appends land in the tail */
+    fn locate_insert(&self, pos: usize) -> Option<(usize, usize)> {
+        let mut curr: Option<&Node<T>> = self.head.as_deref();
+        let mut start: usize = 0;
+        let mut k: usize = 0;
+
+        while let Some(node) = curr {
+            if pos <= start + node.elems.len() {
+                return Some((k, pos - start));
+            }
+
+            start += node.elems.len();
+            k += 1;
+            curr = node.next.as_deref();
+        }
+
+        None
+    }
+
+    fn node_mut(&mut self, k: usize) -> Option<&mut Node<T>> {
+        let mut curr: Option<&mut Node<T>> = self.head.as_deref_mut();
+
+        for _ in 0..k {
+            curr = curr?.next.as_deref_mut();
+        }
+
+        curr
+    }
+
+    fn tail_mut(&mut self) -> Option<&mut Node<T>> {
+        let mut curr: &mut Node<T> = self.head.as_deref_mut()?;
+
+        while curr.next.is_some() {
+            curr = curr.next.as_deref_mut()?;
+        }
+
+        Some(curr)
+    }
+
+    /* restores the fill invariant between the last two nodes */
+    fn repair_tail(&mut self) {
+        let mut curr: Option<&mut Node<T>> = self.head.as_deref_mut();
+
+        while let Some(node) = curr {
+            if node.next.as_ref().is_some_and(|next| next.next.is_none()) {
+                node.absorb_next(N);
+                return;
+            }
+
+            curr = node.next.as_deref_mut();
+        }
+    }
+
+    fn nodes(&self) -> Vec<&Node<T>> {
+        let mut res: Vec<&Node<T>> = Vec::new();
+        let mut curr: Option<&Node<T>> = self.head.as_deref();
+
+        while let Some(node) = curr {
+            res.push(node);
+            curr = node.next.as_deref();
+        }
+
+        res
+    }
+}
+
+impl<T, const N: usize> Default for UnrolledList<T, N> {
+    fn default() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CAPACITY_CHECK;
+
+        UnrolledList {
+            head: None,
+            len: 0
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for UnrolledList<T, N> {
+    /* unlinks iteratively so that long lists don't overflow the stack */
+    fn drop(&mut self) {
+        let mut curr: Option<Box<Node<T>>> = self.head.take();
+
+        while let Some(mut node) = curr {
+            curr = node.next.take();
+        }
+    }
+}
+
+impl<T, const N: usize> Clone for UnrolledList<T, N> where T: Clone {
+    fn clone(&self) -> Self {
+        List::iter(self).cloned().collect()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for UnrolledList<T, N> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(List::iter(self)).finish()
+    }
+}
+
+impl<T, const N: usize> PartialEq for UnrolledList<T, N> where T: Eq {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+
+        List::iter(self).zip(List::iter(other)).all(|(a, b)| a == b)
+    }
+}
+
+impl<T, const N: usize> Eq for UnrolledList<T, N> where T: Eq {}
+
+impl<T, const N: usize> IntoIterator for UnrolledList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        IntoIter {
+            curr: Vec::new().into_iter(),
+            next: self.head.take()
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a UnrolledList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter(self)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut UnrolledList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        List::iter_mut(self)
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledList<T, N> {
+    /* packs nodes full, evening out the last two so the final node isn't
+     * left underfull */
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut chunks: Vec<Vec<T>> = Vec::new();
+        let mut len: usize = 0;
+
+        for elem in iter {
+            if chunks.last().is_none_or(|chunk| chunk.len() == N) {
+                chunks.push(Vec::with_capacity(N));
+            }
+
+            if let Some(chunk) = chunks.last_mut() {
+                chunk.push(elem);
+                len += 1;
+            }
+        }
+
+        if let [.., a, b] = chunks.as_mut_slice() {
+            rebalance(a, b, N);
+        }
+
+        let mut res: UnrolledList<T, N> = UnrolledList::default();
+
+        for chunk in chunks.into_iter().rev() {
+            res.head = Some(Node::with_elems(chunk, res.head.take()));
+        }
+
+        res.len = len;
+        res
+    }
+}
+
+impl<T, const N: usize> Extend<T> for UnrolledList<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append_list(iter.into_iter().collect());
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for UnrolledList<T, N> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> Index<usize> for UnrolledList<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for UnrolledList<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+impl<T, const N: usize> Display for UnrolledList<T, N> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for elem in List::iter(self) {
+            write!(f, "{}, ", elem)?;
+        }
+
+        write!(f, "]")?;
+
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> List<T> for UnrolledList<T, N> {
+    type Iter<'a> = Iter<'a, T> where T: 'a;
+    type IterMut<'a> = IterMut<'a, T> where T: 'a;
+
+    fn new() -> Self {
+        UnrolledList::default()
+    }
+
+    /* nodes are allocated as the list grows, so there is nothing to set
+     * aside up front */
+    fn with_capacity(_capacity: usize) -> Self {
+        UnrolledList::default()
+    }
+
+    fn get(&self, pos: usize) -> Result<&T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let mut curr: Option<&Node<T>> = self.head.as_deref();
+        let mut pos: usize = pos;
+
+        while let Some(node) = curr {
+            if pos < node.elems.len() {
+                return Ok(&node.elems[pos]);
+            }
+
+            pos -= node.elems.len();
+            curr = node.next.as_deref();
+        }
+
+        Err(ListError::Impossible)
+    }
+
+    fn get_mut(&mut self, pos: usize) -> Result<&mut T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (k, offset) = match self.locate(pos) {
+            Some(loc) => loc,
+            None => return Err(ListError::Impossible)
+        };
+
+        match self.node_mut(k) {
+            Some(node) => Ok(&mut node.elems[offset]),
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn set(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        *self.get_mut(pos)? = elem;
+        Ok(())
+    }
+
+    fn insert(&mut self, pos: usize, elem: T) -> Result<(), ListError> {
+        if pos > self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (k, offset) = match self.locate_insert(pos) {
+            Some(loc) => loc,
+            None => {
+                let mut elems: Vec<T> = Vec::with_capacity(N);
+                elems.push(elem);
+
+                self.head = Some(Node::with_elems(elems, None));
+                self.len += 1;
+
+                return Ok(());
+            }
+        };
+
+        let node: &mut Node<T> = match self.node_mut(k) {
+            Some(node) => node,
+            None => return Err(ListError::Impossible)
+        };
+
+        if node.elems.len() == N { /* split full nodes in half */
+            let mut elems: Vec<T> = Vec::with_capacity(N);
+            elems.extend(node.elems.drain(N / 2..));
+            node.next = Some(Node::with_elems(elems, node.next.take()));
+
+            if offset > N / 2 {
+                match node.next.as_mut() {
+                    Some(next) => next.elems.insert(offset - N / 2, elem),
+                    None => return Err(ListError::Impossible)
+                }
+            } else {
+                node.elems.insert(offset, elem);
+            }
+        } else {
+            node.elems.insert(offset, elem);
+        }
+
+        self.len += 1;
+        Ok(())
+    }
+
+    fn remove(&mut self, pos: usize) -> Result<T, ListError> {
+        if pos >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (k, offset) = match self.locate(pos) {
+            Some(loc) => loc,
+            None => return Err(ListError::Impossible)
+        };
+
+        let (elem, has_next, empty) = match self.node_mut(k) {
+            Some(node) => {
+                let elem: T = node.elems.remove(offset);
+                (elem, node.next.is_some(), node.elems.is_empty())
+            },
+            None => return Err(ListError::Impossible)
+        };
+
+        self.len -= 1;
+
+        if has_next {
+            if let Some(node) = self.node_mut(k) {
+                node.absorb_next(N);
+            }
+        } else if k > 0 {
+            if let Some(prev) = self.node_mut(k - 1) {
+                prev.absorb_next(N);
+            }
+        } else if empty {
+            self.head = None;
+        }
+
+        Ok(elem)
+    }
+
+    fn length(&self) -> Result<usize, ListError> {
+        Ok(self.len)
+    }
+
+    fn append(&mut self, elem: T) -> Result<(), ListError> {
+        let len: usize = self.len;
+        self.insert(len, elem)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) -> Result<(), ListError> {
+        if a >= self.len || b >= self.len { /* bounds check */
+            return Err(ListError::OutOfBounds);
+        }
+
+        let (low, high) = (a.min(b), a.max(b));
+
+        let ((low_node, low_offset), (high_node, high_offset)) =
+            match (self.locate(low), self.locate(high)) {
+                (Some(low), Some(high)) => (low, high),
+                _ => return Err(ListError::Impossible)
+            };
+
+        let node: &mut Node<T> = match self.node_mut(low_node) {
+            Some(node) => node,
+            None => return Err(ListError::Impossible)
+        };
+
+        if low_node == high_node {
+            node.elems.swap(low_offset, high_offset);
+            return Ok(());
+        }
+
+        /* walk on from the first node, borrowing its elements and its link
+         * separately */
+        let elems: &mut Vec<T> = &mut node.elems;
+        let mut curr: Option<&mut Node<T>> = node.next.as_deref_mut();
+
+        for _ in low_node + 1..high_node {
+            curr = curr.and_then(|node| node.next.as_deref_mut());
+        }
+
+        match curr {
+            Some(other) => {
+                std::mem::swap(&mut elems[low_offset],
+                               &mut other.elems[high_offset]);
+                Ok(())
+            },
+            None => Err(ListError::Impossible)
+        }
+    }
+
+    fn contains<Q>(&self, elem: &Q) -> Result<bool, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).any(|curr| curr.borrow() == elem))
+    }
+
+    fn find_all<Q>(&self, elem: &Q) -> Result<Option<Vec<usize>>, ListError>
+        where T: Borrow<Q>, Q: Eq + ?Sized {
+        let mut res: Vec<usize> = Vec::new();
+
+        for (i, curr_elem) in List::iter(self).enumerate() {
+            if curr_elem.borrow() == elem {
+                res.push(i);
+            }
+        }
+
+        Ok(Some(res))
+    }
+
+    fn find<Q>(&self, elem: &Q) -> Result<Option<usize>, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).position(|curr| curr.borrow() == elem))
+    }
+
+    fn count<Q>(&self, elem: &Q) -> Result<usize, ListError> where
+        T: Borrow<Q>, Q: Eq + ?Sized {
+        Ok(List::iter(self).filter(|curr| (*curr).borrow() == elem).count())
+    }
+
+    fn clear(&mut self) -> Result<(), ListError> {
+        *self = UnrolledList::default();
+        Ok(())
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        let slices: Vec<&[T]> = self.nodes()
+            .into_iter()
+            .map(|node| node.elems.as_slice())
+            .collect();
+
+        Iter {
+            elems: slices.into_iter().flatten(),
+            remaining: self.len
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let mut slices: Vec<&mut [T]> = Vec::new();
+        let mut curr: Option<&mut Node<T>> = self.head.as_deref_mut();
+
+        while let Some(node) = curr {
+            slices.push(node.elems.as_mut_slice());
+            curr = node.next.as_deref_mut();
+        }
+
+        IterMut {
+            elems: slices.into_iter().flatten(),
+            remaining: self.len
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bubblesort::bubblesort;
+
+    fn cmp_leq<T: Eq + Ord>(a: &T, b: &T) -> bool {
+        a <= b
+    }
+
+    fn contents<const N: usize>(list: &UnrolledList<u64, N>) -> Vec<u64> {
+        List::iter(list).cloned().collect()
+    }
+
+    /* nodes never exceed `N` and are at least half full unless alone */
+    fn assert_invariants<const N: usize>(list: &UnrolledList<u64, N>) {
+        let nodes: Vec<&Node<u64>> = list.nodes();
+
+        assert!(nodes.iter().all(|node| node.elems.len() <= N));
+        assert_eq!(nodes.iter().map(|node| node.elems.len()).sum::<usize>(),
+                   list.len);
+
+        if nodes.len() > 1 {
+            assert!(nodes.iter().all(|node| node.elems.len() >= N / 2));
+        } else if let Some(node) = nodes.first() {
+            assert!(!node.elems.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_new_normal() -> Result<(), ListError> {
+        let actual_list: UnrolledList<u64, 4> = UnrolledList::new();
+
+        assert_eq!(actual_list.length()?, 0);
+        assert!(actual_list.head.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_normal_middle() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = UnrolledList::new();
+        let mut expected_elems: Vec<u64> = Vec::new();
+
+        for i in 0..300 {
+            let pos: usize = (i as usize * 13) % (expected_elems.len() + 1);
+
+            actual_list.insert(pos, i)?;
+            expected_elems.insert(pos, i);
+            assert_invariants(&actual_list);
+        }
+
+        assert_eq!(contents(&actual_list), expected_elems);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_error_out_of_bounds() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = UnrolledList::new();
+
+        let actual_res: Result<(), ListError> = actual_list.insert(1, 4);
+        let expected_res: Result<(), ListError> = Err(ListError::OutOfBounds);
+
+        assert_eq!(actual_res, expected_res);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_normal() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 5> = (0..200).collect();
+        let mut expected_elems: Vec<u64> = (0..200).collect();
+
+        while !expected_elems.is_empty() {
+            let pos: usize = expected_elems.len() / 2;
+
+            assert_eq!(actual_list.remove(pos)?, expected_elems.remove(pos));
+            assert_invariants(&actual_list);
+        }
+
+        assert!(actual_list.head.is_none());
+        assert_eq!(actual_list.remove(0), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_normal() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 8> = (0..100).collect();
+
+        *actual_list.get_mut(42)? = 0;
+
+        assert_eq!(actual_list.get(41)?, &41);
+        assert_eq!(actual_list[42], 0);
+        assert_eq!(actual_list.get(100), Err(ListError::OutOfBounds));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap_normal_across_nodes() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = (0..20).collect();
+
+        actual_list.swap(17, 2)?;
+        actual_list.swap(0, 1)?;
+
+        assert_eq!(contents(&actual_list),
+                   vec![1, 0, 17, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+                        16, 2, 18, 19]);
+
+        bubblesort(&mut actual_list, cmp_leq)?;
+
+        assert_eq!(contents(&actual_list), (0..20).collect::<Vec<u64>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal() -> Result<(), ListError> {
+        for pos in 0..=50 {
+            let mut actual_list: UnrolledList<u64, 4> = (0..50).collect();
+
+            let actual_tail: UnrolledList<u64, 4> =
+                actual_list.split_off(pos)?;
+
+            assert_eq!(contents(&actual_list),
+                       (0..pos as u64).collect::<Vec<u64>>());
+            assert_eq!(contents(&actual_tail),
+                       (pos as u64..50).collect::<Vec<u64>>());
+            assert_invariants(&actual_list);
+            assert_invariants(&actual_tail);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_off_normal_relinks() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 8> = (0..64).collect();
+        let tail_ptrs: Vec<*const u64> = actual_list.nodes()[4..]
+            .iter()
+            .map(|node| node.elems.as_ptr())
+            .collect();
+
+        let actual_tail: UnrolledList<u64, 8> = actual_list.split_off(32)?;
+        let actual_ptrs: Vec<*const u64> = actual_tail.nodes()
+            .iter()
+            .map(|node| node.elems.as_ptr())
+            .collect();
+
+        assert_eq!(actual_ptrs, tail_ptrs);
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_list_normal() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = (0..9).collect();
+        let other: UnrolledList<u64, 4> = (9..30).collect();
+        let other_ptrs: Vec<*const u64> = other.nodes()[1..]
+            .iter()
+            .map(|node| node.elems.as_ptr())
+            .collect();
+
+        actual_list.append_list(other);
+
+        let actual_ptrs: Vec<*const u64> = actual_list.nodes()
+            .iter()
+            .map(|node| node.elems.as_ptr())
+            .collect();
+
+        assert_eq!(contents(&actual_list), (0..30).collect::<Vec<u64>>());
+        assert!(other_ptrs.iter().all(|ptr| actual_ptrs.contains(ptr)));
+        assert_invariants(&actual_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_list_normal_small() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = UnrolledList::new();
+
+        actual_list.append_list(UnrolledList::from(vec![1]));
+        actual_list.append_list(UnrolledList::new());
+        actual_list.append_list(UnrolledList::from(vec![2]));
+        actual_list.extend(vec![3, 4, 5]);
+
+        assert_eq!(contents(&actual_list), vec![1, 2, 3, 4, 5]);
+        assert_eq!(actual_list.length()?, 5);
+        assert_invariants(&actual_list);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_normal() -> Result<(), ListError> {
+        let mut actual_list: UnrolledList<u64, 4> = (0..50).collect();
+
+        for elem in actual_list.iter_mut() {
+            *elem *= 2;
+        }
+
+        assert_eq!(List::iter(&actual_list).len(), 50);
+        assert_eq!(List::iter(&actual_list).next_back(), Some(&98));
+        assert_eq!(actual_list.clone().into_iter().sum::<u64>(), 2450);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_normal_long() -> Result<(), ListError> {
+        let actual_list: UnrolledList<u64, 2> = (0..1_000_000).collect();
+
+        assert_eq!(actual_list.length()?, 1_000_000);
+        Ok(())
+    }
+}